@@ -1,6 +1,10 @@
-use crate::{CoreStage, Plugin, PluginGroup, PluginGroupBuilder, StartupSchedule, StartupStage};
+use crate::{
+	sub_app::SubApp, CoreStage, DuplicatePlugins, PlaceholderPlugin, Plugin, PluginError,
+	PluginGroup, PluginGroupBuilder, PluginsState, StartupSchedule, StartupStage,
+};
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
+	component::{ComponentInfo, ForgetComponentsError},
 	event::{Event, Events},
 	prelude::{FromWorld, IntoExclusiveSystem},
 	schedule::{
@@ -10,8 +14,15 @@ use bevy_ecs::{
 	system::Resource,
 	world::World,
 };
-use bevy_utils::{tracing::debug, HashMap};
-use std::fmt::Debug;
+use bevy_utils::{
+	tracing::{debug, warn},
+	HashMap, HashSet,
+};
+use std::{
+	any::{Any, TypeId},
+	fmt::Debug,
+	sync::Arc,
+};
 
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
@@ -58,16 +69,15 @@ pub struct App {
 	/// the application's event loop and advancing the [`Schedule`].
 	/// Typically, it is not configured manually, but set by one of Bevy's built-in plugins.
 	/// See `bevy::winit::WinitPlugin` and [`ScheduleRunnerPlugin`](crate::schedule_runner::ScheduleRunnerPlugin).
-	pub runner: Box<dyn Fn(App)>,
+	pub runner: Box<dyn Fn(App) + Send>,
 	/// A container of [`Stage`]s set to be run in a linear order.
 	pub schedule: Schedule,
 	sub_apps: HashMap<AppLabelId, SubApp>,
-}
-
-/// Each `SubApp` has its own [`Schedule`] and [`World`], enabling a separation of concerns.
-struct SubApp {
-	app: App,
-	runner: Box<dyn Fn(&mut World, &mut App)>,
+	/// The added plugins, in the order they were added.
+	plugin_registry: Vec<Box<dyn Plugin>>,
+	added_plugin_ids: HashSet<TypeId>,
+	duplicate_plugins: DuplicatePlugins,
+	plugins_state: PluginsState,
 }
 
 impl Default for App {
@@ -105,31 +115,41 @@ impl App {
 			schedule: Default::default(),
 			runner: Box::new(run_once),
 			sub_apps: HashMap::default(),
+			plugin_registry: Vec::new(),
+			added_plugin_ids: HashSet::default(),
+			duplicate_plugins: DuplicatePlugins::default(),
+			plugins_state: PluginsState::Adding,
 		}
 	}
 
 	/// Advances the execution of the [`Schedule`] by one cycle.
 	///
-	/// This method also updates sub apps.
+	/// This method also updates sub apps. The plugins are [finished](Self::finish) and
+	/// [cleaned up](Self::cleanup) first if they weren't yet.
 	///
 	/// See [`add_sub_app`](Self::add_sub_app) and [`run_once`](Schedule::run_once) for more details.
 	pub fn update(&mut self) {
 		#[cfg(feature = "trace")]
 		let _bevy_frame_update_span = info_span!("frame").entered();
+		self.finish();
+		self.cleanup();
 		self.schedule.run(&mut self.world);
 		for sub_app in self.sub_apps.values_mut() {
-			(sub_app.runner)(&mut self.world, &mut sub_app.app);
+			sub_app.update(&mut self.world);
 		}
 	}
 
 	/// Starts the application by calling the app's [runner function](Self::set_runner).
 	///
-	/// Finalizes the [`App`] configuration. For general usage, see the example on the item
+	/// Finalizes the [`App`] configuration, [finishing](Self::finish) and
+	/// [cleaning up](Self::cleanup) its plugins. For general usage, see the example on the item
 	/// level documentation.
 	pub fn run(&mut self) {
 		#[cfg(feature = "trace")]
 		let _bevy_app_run_span = info_span!("bevy_app").entered();
 
+		self.finish();
+		self.cleanup();
 		let mut app = std::mem::replace(self, App::empty());
 		let runner = std::mem::replace(&mut app.runner, Box::new(run_once));
 		(runner)(app);
@@ -734,7 +754,7 @@ impl App {
 	/// App::new()
 	///     .set_runner(my_runner);
 	/// ```
-	pub fn set_runner(&mut self, run_fn: impl Fn(App) + 'static) {
+	pub fn set_runner(&mut self, run_fn: impl Fn(App) + Send + 'static) {
 		self.runner = Box::new(run_fn);
 	}
 
@@ -762,12 +782,270 @@ impl App {
 	/// # }
 	/// App::new().add_plugin(bevy_log::LogPlugin::default());
 	/// ```
+	///
+	/// # Panics
+	///
+	/// Panics if a dependency of the plugin wasn't added before it, or if the plugin is
+	/// [unique](Plugin::is_unique) and was already added while duplicate plugins are
+	/// [errors](Self::set_duplicate_plugins). See [`add_boxed_plugin`](Self::add_boxed_plugin)
+	/// to handle these errors.
 	pub fn add_plugin<T>(&mut self, plugin: T)
 	where
 		T: Plugin,
 	{
+		if let Err(err) = self.add_boxed_plugin(Box::new(plugin)) {
+			panic!("{}", err);
+		}
+	}
+
+	/// Adds a single boxed [`Plugin`], see [`add_plugin`](Self::add_plugin).
+	///
+	/// A [unique](Plugin::is_unique) plugin already added is ignored with a warning, unless
+	/// [configured](Self::set_duplicate_plugins) otherwise.
+	pub fn add_boxed_plugin(&mut self, plugin: Box<dyn Plugin>) -> Result<(), PluginError> {
+		let type_id = plugin_type_id(&*plugin);
+		if plugin.is_unique() && self.is_plugin_added_by_id(type_id) {
+			return match self.duplicate_plugins {
+				DuplicatePlugins::Error => Err(PluginError::DuplicatePlugin {
+					plugin_name: plugin.name().to_string(),
+				}),
+				DuplicatePlugins::Warn => {
+					warn!("ignored duplicate plugin: {}", plugin.name());
+					Ok(())
+				},
+				DuplicatePlugins::Ignore => {
+					debug!("ignored duplicate plugin: {}", plugin.name());
+					Ok(())
+				},
+			};
+		}
+		if let Some(dependency) = plugin
+			.dependencies()
+			.into_iter()
+			.find(|dependency| !self.is_plugin_added_by_id(dependency.type_id()))
+		{
+			return Err(PluginError::MissingDependency {
+				plugin_name: plugin.name().to_string(),
+				dependency_name: dependency.name(),
+			});
+		}
+		if self.plugins_state != PluginsState::Adding {
+			warn!(
+				"plugin {} was added after the plugins were finished, its `finish` and `cleanup` won't run",
+				plugin.name()
+			);
+		}
+
 		debug!("added plugin: {}", plugin.name());
+		// The plugin holds its place while it is built, so the plugins it adds are added after
+		// it and can't add it again.
+		let index = self.plugin_registry.len();
+		self
+			.plugin_registry
+			.push(Box::new(PlaceholderPlugin));
+		self.added_plugin_ids.insert(type_id);
 		plugin.build(self);
+		self.plugin_registry[index] = plugin;
+		Ok(())
+	}
+
+	/// Returns `true` if a plugin of type `T` was added.
+	pub fn is_plugin_added<T: Plugin>(&self) -> bool {
+		self.is_plugin_added_by_id(TypeId::of::<T>())
+	}
+
+	fn is_plugin_added_by_id(&self, type_id: TypeId) -> bool {
+		self.added_plugin_ids.contains(&type_id)
+	}
+
+	/// Removes the plugins matching `predicate` from the app, returning them.
+	///
+	/// The systems and resources the plugins added are kept, see
+	/// [`remove_systems`](Self::remove_systems). This is mostly useful to unload dynamically
+	/// loaded plugins.
+	pub fn remove_plugins(
+		&mut self,
+		mut predicate: impl FnMut(&dyn Plugin) -> bool,
+	) -> Vec<Box<dyn Plugin>> {
+		let (removed, kept) = std::mem::take(&mut self.plugin_registry)
+			.into_iter()
+			.partition(|plugin| predicate(&**plugin));
+		self.plugin_registry = kept;
+		for plugin in &removed {
+			self
+				.added_plugin_ids
+				.remove(&plugin_type_id(&**plugin));
+		}
+		removed
+	}
+
+	/// Removes the systems whose name matches `predicate` from the schedule of the app and of its
+	/// sub apps, returning the number of removed systems.
+	///
+	/// See [`Schedule::remove_systems`].
+	pub fn remove_systems(&mut self, mut predicate: impl FnMut(&str) -> bool) -> usize {
+		self.remove_systems_inner(&mut predicate)
+	}
+
+	fn remove_systems_inner(&mut self, predicate: &mut dyn FnMut(&str) -> bool) -> usize {
+		let mut removed = self.schedule.remove_systems(&mut *predicate);
+		for sub_app in self.sub_apps.values_mut() {
+			removed += sub_app
+				.app_mut()
+				.remove_systems_inner(predicate);
+		}
+		removed
+	}
+
+	/// Removes the resources whose [`ComponentInfo`] matches `predicate` from the world of the app
+	/// and of its sub apps, returning the number of removed resources.
+	pub fn remove_resources(&mut self, mut predicate: impl FnMut(&ComponentInfo) -> bool) -> usize {
+		self.remove_resources_inner(&mut predicate)
+	}
+
+	fn remove_resources_inner(&mut self, predicate: &mut dyn FnMut(&ComponentInfo) -> bool) -> usize {
+		let resource_ids: Vec<_> = self
+			.world
+			.components()
+			.iter()
+			.filter(|info| predicate(info))
+			.map(|info| info.id())
+			.collect();
+		let mut removed = resource_ids
+			.into_iter()
+			.filter_map(|id| self.world.remove_resource_by_id(id))
+			.count();
+		for sub_app in self.sub_apps.values_mut() {
+			removed += sub_app
+				.app_mut()
+				.remove_resources_inner(predicate);
+		}
+		removed
+	}
+
+	/// Returns the types of the components and resources registered in the world of the app and
+	/// of its sub apps.
+	pub fn component_type_ids(&mut self) -> HashSet<TypeId> {
+		let mut type_ids: HashSet<TypeId> = self
+			.world
+			.components()
+			.iter()
+			.filter_map(ComponentInfo::type_id)
+			.collect();
+		for sub_app in self.sub_apps.values_mut() {
+			type_ids.extend(sub_app.app_mut().component_type_ids());
+		}
+		type_ids
+	}
+
+	/// Removes the resources and forgets the component and resource types whose [`ComponentInfo`]
+	/// matches `predicate` in the world of the app and of its sub apps, returning the number of
+	/// forgotten types, see [`World::forget_components`].
+	///
+	/// Nothing is removed or forgotten if an entity of one of the worlds still has one of the
+	/// components.
+	pub fn forget_components(
+		&mut self,
+		mut predicate: impl FnMut(&ComponentInfo) -> bool,
+	) -> Result<usize, ForgetComponentsError> {
+		self.check_components_unused(&mut predicate)?;
+		self.remove_resources_inner(&mut predicate);
+		self.forget_components_inner(&mut predicate)
+	}
+
+	fn check_components_unused(
+		&mut self,
+		predicate: &mut dyn FnMut(&ComponentInfo) -> bool,
+	) -> Result<(), ForgetComponentsError> {
+		let archetypes = self.world.archetypes();
+		if let Some(info) = self
+			.world
+			.components()
+			.iter()
+			.filter(|info| predicate(info))
+			.find(|info| {
+				archetypes
+					.iter()
+					.any(|archetype| archetype.contains(info.id()) && !archetype.is_empty())
+			}) {
+			return Err(ForgetComponentsError::InUse(info.name().to_string()));
+		}
+		for sub_app in self.sub_apps.values_mut() {
+			sub_app
+				.app_mut()
+				.check_components_unused(predicate)?;
+		}
+		Ok(())
+	}
+
+	fn forget_components_inner(
+		&mut self,
+		predicate: &mut dyn FnMut(&ComponentInfo) -> bool,
+	) -> Result<usize, ForgetComponentsError> {
+		let mut forgotten = self.world.forget_components(&mut *predicate)?;
+		for sub_app in self.sub_apps.values_mut() {
+			forgotten += sub_app
+				.app_mut()
+				.forget_components_inner(predicate)?;
+		}
+		Ok(forgotten)
+	}
+
+	/// Sets what [`add_plugin`](Self::add_plugin) does when a [unique](Plugin::is_unique) plugin
+	/// is added again, ignoring it with a warning by default.
+	pub fn set_duplicate_plugins(&mut self, duplicate_plugins: DuplicatePlugins) {
+		self.duplicate_plugins = duplicate_plugins;
+	}
+
+	/// Returns the configuration phase of the plugins of the app.
+	pub fn plugins_state(&self) -> PluginsState {
+		self.plugins_state
+	}
+
+	/// Runs [`Plugin::finish`] for all the plugins, in the order they were added, and for the
+	/// plugins of the sub apps, once all the plugins are added.
+	///
+	/// It is called by [`run`](Self::run) and [`update`](Self::update), and only has an effect
+	/// the first time.
+	pub fn finish(&mut self) {
+		if self.plugins_state != PluginsState::Adding {
+			return;
+		}
+		self.plugins_state = PluginsState::Finished;
+		self.for_each_plugin(|plugin, app| plugin.finish(app));
+		for sub_app in self.sub_apps.values_mut() {
+			sub_app.app_mut().finish();
+		}
+	}
+
+	/// Runs [`Plugin::cleanup`] for all the plugins, in the order they were added, and for the
+	/// plugins of the sub apps, once they are [finished](Self::finish).
+	///
+	/// It is called by [`run`](Self::run) and [`update`](Self::update), and only has an effect
+	/// the first time.
+	pub fn cleanup(&mut self) {
+		if self.plugins_state != PluginsState::Finished {
+			return;
+		}
+		self.plugins_state = PluginsState::Cleaned;
+		self.for_each_plugin(|plugin, app| plugin.cleanup(app));
+		for sub_app in self.sub_apps.values_mut() {
+			sub_app.app_mut().cleanup();
+		}
+	}
+
+	/// Calls `f` for each plugin in order, including the plugins added by previous calls.
+	fn for_each_plugin(&mut self, f: impl Fn(&dyn Plugin, &mut App)) {
+		let mut index = 0;
+		while index < self.plugin_registry.len() {
+			let plugin = std::mem::replace(
+				&mut self.plugin_registry[index],
+				Box::new(PlaceholderPlugin),
+			);
+			f(&*plugin, self);
+			self.plugin_registry[index] = plugin;
+			index += 1;
+		}
 	}
 
 	/// Alternatyive to add_plugin(default());
@@ -900,26 +1178,149 @@ impl App {
 		}
 	}
 
+	/// Registers `alias` as another name of type `T` in the [`TypeRegistry`](bevy_reflect::TypeRegistry)
+	/// resource, so saved data using the old name of a renamed type still deserializes.
+	///
+	/// See [`bevy_reflect::TypeRegistry::register_type_alias`].
+	#[cfg(feature = "bevy_reflect")]
+	pub fn register_type_alias<T: bevy_reflect::Reflect + 'static>(
+		&mut self,
+		alias: impl Into<String>,
+	) {
+		let registry = self
+			.world
+			.resource_mut::<bevy_reflect::TypeRegistryArc>();
+		registry.write().register_type_alias::<T>(alias);
+	}
+
+	/// Sets the version of type `T` in the [`TypeRegistry`](bevy_reflect::TypeRegistry) resource.
+	///
+	/// See [`bevy_reflect::TypeRegistration::set_version`].
+	#[cfg(feature = "bevy_reflect")]
+	pub fn set_type_version<T: bevy_reflect::Reflect + 'static>(&mut self, version: u32) {
+		let registry = self
+			.world
+			.resource_mut::<bevy_reflect::TypeRegistryArc>();
+		registry.write().set_type_version::<T>(version);
+	}
+
+	/// Adds a migration of the reflected values of type `T` from version `from_version` to the next
+	/// one in the [`TypeRegistry`](bevy_reflect::TypeRegistry) resource.
+	///
+	/// See [`bevy_reflect::TypeRegistration::add_migration`].
+	#[cfg(feature = "bevy_reflect")]
+	pub fn add_type_migration<T: bevy_reflect::Reflect + 'static>(
+		&mut self,
+		from_version: u32,
+		migration: impl Fn(Box<dyn bevy_reflect::Reflect>) -> Box<dyn bevy_reflect::Reflect>
+			+ Send
+			+ Sync
+			+ 'static,
+	) {
+		let registry = self
+			.world
+			.resource_mut::<bevy_reflect::TypeRegistryArc>();
+		registry
+			.write()
+			.add_type_migration::<T>(from_version, migration);
+	}
+
 	/// Adds an [`App`] as a child of the current one.
 	///
 	/// The provided function `f` is called by the [`update`](Self::update) method. The [`World`]
 	/// parameter represents the main app world, while the [`App`] parameter is just a mutable
 	/// reference to the `SubApp` itself.
+	///
+	/// A sub app added this way can't be [pipelined](Self::set_sub_app_pipelined), see
+	/// [`add_sub_app_with_extract`](Self::add_sub_app_with_extract).
 	pub fn add_sub_app(
 		&mut self,
 		label: impl AppLabel,
 		app: App,
-		sub_app_runner: impl Fn(&mut World, &mut App) + 'static,
+		sub_app_runner: impl Fn(&mut World, &mut App) + Send + 'static,
 	) {
+		let label = label.as_label();
 		self.sub_apps.insert(
-			label.as_label(),
-			SubApp {
-				app,
-				runner: Box::new(sub_app_runner),
-			},
+			label,
+			SubApp::new(label, app, Box::new(sub_app_runner), None),
 		);
 	}
 
+	/// Adds an [`App`] as a child of the current one, updated in two steps.
+	///
+	/// The [`update`](Self::update) method first calls `extract` with the main app world, which
+	/// is the only point where the sub app can read it, then calls `run` to advance the sub app.
+	/// If the sub app is [pipelined](Self::set_sub_app_pipelined), `run` is called on a separate
+	/// thread while the main app updates the next frame.
+	pub fn add_sub_app_with_extract(
+		&mut self,
+		label: impl AppLabel,
+		app: App,
+		extract: impl Fn(&mut World, &mut App) + Send + 'static,
+		run: impl Fn(&mut App) + Send + Sync + 'static,
+	) {
+		let label = label.as_label();
+		self.sub_apps.insert(
+			label,
+			SubApp::new(label, app, Box::new(extract), Some(Arc::new(run))),
+		);
+	}
+
+	/// Sets whether the sub app with the given `label` runs on a thread of its own.
+	///
+	/// A pipelined sub app runs frame `N` while the main app updates frame `N + 1`: each
+	/// [`update`](Self::update) waits for the sub app to finish the previous frame, extracts the
+	/// current frame and hands the sub app over to its thread. That thread becomes the
+	/// [main thread](World::set_main_thread) of the sub app world, so only the `run` step can
+	/// access its non-send data.
+	///
+	/// This has no effect on sub apps added with [`add_sub_app`](Self::add_sub_app), which
+	/// can't be split in an extract and a run step, nor on `wasm32`, where there are no threads.
+	///
+	/// # Panics
+	///
+	/// Panics if the sub app doesn't exist, or if its world contains non-send resources.
+	pub fn set_sub_app_pipelined(&mut self, label: impl AppLabel, pipelined: bool) {
+		let label = label.as_label();
+		let sub_app = match self.sub_apps.get_mut(&label) {
+			Some(sub_app) => sub_app,
+			None => panic!("Sub-App with label '{:?}' does not exist", label.as_str()),
+		};
+		if pipelined && !sub_app.can_be_pipelined() {
+			warn!(
+				"Sub-App with label '{:?}' has no separate run step and can't be pipelined",
+				label.as_str()
+			);
+			return;
+		}
+		if pipelined && cfg!(target_arch = "wasm32") {
+			warn!(
+				"Sub-App with label '{:?}' can't be pipelined without threads",
+				label.as_str()
+			);
+			return;
+		}
+		sub_app.set_pipelined(pipelined);
+	}
+
+	/// Returns whether the sub app with the given `label` exists and is
+	/// [pipelined](Self::set_sub_app_pipelined).
+	pub fn is_sub_app_pipelined(&self, label: impl AppLabel) -> bool {
+		matches!(self.sub_apps.get(&label.as_label()), Some(sub_app) if sub_app.is_pipelined())
+	}
+
+	/// Waits for the [pipelined](Self::set_sub_app_pipelined) sub apps to finish running their
+	/// current frame.
+	///
+	/// # Panics
+	///
+	/// Panics if a sub app panicked on its thread.
+	pub fn wait_for_sub_apps(&mut self) {
+		for sub_app in self.sub_apps.values_mut() {
+			sub_app.app_mut();
+		}
+	}
+
 	/// Retrieves a `SubApp` stored inside this [`App`].
 	///
 	/// # Panics
@@ -934,12 +1335,15 @@ impl App {
 
 	/// Retrieves a `SubApp` inside this [`App`] with the given label, if it exists. Otherwise returns
 	/// an [`Err`] containing the given label.
+	///
+	/// A [pipelined](Self::set_sub_app_pipelined) sub app is only returned once it has finished
+	/// running its current frame.
 	pub fn get_sub_app_mut(&mut self, label: impl AppLabel) -> Result<&mut App, AppLabelId> {
 		let label = label.as_label();
 		self
 			.sub_apps
 			.get_mut(&label)
-			.map(|sub_app| &mut sub_app.app)
+			.map(SubApp::app_mut)
 			.ok_or(label)
 	}
 
@@ -947,25 +1351,37 @@ impl App {
 	///
 	/// # Panics
 	///
-	/// Panics if the `SubApp` doesn't exist.
+	/// Panics if the `SubApp` doesn't exist, or if it's [pipelined](Self::set_sub_app_pipelined)
+	/// and still running on its thread.
 	pub fn sub_app(&self, label: impl AppLabel) -> &App {
 		match self.get_sub_app(label) {
 			Ok(app) => app,
-			Err(label) => panic!("Sub-App with label '{:?}' does not exist", label.as_str()),
+			Err(label) => panic!(
+				"Sub-App with label '{:?}' does not exist or is running on its thread",
+				label.as_str()
+			),
 		}
 	}
 
-	/// Retrieves a `SubApp` inside this [`App`] with the given label, if it exists. Otherwise returns
-	/// an [`Err`] containing the given label.
+	/// Retrieves a `SubApp` inside this [`App`] with the given label, if it exists and isn't
+	/// running on its thread. Otherwise returns an [`Err`] containing the given label.
+	///
+	/// Use [`wait_for_sub_apps`](Self::wait_for_sub_apps) to access a
+	/// [pipelined](Self::set_sub_app_pipelined) sub app.
 	pub fn get_sub_app(&self, label: impl AppLabel) -> Result<&App, impl AppLabel> {
 		self
 			.sub_apps
 			.get(&label.as_label())
-			.map(|sub_app| &sub_app.app)
+			.and_then(SubApp::app)
 			.ok_or(label)
 	}
 }
 
+/// Gets the [`TypeId`] of the concrete type of a plugin.
+fn plugin_type_id(plugin: &dyn Plugin) -> TypeId {
+	(plugin as &dyn Any).type_id()
+}
+
 fn run_once(mut app: App) {
 	app.update();
 }
@@ -978,3 +1394,284 @@ fn run_once(mut app: App) {
 /// frame is over.
 #[derive(Debug, Clone, Default)]
 pub struct AppExit;
+
+#[cfg(test)]
+mod tests {
+	use crate::{App, DuplicatePlugins, Plugin, PluginError, PluginId, PluginsState};
+	use bevy_ecs::component::{Component, ComponentInfo, ForgetComponentsError};
+	use std::{
+		any::TypeId,
+		sync::{mpsc, Mutex},
+		thread::ThreadId,
+	};
+
+	struct PluginA;
+	impl Plugin for PluginA {
+		fn build(&self, _app: &mut App) {}
+	}
+
+	struct PluginB;
+	impl Plugin for PluginB {
+		fn build(&self, _app: &mut App) {}
+		fn dependencies(&self) -> Vec<PluginId> {
+			vec![PluginId::of::<PluginA>()]
+		}
+	}
+
+	struct NonUniquePlugin;
+	impl Plugin for NonUniquePlugin {
+		fn build(&self, _app: &mut App) {}
+		fn is_unique(&self) -> bool {
+			false
+		}
+	}
+
+	#[derive(Default)]
+	struct Phases(Vec<&'static str>);
+
+	struct PhasesPlugin;
+	impl Plugin for PhasesPlugin {
+		fn build(&self, app: &mut App) {
+			app.init_resource::<Phases>();
+			app
+				.world
+				.resource_mut::<Phases>()
+				.0
+				.push("build");
+		}
+		fn finish(&self, app: &mut App) {
+			app
+				.world
+				.resource_mut::<Phases>()
+				.0
+				.push("finish");
+		}
+		fn cleanup(&self, app: &mut App) {
+			app
+				.world
+				.resource_mut::<Phases>()
+				.0
+				.push("cleanup");
+		}
+	}
+
+	#[test]
+	fn duplicate_plugins() {
+		let mut app = App::new();
+		app.add_plugin(PluginA);
+		assert!(app.is_plugin_added::<PluginA>());
+		assert!(!app.is_plugin_added::<PluginB>());
+		// ignored by default
+		app.add_plugin(PluginA);
+		assert_eq!(app.plugin_registry.len(), 1);
+
+		app.set_duplicate_plugins(DuplicatePlugins::Error);
+		assert_eq!(
+			app.add_boxed_plugin(Box::new(PluginA)),
+			Err(PluginError::DuplicatePlugin {
+				plugin_name: std::any::type_name::<PluginA>().to_string()
+			})
+		);
+
+		app.set_duplicate_plugins(DuplicatePlugins::Ignore);
+		app.add_plugin(PluginA);
+		app.add_plugin(NonUniquePlugin);
+		app.add_plugin(NonUniquePlugin);
+		assert_eq!(app.plugin_registry.len(), 3);
+	}
+
+	#[test]
+	fn remove_plugins() {
+		let mut app = App::new();
+		app.add_plugin(PluginA);
+		app.add_plugin(PluginB);
+		let removed = app.remove_plugins(|plugin| plugin.name().ends_with("PluginB"));
+		assert_eq!(removed.len(), 1);
+		assert!(app.is_plugin_added::<PluginA>());
+		assert!(!app.is_plugin_added::<PluginB>());
+		app.add_plugin(PluginB);
+	}
+
+	#[derive(Component)]
+	struct Marker;
+
+	#[test]
+	fn forget_components() {
+		let mut app = App::empty();
+		app.insert_resource(Frame(0));
+		add_recording_sub_app(&mut app, |_| {});
+		let entity = app
+			.sub_app_mut(SUB_APP)
+			.world
+			.spawn()
+			.insert(Marker)
+			.id();
+		let is_forgotten =
+			|info: &ComponentInfo| info.name().ends_with("::Frame") || info.name().ends_with("::Marker");
+
+		let type_ids = app.component_type_ids();
+		assert!(type_ids.contains(&TypeId::of::<Frame>()));
+		assert!(type_ids.contains(&TypeId::of::<Marker>()));
+
+		assert_eq!(
+			app.forget_components(is_forgotten),
+			Err(ForgetComponentsError::InUse(
+				std::any::type_name::<Marker>().to_string()
+			))
+		);
+		assert!(app.world.contains_resource::<Frame>());
+
+		app.sub_app_mut(SUB_APP).world.despawn(entity);
+		assert_eq!(app.forget_components(is_forgotten), Ok(2));
+		assert!(!app
+			.component_type_ids()
+			.contains(&TypeId::of::<Marker>()));
+		assert!(!app.world.contains_resource::<Frame>());
+		assert_eq!(
+			app
+				.world
+				.components()
+				.get_resource_id(TypeId::of::<Frame>()),
+			None
+		);
+		assert_eq!(
+			app
+				.sub_app(SUB_APP)
+				.world
+				.components()
+				.component_id::<Marker>(),
+			None
+		);
+	}
+
+	#[test]
+	fn plugin_dependencies() {
+		let mut app = App::new();
+		assert_eq!(
+			app.add_boxed_plugin(Box::new(PluginB)),
+			Err(PluginError::MissingDependency {
+				plugin_name: std::any::type_name::<PluginB>().to_string(),
+				dependency_name: std::any::type_name::<PluginA>(),
+			})
+		);
+		app.add_plugin(PluginA);
+		app.add_plugin(PluginB);
+		assert!(app.is_plugin_added::<PluginB>());
+	}
+
+	#[test]
+	fn plugin_phases() {
+		let mut app = App::new();
+		app.add_plugin(PhasesPlugin);
+		assert_eq!(app.plugins_state(), PluginsState::Adding);
+		app.update();
+		app.update();
+		assert_eq!(app.plugins_state(), PluginsState::Cleaned);
+		assert_eq!(
+			app.world.resource::<Phases>().0,
+			["build", "finish", "cleanup"]
+		);
+	}
+
+	const SUB_APP: &str = "sub_app";
+
+	struct Frame(u32);
+
+	#[derive(Default)]
+	struct Runs(Vec<(u32, ThreadId)>);
+
+	/// Adds a sub app extracting the [`Frame`] of the main world, and recording the frame and
+	/// thread of each run.
+	fn add_recording_sub_app(app: &mut App, run: impl Fn(&mut App) + Send + Sync + 'static) {
+		let mut sub_app = App::empty();
+		sub_app.init_resource::<Runs>();
+		app.add_sub_app_with_extract(
+			SUB_APP,
+			sub_app,
+			|world, sub_app| {
+				let frame = world.resource::<Frame>().0;
+				sub_app.insert_resource(Frame(frame));
+			},
+			move |sub_app| {
+				run(sub_app);
+				let frame = sub_app.world.resource::<Frame>().0;
+				sub_app
+					.world
+					.resource_mut::<Runs>()
+					.0
+					.push((frame, std::thread::current().id()));
+			},
+		);
+	}
+
+	fn next_frame(app: &mut App) {
+		app.world.resource_mut::<Frame>().0 += 1;
+		app.update();
+	}
+
+	#[test]
+	fn pipelined_sub_app() {
+		let mut app = App::empty();
+		app.insert_resource(Frame(0));
+		add_recording_sub_app(&mut app, |_| {});
+		next_frame(&mut app);
+		app.set_sub_app_pipelined(SUB_APP, true);
+		assert!(app.is_sub_app_pipelined(SUB_APP));
+		next_frame(&mut app);
+		next_frame(&mut app);
+		app.set_sub_app_pipelined(SUB_APP, false);
+		next_frame(&mut app);
+
+		let main_thread = std::thread::current().id();
+		let runs = &app.sub_app(SUB_APP).world.resource::<Runs>().0;
+		assert_eq!(
+			runs
+				.iter()
+				.map(|(frame, _)| *frame)
+				.collect::<Vec<_>>(),
+			[1, 2, 3, 4]
+		);
+		assert_eq!(runs[0].1, main_thread);
+		assert_ne!(runs[1].1, main_thread);
+		assert_eq!(runs[1].1, runs[2].1);
+		assert_eq!(runs[3].1, main_thread);
+	}
+
+	#[test]
+	fn pipelined_sub_app_runs_alongside_main_app() {
+		let (release_sender, release_receiver) = mpsc::channel::<()>();
+		let release_receiver = Mutex::new(release_receiver);
+		let mut app = App::empty();
+		app.insert_resource(Frame(0));
+		add_recording_sub_app(&mut app, move |_| {
+			release_receiver.lock().unwrap().recv().unwrap();
+		});
+		app.set_sub_app_pipelined(SUB_APP, true);
+
+		// the sub app waits for the release of frame 1 while the main app updates
+		next_frame(&mut app);
+		assert!(app.get_sub_app(SUB_APP).is_err());
+		app.world.resource_mut::<Frame>().0 += 1;
+		release_sender.send(()).unwrap();
+		release_sender.send(()).unwrap();
+		app.update();
+
+		app.wait_for_sub_apps();
+		let runs = &app.sub_app(SUB_APP).world.resource::<Runs>().0;
+		assert_eq!(
+			runs
+				.iter()
+				.map(|(frame, _)| *frame)
+				.collect::<Vec<_>>(),
+			[1, 2]
+		);
+	}
+
+	#[test]
+	fn sub_app_without_run_step_is_not_pipelined() {
+		let mut app = App::empty();
+		app.add_sub_app(SUB_APP, App::empty(), |_, _| {});
+		app.set_sub_app_pipelined(SUB_APP, true);
+		assert!(!app.is_sub_app_pipelined(SUB_APP));
+	}
+}
@@ -1,31 +1,455 @@
-use crate::{app::AppExit, App};
-use serde::Deserialize;
-
-use bevy_utils::tracing::info;
+use crate::{app::AppExit, App, CoreStage};
+use bevy_ecs::{
+	event::{Event, EventReader, EventWriter, Events},
+	prelude::IntoExclusiveSystem,
+	schedule::{ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion, SystemLabel},
+	system::{Res, ResMut},
+	world::World,
+};
+use bevy_utils::{
+	get_short_name,
+	tracing::{error, info},
+	Duration, HashMap,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+	any::type_name,
+	collections::{BTreeMap, VecDeque},
+	io::{self, BufRead, Write},
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
+};
 
 /// A configuration struct for automated CI testing.
 ///
 /// It gets used when the `bevy_ci_testing` feature is enabled to automatically
 /// exit a Bevy app when run through the CI. This is needed because otherwise
 /// Bevy apps would be stuck in the game loop and wouldn't allow the CI to progress.
+///
+/// The configuration can also script events, capture frames and fail the run on errors, to
+/// smoke test an app:
+///
+/// ```ron
+/// (
+///     exit_after: Some(120),
+///     events: [
+///         (frame: 30, event: "KeyboardInput", value: "(scan_code: 57, key_code: Some(Space), state: Pressed)"),
+///         (frame: 31, event: "KeyboardInput", value: "(scan_code: 57, key_code: Some(Space), state: Released)"),
+///     ],
+///     capture_frames: [60, 119],
+///     fail_on_error: true,
+///     fail_on_panic: true,
+/// )
+/// ```
 #[derive(Deserialize)]
 pub struct CiTestingConfig {
 	/// The number of frames after which Bevy should exit.
 	pub exit_after: Option<u32>,
+	/// Record the input events and time deltas of every frame into this file.
+	pub record: Option<String>,
+	/// Replay the input events and time deltas recorded in this file, then exit.
+	pub replay: Option<String>,
+	/// Events to send at specific frames.
+	#[serde(default)]
+	pub events: Vec<CiTestingEvent>,
+	/// The frames to capture, see [`CiTestingFrameCapture`]. Only the Vulkan and DX12 backends
+	/// support frame captures, an error is logged for each capture on the other backends.
+	#[serde(default)]
+	pub capture_frames: Vec<u32>,
+	/// The prefix of the paths of the frame captures, followed by the frame number and `.png`.
+	/// Defaults to `capture-`.
+	pub capture_prefix: Option<String>,
+	/// Fail the run as soon as an error is logged by the `LogPlugin` of `bevy_log`.
+	#[serde(default)]
+	pub fail_on_error: bool,
+	/// Exit with a failure code as soon as any thread panics, including the threads running
+	/// systems.
+	#[serde(default)]
+	pub fail_on_panic: bool,
+}
+
+/// An event sent by the [`CiTestingConfig`] at a given frame.
+///
+/// The event type must either be recorded with [`App::add_recorded_event`], such as the input and
+/// window events, or be registered for reflection with `#[reflect(Event)]`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CiTestingEvent {
+	/// The frame during which the event is sent, starting at 0.
+	pub frame: u32,
+	/// The full or short type name of the event.
+	pub event: String,
+	/// The event as RON: serialized with serde for the recorded events, or in the reflection
+	/// format of scenes, including the type name, for the reflected events.
+	pub value: String,
+}
+
+/// An event asking to capture the frame rendered during the current frame into a PNG file, sent
+/// for the [`capture_frames`](CiTestingConfig::capture_frames) of the [`CiTestingConfig`].
+///
+/// The captures are done by `bevy_render`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CiTestingFrameCapture {
+	/// The path of the PNG file.
+	pub path: String,
+}
+
+/// The current frame of a CI testing run, starting at 0.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CiTestingFrame(pub u32);
+
+/// The number of errors logged during a CI testing run, counted by the `LogPlugin` of `bevy_log`
+/// and checked when [`fail_on_error`](CiTestingConfig::fail_on_error) is set.
+#[derive(Debug, Default, Clone)]
+pub struct CiTestingErrorCount(Arc<AtomicUsize>);
+
+impl CiTestingErrorCount {
+	/// Counts an error.
+	pub fn increment(&self) {
+		self.0.fetch_add(1, Ordering::Relaxed);
+	}
+
+	/// The number of errors counted so far.
+	pub fn get(&self) -> usize {
+		self.0.load(Ordering::Relaxed)
+	}
+}
+
+/// The labels of the systems recording and replaying frames.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub enum CiTestingSystem {
+	/// Adds the events of the current frame to the [`InputRecorder`].
+	RecordEvents,
+	/// Writes the current frame of the [`InputRecorder`], after [`CiTestingSystem::RecordEvents`].
+	WriteFrame,
+	/// Sends the events of the current frame of the [`InputReplayer`].
+	ReplayEvents,
+	/// Sends the [scripted events](CiTestingConfig::events) of the current frame.
+	ScriptEvents,
+	/// Advances the [`CiTestingFrame`], at the end of the frame.
+	AdvanceFrame,
+}
+
+/// The input events and time delta of a single frame, as recorded by the [`InputRecorder`].
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RecordedFrame {
+	/// The real time elapsed since the previous frame.
+	pub delta: Duration,
+	/// The events sent during the frame as RON lists, by event type name.
+	pub events: BTreeMap<String, String>,
+}
+
+/// Records the [`RecordedFrame`]s of an app, one RON line per frame.
+///
+/// Every frame is written and flushed as soon as it ends, so a recording is usable even if the
+/// app crashes.
+pub struct InputRecorder {
+	writer: Box<dyn Write + Send + Sync>,
+	frame: RecordedFrame,
+}
+
+impl InputRecorder {
+	/// Creates a recorder writing its frames into `writer`.
+	pub fn new(writer: impl Write + Send + Sync + 'static) -> Self {
+		Self {
+			writer: Box::new(writer),
+			frame: RecordedFrame::default(),
+		}
+	}
+
+	/// Sets the real time elapsed since the previous frame.
+	pub fn record_delta(&mut self, delta: Duration) {
+		self.frame.delta = delta;
+	}
+
+	/// Adds `events` to the current frame.
+	pub fn record_events<'a, E: Serialize + 'a>(&mut self, events: impl IntoIterator<Item = &'a E>) {
+		let events: Vec<&E> = events.into_iter().collect();
+		if events.is_empty() {
+			return;
+		}
+		let events = ron::to_string(&events).expect("error serializing recorded events");
+		self
+			.frame
+			.events
+			.insert(type_name::<E>().to_string(), events);
+	}
+
+	/// Writes the current frame and starts the next one.
+	pub fn write_frame(&mut self) -> io::Result<()> {
+		let frame = std::mem::take(&mut self.frame);
+		let line =
+			ron::to_string(&frame).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+		writeln!(self.writer, "{}", line)?;
+		self.writer.flush()
+	}
+}
+
+/// Replays the [`RecordedFrame`]s written by an [`InputRecorder`], one frame per app update.
+pub struct InputReplayer {
+	frames: VecDeque<RecordedFrame>,
+	frame: Option<RecordedFrame>,
+}
+
+impl InputReplayer {
+	/// Creates a replayer for the given frames.
+	pub fn new(frames: impl IntoIterator<Item = RecordedFrame>) -> Self {
+		let mut frames: VecDeque<_> = frames.into_iter().collect();
+		let frame = frames.pop_front();
+		Self { frames, frame }
+	}
+
+	/// Reads the frames written by an [`InputRecorder`].
+	pub fn from_reader(reader: impl BufRead) -> io::Result<Self> {
+		let mut frames = Vec::new();
+		for line in reader.lines() {
+			let line = line?;
+			if line.trim().is_empty() {
+				continue;
+			}
+			let frame =
+				ron::from_str(&line).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+			frames.push(frame);
+		}
+		Ok(Self::new(frames))
+	}
+
+	/// The real time elapsed since the previous frame, or `None` once all the frames were replayed.
+	pub fn delta(&self) -> Option<Duration> {
+		self.frame.as_ref().map(|frame| frame.delta)
+	}
+
+	/// The events of type `E` recorded during the current frame.
+	pub fn events<E: DeserializeOwned>(&self) -> Vec<E> {
+		self
+			.frame
+			.as_ref()
+			.and_then(|frame| frame.events.get(type_name::<E>()))
+			.map(|events| ron::from_str(events).expect("error deserializing recorded events"))
+			.unwrap_or_default()
+	}
+
+	/// Returns `true` once all the frames were replayed.
+	pub fn is_finished(&self) -> bool {
+		self.frame.is_none()
+	}
+
+	/// Moves to the next frame.
+	pub fn next_frame(&mut self) {
+		self.frame = self.frames.pop_front();
+	}
+}
+
+impl App {
+	/// Records or replays the events of type `E` when the [`CiTestingConfig`] asks for it.
+	///
+	/// Plugins call this for the events coming from outside of the app, such as input and window
+	/// events, so that replaying a recording reproduces the same frames. While an [`InputReplayer`]
+	/// exists, the events of type `E` sent before the frame starts are replaced by the recorded
+	/// ones. The events can also be [scripted](CiTestingConfig::events) by their type name.
+	///
+	/// The [`InputRecorder`] and [`InputReplayer`] may be inserted before or after this is called.
+	pub fn add_recorded_event<E>(&mut self)
+	where
+		E: Event + Serialize + DeserializeOwned,
+	{
+		self.init_resource::<ScriptedEventSenders>();
+		let mut senders = self
+			.world
+			.resource_mut::<ScriptedEventSenders>();
+		senders
+			.senders
+			.insert(type_name::<E>().to_string(), send_scripted_event::<E>);
+		senders
+			.senders
+			.insert(get_short_name(type_name::<E>()), send_scripted_event::<E>);
+		self.add_system_to_stage(
+			CoreStage::Last,
+			record_events_system::<E>.label(CiTestingSystem::RecordEvents),
+		);
+		self.add_system_to_stage(
+			CoreStage::First,
+			replay_events_system::<E>
+				.exclusive_system()
+				.at_start()
+				.label(CiTestingSystem::ReplayEvents),
+		);
+	}
+}
+
+fn record_events_system<E: Event + Serialize>(
+	mut events: EventReader<E>,
+	recorder: Option<ResMut<InputRecorder>>,
+) {
+	if let Some(mut recorder) = recorder {
+		recorder.record_events(events.iter());
+	}
+}
+
+fn write_recorded_frame_system(mut recorder: ResMut<InputRecorder>) {
+	recorder
+		.write_frame()
+		.expect("error writing recorded frame");
+}
+
+fn replay_events_system<E: Event + DeserializeOwned>(
+	replayer: Option<Res<InputReplayer>>,
+	mut events: ResMut<Events<E>>,
+) {
+	if let Some(replayer) = replayer {
+		// the live events are dropped, the replayed frames only see the recorded ones
+		events.clear();
+		events.extend(replayer.events::<E>());
+	}
+}
+
+fn next_replayed_frame_system(
+	mut replayer: ResMut<InputReplayer>,
+	mut app_exit_events: EventWriter<AppExit>,
+) {
+	replayer.next_frame();
+	if replayer.is_finished() {
+		app_exit_events.send(AppExit);
+		info!("Exiting after replaying all the recorded frames. Test successful!");
+	}
+}
+
+/// Sends the scripted events of the types recorded with [`App::add_recorded_event`], by type name.
+#[derive(Default)]
+struct ScriptedEventSenders {
+	senders: HashMap<String, fn(&mut World, &str)>,
+}
+
+fn send_scripted_event<E: Event + DeserializeOwned>(world: &mut World, value: &str) {
+	let event: E = ron::from_str(value).unwrap_or_else(|err| {
+		panic!(
+			"error deserializing scripted event `{}`: {}",
+			type_name::<E>(),
+			err
+		)
+	});
+	world.send_event(event);
+}
+
+#[cfg(feature = "bevy_reflect")]
+fn send_reflected_event(world: &mut World, event: &CiTestingEvent) -> bool {
+	use bevy_ecs::reflect::ReflectEvent;
+	use bevy_reflect::{serde::ReflectDeserializer, TypeRegistryArc};
+	use serde::de::DeserializeSeed;
+
+	let type_registry = match world.get_resource::<TypeRegistryArc>() {
+		Some(type_registry) => type_registry.clone(),
+		None => return false,
+	};
+	let type_registry = type_registry.read();
+	let reflect_event = match type_registry
+		.get_with_name(&event.event)
+		.or_else(|| type_registry.get_with_short_name(&event.event))
+		.and_then(|registration| registration.data::<ReflectEvent>())
+	{
+		Some(reflect_event) => reflect_event.clone(),
+		None => return false,
+	};
+	let value = ron::de::Deserializer::from_str(&event.value)
+		.map_err(|err| err.to_string())
+		.and_then(|mut deserializer| {
+			ReflectDeserializer::new(&type_registry)
+				.deserialize(&mut deserializer)
+				.map_err(|err| err.to_string())
+		})
+		.unwrap_or_else(|err| {
+			panic!(
+				"error deserializing scripted event `{}`: {}",
+				event.event, err
+			)
+		});
+	drop(type_registry);
+	reflect_event.send(world, &*value);
+	true
+}
+
+fn send_scripted_events_system(world: &mut World) {
+	let frame = world.resource::<CiTestingFrame>().0;
+	let events: Vec<CiTestingEvent> = world
+		.resource::<CiTestingConfig>()
+		.events
+		.iter()
+		.filter(|event| event.frame == frame)
+		.cloned()
+		.collect();
+	for event in events {
+		let sender = world
+			.resource::<ScriptedEventSenders>()
+			.senders
+			.get(&event.event)
+			.copied();
+		if let Some(send) = sender {
+			send(world, &event.value);
+			continue;
+		}
+		#[cfg(feature = "bevy_reflect")]
+		if send_reflected_event(world, &event) {
+			continue;
+		}
+		panic!(
+			"cannot send scripted event `{}`, it must be recorded with `add_recorded_event` or registered with `#[reflect(Event)]`",
+			event.event
+		);
+	}
+}
+
+fn request_frame_captures_system(
+	frame: Res<CiTestingFrame>,
+	ci_testing_config: Res<CiTestingConfig>,
+	mut captures: EventWriter<CiTestingFrameCapture>,
+) {
+	if ci_testing_config
+		.capture_frames
+		.contains(&frame.0)
+	{
+		let prefix = ci_testing_config
+			.capture_prefix
+			.as_deref()
+			.unwrap_or("capture-");
+		captures.send(CiTestingFrameCapture {
+			path: format!("{}{}.png", prefix, frame.0),
+		});
+	}
+}
+
+fn fail_on_error_system(error_count: Res<CiTestingErrorCount>) {
+	let errors = error_count.get();
+	if errors > 0 {
+		panic!("{} errors were logged. Test failed!", errors);
+	}
+}
+
+fn advance_frame_system(mut frame: ResMut<CiTestingFrame>) {
+	frame.0 += 1;
 }
 
 fn ci_testing_exit_after(
-	mut current_frame: bevy_ecs::prelude::Local<u32>,
-	ci_testing_config: bevy_ecs::prelude::Res<CiTestingConfig>,
-	mut app_exit_events: bevy_ecs::event::EventWriter<AppExit>,
+	frame: Res<CiTestingFrame>,
+	ci_testing_config: Res<CiTestingConfig>,
+	mut app_exit_events: EventWriter<AppExit>,
 ) {
 	if let Some(exit_after) = ci_testing_config.exit_after {
-		if *current_frame > exit_after {
+		if frame.0 > exit_after {
 			app_exit_events.send(AppExit);
 			info!("Exiting after {} frames. Test successful!", exit_after);
 		}
 	}
-	*current_frame += 1;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn set_fail_on_panic_hook() {
+	let default_hook = std::panic::take_hook();
+	std::panic::set_hook(Box::new(move |info| {
+		default_hook(info);
+		error!("Exiting after a panic. Test failed!");
+		std::process::exit(1);
+	}));
 }
 
 pub(crate) fn setup_app(app: &mut App) -> &mut App {
@@ -43,8 +467,306 @@ pub(crate) fn setup_app(app: &mut App) -> &mut App {
 		ron::from_str(config).expect("error deserializing CI testing configuration file")
 	};
 
+	configure_app(app, config)
+}
+
+fn configure_app(app: &mut App, config: CiTestingConfig) -> &mut App {
+	#[cfg(not(target_arch = "wasm32"))]
+	{
+		if let Some(path) = &config.record {
+			let file = std::fs::File::create(path).expect("error creating recording file");
+			app.insert_resource(InputRecorder::new(io::BufWriter::new(file)));
+			app.add_system_to_stage(
+				CoreStage::Last,
+				write_recorded_frame_system
+					.label(CiTestingSystem::WriteFrame)
+					.after(CiTestingSystem::RecordEvents),
+			);
+		}
+		if let Some(path) = &config.replay {
+			let file = std::fs::File::open(path).expect("error opening recording file");
+			let replayer =
+				InputReplayer::from_reader(io::BufReader::new(file)).expect("error reading recording file");
+			app.insert_resource(replayer);
+			app.add_system_to_stage(CoreStage::Last, next_replayed_frame_system);
+		}
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	if config.fail_on_panic {
+		set_fail_on_panic_hook();
+	}
+	if config.fail_on_error {
+		app.init_resource::<CiTestingErrorCount>();
+		app.add_system_to_stage(
+			CoreStage::Last,
+			fail_on_error_system.exclusive_system().at_end(),
+		);
+	}
+
 	app.insert_resource(config);
+	app.init_resource::<CiTestingFrame>();
+	app.init_resource::<ScriptedEventSenders>();
+	app.add_event::<CiTestingFrameCapture>();
+	app.add_system_to_stage(
+		CoreStage::First,
+		send_scripted_events_system
+			.exclusive_system()
+			.at_end()
+			.label(CiTestingSystem::ScriptEvents),
+	);
 	app.add_system(ci_testing_exit_after);
+	app.add_system_to_stage(
+		CoreStage::Last,
+		request_frame_captures_system.before(CiTestingSystem::AdvanceFrame),
+	);
+	app.add_system_to_stage(
+		CoreStage::Last,
+		advance_frame_system.label(CiTestingSystem::AdvanceFrame),
+	);
 
 	app
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	#[cfg(feature = "bevy_reflect")]
+	use bevy_ecs::reflect::ReflectEvent;
+	#[cfg(feature = "bevy_reflect")]
+	use bevy_reflect::Reflect;
+	use std::sync::{Arc, Mutex};
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct Key(char);
+
+	#[cfg(feature = "bevy_reflect")]
+	#[derive(Reflect, Default)]
+	#[reflect(Event)]
+	struct Score {
+		points: u32,
+	}
+
+	#[derive(Clone, Default)]
+	struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+	impl Write for SharedBuffer {
+		fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+			self.0.lock().unwrap().write(buf)
+		}
+
+		fn flush(&mut self) -> io::Result<()> {
+			Ok(())
+		}
+	}
+
+	// `App::new` reads the CI testing configuration file
+	fn test_app() -> App {
+		let mut app = App::empty();
+		app.add_default_stages();
+		app.add_event::<AppExit>();
+		app
+	}
+
+	#[test]
+	fn record_and_replay() {
+		let buffer = SharedBuffer::default();
+		let mut app = test_app();
+		app.add_event::<Key>();
+		app.add_recorded_event::<Key>();
+		// the recorder may be inserted after the recorded events are added
+		app.insert_resource(InputRecorder::new(buffer.clone()));
+		app.add_system_to_stage(
+			CoreStage::Last,
+			write_recorded_frame_system.after(CiTestingSystem::RecordEvents),
+		);
+
+		app.world.send_event(Key('a'));
+		app.update();
+		app
+			.world
+			.resource_mut::<InputRecorder>()
+			.record_delta(Duration::from_millis(16));
+		app.world.send_event(Key('b'));
+		app.world.send_event(Key('c'));
+		app.update();
+
+		let recording = buffer.0.lock().unwrap().clone();
+		let replayer = InputReplayer::from_reader(&recording[..]).unwrap();
+
+		let mut app = test_app();
+		app.add_event::<Key>();
+		app.add_recorded_event::<Key>();
+		app.insert_resource(replayer);
+		app.add_system_to_stage(CoreStage::Last, next_replayed_frame_system);
+		app.init_resource::<Vec<char>>();
+		app.add_system(
+			|mut events: EventReader<Key>, mut keys: ResMut<Vec<char>>| {
+				keys.extend(events.iter().map(|key| key.0));
+			},
+		);
+
+		assert_eq!(
+			Some(Duration::ZERO),
+			app.world.resource::<InputReplayer>().delta()
+		);
+		// the live events are ignored during the replay
+		app.world.send_event(Key('x'));
+		app.update();
+		assert_eq!(
+			Some(Duration::from_millis(16)),
+			app.world.resource::<InputReplayer>().delta()
+		);
+		assert_eq!(
+			vec![Key('b'), Key('c')],
+			app
+				.world
+				.resource::<InputReplayer>()
+				.events::<Key>()
+		);
+		app.world.send_event(Key('y'));
+		app.update();
+		assert!(app
+			.world
+			.resource::<InputReplayer>()
+			.is_finished());
+		assert_eq!(vec!['a', 'b', 'c'], *app.world.resource::<Vec<char>>());
+		assert_eq!(
+			1,
+			app
+				.world
+				.resource::<bevy_ecs::event::Events<AppExit>>()
+				.len()
+		);
+	}
+
+	#[test]
+	fn scripted_events_and_frame_captures() {
+		let mut app = test_app();
+		configure_app(
+			&mut app,
+			CiTestingConfig {
+				exit_after: Some(3),
+				record: None,
+				replay: None,
+				events: vec![CiTestingEvent {
+					frame: 1,
+					event: "Key".to_string(),
+					value: "Key('x')".to_string(),
+				}],
+				capture_frames: vec![2],
+				capture_prefix: Some("frames/".to_string()),
+				fail_on_error: false,
+				fail_on_panic: false,
+			},
+		);
+		app.add_event::<Key>();
+		app.add_recorded_event::<Key>();
+		app.init_resource::<Vec<char>>();
+		app.add_system(
+			|mut events: EventReader<Key>, mut keys: ResMut<Vec<char>>| {
+				keys.extend(events.iter().map(|key| key.0));
+			},
+		);
+
+		app.update();
+		assert!(app.world.resource::<Vec<char>>().is_empty());
+		app.update();
+		assert_eq!(vec!['x'], *app.world.resource::<Vec<char>>());
+		app.update();
+		let captures: Vec<CiTestingFrameCapture> = app
+			.world
+			.resource_mut::<bevy_ecs::event::Events<CiTestingFrameCapture>>()
+			.drain()
+			.collect();
+		assert_eq!(
+			vec![CiTestingFrameCapture {
+				path: "frames/2.png".to_string()
+			}],
+			captures
+		);
+		app.update();
+		assert!(app
+			.world
+			.resource::<bevy_ecs::event::Events<AppExit>>()
+			.is_empty());
+		app.update();
+		assert!(!app
+			.world
+			.resource::<bevy_ecs::event::Events<AppExit>>()
+			.is_empty());
+	}
+
+	#[test]
+	#[should_panic(expected = "1 errors were logged. Test failed!")]
+	fn fail_on_error() {
+		let mut app = test_app();
+		configure_app(
+			&mut app,
+			CiTestingConfig {
+				exit_after: None,
+				record: None,
+				replay: None,
+				events: Vec::new(),
+				capture_frames: Vec::new(),
+				capture_prefix: None,
+				fail_on_error: true,
+				fail_on_panic: false,
+			},
+		);
+
+		app.update();
+		app
+			.world
+			.resource::<CiTestingErrorCount>()
+			.increment();
+		app.update();
+	}
+
+	#[cfg(feature = "bevy_reflect")]
+	#[test]
+	fn scripted_reflected_events() {
+		use bevy_reflect::TypeRegistryArc;
+
+		let mut app = test_app();
+		app.init_resource::<TypeRegistryArc>();
+		app.register_type::<Score>();
+		app.add_event::<Score>();
+		configure_app(
+			&mut app,
+			CiTestingConfig {
+				exit_after: None,
+				record: None,
+				replay: None,
+				events: vec![CiTestingEvent {
+					frame: 0,
+					event: "Score".to_string(),
+					value: r#"{
+						"type": "bevy_app::ci_testing::tests::Score",
+						"struct": {
+							"points": {
+								"type": "u32",
+								"value": 5,
+							},
+						},
+					}"#
+						.to_string(),
+				}],
+				capture_frames: Vec::new(),
+				capture_prefix: None,
+				fail_on_error: false,
+				fail_on_panic: false,
+			},
+		);
+		app.init_resource::<u32>();
+		app.add_system(|mut events: EventReader<Score>, mut points: ResMut<u32>| {
+			*points += events
+				.iter()
+				.map(|score| score.points)
+				.sum::<u32>();
+		});
+
+		app.update();
+		assert_eq!(5, *app.world.resource::<u32>());
+	}
+}
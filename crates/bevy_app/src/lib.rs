@@ -6,15 +6,20 @@ mod app;
 mod plugin;
 mod plugin_group;
 mod schedule_runner;
+mod sub_app;
+mod test_app;
 
 #[cfg(feature = "bevy_ci_testing")]
 mod ci_testing;
 
 pub use app::*;
 pub use bevy_derive::DynamicPlugin;
+#[cfg(feature = "bevy_ci_testing")]
+pub use ci_testing::*;
 pub use plugin::*;
 pub use plugin_group::*;
 pub use schedule_runner::*;
+pub use test_app::*;
 
 #[allow(missing_docs)]
 pub mod prelude {
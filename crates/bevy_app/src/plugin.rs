@@ -1,17 +1,43 @@
 use crate::App;
-use std::any::Any;
+use std::{
+	any::{Any, TypeId},
+	os::raw::c_char,
+};
+use thiserror::Error;
 
 /// A collection of Bevy app logic and configuration.
 ///
 /// Plugins configure an [`App`]. When an [`App`] registers a plugin,
-/// the plugin's [`Plugin::build`] function is run.
+/// the plugin's [`Plugin::build`] function is run. Once all the plugins are added, their
+/// [`Plugin::finish`] and then their [`Plugin::cleanup`] functions are run, in the order the
+/// plugins were added, see [`App::finish`].
 pub trait Plugin: Any + Send + Sync {
 	/// Configures the [`App`] to which this plugin is added.
 	fn build(&self, app: &mut App);
+	/// Finishes configuring the [`App`] once all the plugins are built, for example with
+	/// resources added by plugins added after this one.
+	fn finish(&self, _app: &mut App) {
+		// do nothing
+	}
+	/// Runs after all the plugins are [finished](Plugin::finish), before the [`App`] runs.
+	fn cleanup(&self, _app: &mut App) {
+		// do nothing
+	}
 	/// Configures a name for the [`Plugin`] which is primarily used for debugging.
 	fn name(&self) -> &str {
 		std::any::type_name::<Self>()
 	}
+	/// Returns `true` if the plugin can only be added once to an [`App`], see
+	/// [`DuplicatePlugins`].
+	fn is_unique(&self) -> bool {
+		true
+	}
+	/// The plugins that must be added to the [`App`] before this one.
+	///
+	/// A [`PluginGroup`](crate::PluginGroup) builds the dependencies of its plugins first.
+	fn dependencies(&self) -> Vec<PluginId> {
+		Vec::new()
+	}
 }
 
 /// A type representing an unsafe function that returns a mutable pointer to a [`Plugin`].
@@ -19,3 +45,100 @@ pub trait Plugin: Any + Send + Sync {
 ///
 /// See `bevy_dynamic_plugin/src/loader.rs#dynamically_load_plugin`.
 pub type CreatePlugin = unsafe fn() -> *mut dyn Plugin;
+
+/// A type representing an unsafe function that returns the `bevy::BUILD_FINGERPRINT` a
+/// dynamically loaded plugin was built with, as a NUL-terminated string.
+///
+/// The fingerprint is checked before calling the [`CreatePlugin`] function of the plugin, see
+/// `bevy_dynamic_plugin/src/loader.rs#dynamically_load_plugin`.
+pub type PluginBuildFingerprint = unsafe extern "C" fn() -> *const c_char;
+
+/// A type representing an unsafe function that returns the name of the crate of a dynamically
+/// loaded plugin, as a NUL-terminated string.
+pub type PluginCrateName = unsafe extern "C" fn() -> *const c_char;
+
+/// Identifies a type of [`Plugin`], for example a [dependency](Plugin::dependencies).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PluginId {
+	type_id: TypeId,
+	name: &'static str,
+}
+
+impl PluginId {
+	/// Returns the identifier of the plugin type `T`.
+	pub fn of<T: Plugin>() -> Self {
+		PluginId {
+			type_id: TypeId::of::<T>(),
+			name: std::any::type_name::<T>(),
+		}
+	}
+
+	/// Returns the [`TypeId`] of the plugin type.
+	pub fn type_id(&self) -> TypeId {
+		self.type_id
+	}
+
+	/// Returns the name of the plugin type.
+	pub fn name(&self) -> &'static str {
+		self.name
+	}
+}
+
+/// What [`App::add_plugin`] does when a [unique](Plugin::is_unique) plugin already added to the
+/// [`App`] is added again, see [`App::set_duplicate_plugins`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicatePlugins {
+	/// The plugin is ignored with a warning.
+	#[default]
+	Warn,
+	/// The plugin is ignored.
+	Ignore,
+	/// Adding the plugin fails with [`PluginError::DuplicatePlugin`].
+	Error,
+}
+
+/// The progress of the [`Plugin`]s of an [`App`] through their configuration phases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PluginsState {
+	/// Plugins are being added and [built](Plugin::build).
+	Adding,
+	/// The plugins are [finished](Plugin::finish).
+	Finished,
+	/// The plugins are [cleaned up](Plugin::cleanup), the [`App`] is ready to run.
+	Cleaned,
+}
+
+/// An error adding a [`Plugin`] to an [`App`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PluginError {
+	/// A unique plugin was added again.
+	#[error("plugin `{plugin_name}` was already added")]
+	DuplicatePlugin {
+		/// The name of the plugin.
+		plugin_name: String,
+	},
+	/// The [dependencies](Plugin::dependencies) of the plugins of a
+	/// [`PluginGroup`](crate::PluginGroup) form a cycle.
+	#[error("plugins depend on each other in a cycle: {}", .plugin_names.join(" -> "))]
+	DependencyCycle {
+		/// The names of the plugins in the cycle, starting and ending with the same plugin.
+		plugin_names: Vec<String>,
+	},
+	/// A plugin was added before one of its [dependencies](Plugin::dependencies).
+	#[error(
+		"plugin `{plugin_name}` depends on plugin `{dependency_name}`, which wasn't added before it"
+	)]
+	MissingDependency {
+		/// The name of the plugin.
+		plugin_name: String,
+		/// The name of the missing dependency.
+		dependency_name: &'static str,
+	},
+}
+
+/// Holds the place of a plugin in the [`App`] while it is built, finished or cleaned up.
+pub(crate) struct PlaceholderPlugin;
+
+impl Plugin for PlaceholderPlugin {
+	fn build(&self, _app: &mut App) {}
+}
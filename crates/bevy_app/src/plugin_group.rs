@@ -1,5 +1,5 @@
-use crate::{App, Plugin};
-use bevy_utils::{tracing::debug, tracing::warn, HashMap};
+use crate::{App, Plugin, PluginError};
+use bevy_utils::{tracing::warn, HashMap, HashSet};
 use std::any::TypeId;
 
 /// Combines multiple [`Plugin`]s into a single unit.
@@ -130,18 +130,72 @@ impl PluginGroupBuilder {
 		plugin_entry.enabled = false;
 	}
 
-	/// Consumes the [`PluginGroupBuilder`] and [builds](Plugin::build) the contained [`Plugin`]s
-	/// in the order specified.
-	pub fn finish(self, app: &mut App) {
-		for ty in &self.order {
-			if let Some(entry) = self.plugins.get(ty) {
-				if entry.enabled {
-					debug!("added plugin: {}", entry.plugin.name());
-					entry.plugin.build(app);
-				}
+	/// Consumes the [`PluginGroupBuilder`] and [adds](App::add_boxed_plugin) the enabled
+	/// [`Plugin`]s to the app in the order specified, after their
+	/// [dependencies](Plugin::dependencies) in the group.
+	///
+	/// # Panics
+	///
+	/// Panics if the dependencies of the plugins form a cycle, or if a plugin can't be added, see
+	/// [`App::add_plugin`].
+	pub fn finish(mut self, app: &mut App) {
+		let order = match self.build_order() {
+			Ok(order) => order,
+			Err(err) => panic!("{}", err),
+		};
+		for ty in order {
+			let entry = self
+				.plugins
+				.remove(&ty)
+				.expect("ordered plugins are in the group");
+			if let Err(err) = app.add_boxed_plugin(entry.plugin) {
+				panic!("{}", err);
 			}
 		}
 	}
+
+	/// Orders the enabled plugins so the dependencies in the group of each plugin come before it.
+	fn build_order(&self) -> Result<Vec<TypeId>, PluginError> {
+		let mut order = Vec::new();
+		let mut visited = HashSet::default();
+		for ty in &self.order {
+			self.visit_dependencies(*ty, &mut visited, &mut Vec::new(), &mut order)?;
+		}
+		Ok(order)
+	}
+
+	/// Adds the plugin `ty` to `order` after its dependencies, `visiting` being the plugins
+	/// whose dependencies are being visited.
+	fn visit_dependencies(
+		&self,
+		ty: TypeId,
+		visited: &mut HashSet<TypeId>,
+		visiting: &mut Vec<TypeId>,
+		order: &mut Vec<TypeId>,
+	) -> Result<(), PluginError> {
+		if !matches!(self.plugins.get(&ty), Some(entry) if entry.enabled) || visited.contains(&ty) {
+			return Ok(());
+		}
+		if let Some(index) = visiting
+			.iter()
+			.position(|visiting| *visiting == ty)
+		{
+			let plugin_names = visiting[index..]
+				.iter()
+				.chain(std::iter::once(&ty))
+				.map(|ty| self.plugins[ty].plugin.name().to_string())
+				.collect();
+			return Err(PluginError::DependencyCycle { plugin_names });
+		}
+		visiting.push(ty);
+		for dependency in self.plugins[&ty].plugin.dependencies() {
+			self.visit_dependencies(dependency.type_id(), visited, visiting, order)?;
+		}
+		visiting.pop();
+		visited.insert(ty);
+		order.push(ty);
+		Ok(())
+	}
 }
 
 /// A plugin group which doesn't do anything. Useful for examples:
@@ -163,7 +217,7 @@ impl PluginGroup for NoopPluginGroup {
 #[cfg(test)]
 mod tests {
 	use super::PluginGroupBuilder;
-	use crate::{App, Plugin};
+	use crate::{App, Plugin, PluginError, PluginId};
 
 	struct PluginA;
 	impl Plugin for PluginA {
@@ -180,6 +234,30 @@ mod tests {
 		fn build(&self, _: &mut App) {}
 	}
 
+	struct PluginD;
+	impl Plugin for PluginD {
+		fn build(&self, _: &mut App) {}
+		fn dependencies(&self) -> Vec<PluginId> {
+			vec![PluginId::of::<PluginC>(), PluginId::of::<PluginA>()]
+		}
+	}
+
+	struct PluginE;
+	impl Plugin for PluginE {
+		fn build(&self, _: &mut App) {}
+		fn dependencies(&self) -> Vec<PluginId> {
+			vec![PluginId::of::<PluginF>()]
+		}
+	}
+
+	struct PluginF;
+	impl Plugin for PluginF {
+		fn build(&self, _: &mut App) {}
+		fn dependencies(&self) -> Vec<PluginId> {
+			vec![PluginId::of::<PluginE>()]
+		}
+	}
+
 	#[test]
 	fn basic_ordering() {
 		let mut group = PluginGroupBuilder::default();
@@ -284,4 +362,57 @@ mod tests {
 			]
 		);
 	}
+
+	#[test]
+	fn dependencies_first() {
+		let mut group = PluginGroupBuilder::default();
+		group.add(PluginA);
+		group.add(PluginD);
+		group.add(PluginB);
+		group.add(PluginC);
+		group.disable::<PluginB>();
+
+		assert_eq!(
+			group.build_order().unwrap(),
+			vec![
+				std::any::TypeId::of::<PluginA>(),
+				std::any::TypeId::of::<PluginC>(),
+				std::any::TypeId::of::<PluginD>(),
+			]
+		);
+
+		let mut app = App::new();
+		group.finish(&mut app);
+		assert!(app.is_plugin_added::<PluginD>());
+		assert!(!app.is_plugin_added::<PluginB>());
+	}
+
+	#[test]
+	fn dependency_cycle() {
+		let mut group = PluginGroupBuilder::default();
+		group.add(PluginA);
+		group.add(PluginE);
+		group.add(PluginF);
+
+		match group.build_order() {
+			Err(PluginError::DependencyCycle { plugin_names }) => assert_eq!(
+				plugin_names,
+				vec![
+					std::any::type_name::<PluginE>(),
+					std::any::type_name::<PluginF>(),
+					std::any::type_name::<PluginE>(),
+				]
+			),
+			order => panic!("expected a dependency cycle, got {:?}", order),
+		}
+
+		group.disable::<PluginF>();
+		assert_eq!(
+			group.build_order().unwrap(),
+			vec![
+				std::any::TypeId::of::<PluginA>(),
+				std::any::TypeId::of::<PluginE>(),
+			]
+		);
+	}
 }
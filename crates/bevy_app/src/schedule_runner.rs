@@ -63,7 +63,20 @@ impl ScheduleRunnerSettings {
 #[derive(Default)]
 pub struct ScheduleRunnerPlugin;
 
-impl ScheduleRunnerPlugin {}
+impl ScheduleRunnerPlugin {
+	/// Reads the [`AppExit`] events sent since the last call, returning the last one. The app
+	/// stops running frames once one is sent.
+	pub(crate) fn read_exit(
+		app: &App,
+		app_exit_event_reader: &mut ManualEventReader<AppExit>,
+	) -> Option<AppExit> {
+		let app_exit_events = app.world.get_resource::<Events<AppExit>>()?;
+		app_exit_event_reader
+			.iter(app_exit_events)
+			.last()
+			.cloned()
+	}
+}
 
 impl Plugin for ScheduleRunnerPlugin {
 	fn build(&self, app: &mut App) {
@@ -78,29 +91,18 @@ impl Plugin for ScheduleRunnerPlugin {
 					app.update();
 				},
 				RunMode::Loop { wait } => {
-					// TODO: Move into seperate fn in ScheduleRunnerPlugin
 					let mut tick =
 						move |app: &mut App, wait: Option<Duration>| -> Result<Option<Duration>, AppExit> {
 							let start_time = Instant::now();
 
-							if let Some(app_exit_events) = app.world.get_resource_mut::<Events<AppExit>>() {
-								if let Some(exit) = app_exit_event_reader
-									.iter(&app_exit_events)
-									.last()
-								{
-									return Err(exit.clone());
-								}
+							if let Some(exit) = ScheduleRunnerPlugin::read_exit(app, &mut app_exit_event_reader) {
+								return Err(exit);
 							}
 
 							app.update();
 
-							if let Some(app_exit_events) = app.world.get_resource_mut::<Events<AppExit>>() {
-								if let Some(exit) = app_exit_event_reader
-									.iter(&app_exit_events)
-									.last()
-								{
-									return Err(exit.clone());
-								}
+							if let Some(exit) = ScheduleRunnerPlugin::read_exit(app, &mut app_exit_event_reader) {
+								return Err(exit);
 							}
 
 							let end_time = Instant::now();
@@ -0,0 +1,158 @@
+use crate::{App, AppLabel, AppLabelId};
+use bevy_ecs::world::World;
+use std::sync::{mpsc, Arc};
+
+type ExtractFn = Box<dyn Fn(&mut World, &mut App) + Send>;
+type RunFn = Arc<dyn Fn(&mut App) + Send + Sync>;
+
+/// Each `SubApp` has its own [`Schedule`](bevy_ecs::schedule::Schedule) and [`World`], enabling a
+/// separation of concerns.
+///
+/// A sub app is updated in two steps: `extract` copies the data it needs from the main world,
+/// then `run` advances its schedule. When the sub app is pipelined, `run` happens on a thread of
+/// its own while the main world simulates the next frame.
+pub(crate) struct SubApp {
+	label: AppLabelId,
+	/// The app, `None` while it's running on the pipeline thread.
+	app: Option<App>,
+	extract: ExtractFn,
+	run: Option<RunFn>,
+	pipeline: Option<SubAppPipeline>,
+}
+
+/// The thread running a pipelined sub app, and the channels handing the app over to it and back.
+struct SubAppPipeline {
+	to_thread: Option<mpsc::Sender<App>>,
+	from_thread: mpsc::Receiver<App>,
+	thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl SubApp {
+	pub(crate) fn new(label: AppLabelId, app: App, extract: ExtractFn, run: Option<RunFn>) -> Self {
+		SubApp {
+			label,
+			app: Some(app),
+			extract,
+			run,
+			pipeline: None,
+		}
+	}
+
+	/// Returns the app, or `None` if it's running on the pipeline thread.
+	pub(crate) fn app(&self) -> Option<&App> {
+		self.app.as_ref()
+	}
+
+	/// Returns the app, waiting for the pipeline thread to finish running it first.
+	///
+	/// # Panics
+	///
+	/// Panics if the app panicked on the pipeline thread.
+	pub(crate) fn app_mut(&mut self) -> &mut App {
+		if self.app.is_none() {
+			let pipeline = self
+				.pipeline
+				.as_ref()
+				.expect("a sub app can only be missing while pipelined");
+			match pipeline.from_thread.recv() {
+				Ok(app) => self.app = Some(app),
+				Err(_) => panic!("pipelined sub app {:?} panicked", self.label.as_str()),
+			}
+		}
+		self.app.as_mut().unwrap()
+	}
+
+	pub(crate) fn can_be_pipelined(&self) -> bool {
+		self.run.is_some()
+	}
+
+	pub(crate) fn is_pipelined(&self) -> bool {
+		self.pipeline.is_some()
+	}
+
+	/// Extracts the data of the current frame from `world` and runs the sub app, on its pipeline
+	/// thread if it has one.
+	///
+	/// When pipelined, this first waits for the sub app to finish running the previous frame.
+	pub(crate) fn update(&mut self, world: &mut World) {
+		self.app_mut();
+		(self.extract)(world, self.app.as_mut().unwrap());
+		match (&self.pipeline, &self.run) {
+			(Some(pipeline), _) => {
+				let app = self.app.take().unwrap();
+				if pipeline
+					.to_thread
+					.as_ref()
+					.unwrap()
+					.send(app)
+					.is_err()
+				{
+					panic!("pipelined sub app {:?} panicked", self.label.as_str());
+				}
+			},
+			(None, Some(run)) => run(self.app.as_mut().unwrap()),
+			(None, None) => {},
+		}
+	}
+
+	/// Starts or stops running the sub app on a thread of its own.
+	///
+	/// The [main thread](World::set_main_thread) of the sub app world follows it, so the sub app
+	/// can't contain non-send resources.
+	pub(crate) fn set_pipelined(&mut self, pipelined: bool) {
+		if pipelined == self.is_pipelined() {
+			return;
+		}
+		if !pipelined {
+			self.app_mut();
+			self.pipeline = None;
+			self
+				.app
+				.as_mut()
+				.unwrap()
+				.world
+				.set_main_thread(std::thread::current().id());
+			return;
+		}
+
+		let run = self
+			.run
+			.clone()
+			.expect("only sub apps with a separate run function can be pipelined");
+		let (to_thread, thread_receiver) = mpsc::channel::<App>();
+		let (thread_sender, from_thread) = mpsc::channel();
+		let thread = std::thread::Builder::new()
+			.name(format!("{} sub app", self.label.as_str()))
+			.spawn(move || {
+				while let Ok(mut app) = thread_receiver.recv() {
+					run(&mut app);
+					if thread_sender.send(app).is_err() {
+						break;
+					}
+				}
+			})
+			.expect("failed to spawn the thread of a pipelined sub app");
+		self
+			.app
+			.as_mut()
+			.unwrap()
+			.world
+			.set_main_thread(thread.thread().id());
+		self.pipeline = Some(SubAppPipeline {
+			to_thread: Some(to_thread),
+			from_thread,
+			thread: Some(thread),
+		});
+	}
+}
+
+impl Drop for SubAppPipeline {
+	fn drop(&mut self) {
+		// closing the channel stops the thread once it's done with the current frame
+		self.to_thread = None;
+		if let Some(thread) = self.thread.take() {
+			// a panic of the thread was already reported by the panic hook
+			let _ = thread.join();
+		}
+	}
+}
@@ -0,0 +1,521 @@
+use crate::{
+	app::{App, AppExit},
+	CoreStage, ScheduleRunnerPlugin,
+};
+use bevy_ecs::{
+	entity::Entity,
+	event::{Event, Events, ManualEventReader},
+	prelude::Component,
+	prelude::IntoExclusiveSystem,
+	query::{ROQueryItem, WorldQuery},
+	system::Resource,
+	world::{Mut, World},
+};
+use bevy_utils::{Duration, Instant};
+use std::{
+	fmt::Debug,
+	ops::{Deref, DerefMut},
+};
+
+/// A manually advanced clock, used instead of the wall clock to update the `Time` resource of
+/// `bevy_time` when present, see [`TestApp`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MockClock {
+	now: Instant,
+}
+
+impl Default for MockClock {
+	fn default() -> Self {
+		MockClock {
+			now: Instant::now(),
+		}
+	}
+}
+
+impl MockClock {
+	/// The current instant of the clock.
+	pub fn now(&self) -> Instant {
+		self.now
+	}
+
+	/// Moves the clock forward by `duration`.
+	pub fn advance(&mut self, duration: Duration) {
+		self.now += duration;
+	}
+}
+
+/// The events of type `E` captured by a [`TestApp`], see [`TestApp::capture_events`].
+struct CapturedEvents<E> {
+	events: Vec<E>,
+}
+
+type EventCapture = Box<dyn FnMut(&mut World)>;
+
+/// A headless [`App`] for tests, advanced frame by frame with a [`MockClock`].
+///
+/// The frames run like with [`RunMode::Loop`](crate::RunMode::Loop), but the test drives them
+/// itself with [`step`](Self::step) and [`step_for`](Self::step_for), and inspects the [`World`]
+/// in between. As with [`RunMode::Loop`](crate::RunMode::Loop), no frame runs once an
+/// [`AppExit`] event was sent. Every frame advances the clock by the
+/// [frame delta](Self::set_frame_delta), so the time seen by the systems doesn't depend on how
+/// fast the test runs. The CI testing configuration isn't read.
+///
+/// The [`TestApp`] dereferences to its [`App`] to add plugins, systems and resources.
+///
+/// ```
+/// # use bevy_app::TestApp;
+/// # use bevy_ecs::prelude::*;
+/// struct Jump;
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn spawn_on_jump(mut commands: Commands, mut jumps: EventReader<Jump>) {
+///     for _ in jumps.iter() {
+///         commands.spawn().insert(Player);
+///     }
+/// }
+///
+/// let mut app = TestApp::new();
+/// app.add_event::<Jump>();
+/// app.add_system(spawn_on_jump);
+///
+/// app.send_event(Jump);
+/// app.step(2);
+/// app.assert_count::<With<Player>>(1);
+/// ```
+pub struct TestApp {
+	app: App,
+	frame: u32,
+	frame_delta: Duration,
+	captures: Vec<EventCapture>,
+	app_exit_event_reader: ManualEventReader<AppExit>,
+	exit: Option<AppExit>,
+}
+
+impl Default for TestApp {
+	fn default() -> Self {
+		let mut app = App::empty();
+		#[cfg(feature = "bevy_reflect")]
+		app.init_resource::<bevy_reflect::TypeRegistryArc>();
+
+		app.add_default_stages();
+		app.add_event::<AppExit>();
+		app.add_system_to_stage(CoreStage::Last, World::clear_trackers.exclusive_system());
+		app.init_resource::<MockClock>();
+
+		TestApp {
+			app,
+			frame: 0,
+			frame_delta: Duration::from_secs(1) / 60,
+			captures: Vec::new(),
+			app_exit_event_reader: ManualEventReader::default(),
+			exit: None,
+		}
+	}
+}
+
+impl Deref for TestApp {
+	type Target = App;
+
+	fn deref(&self) -> &App {
+		&self.app
+	}
+}
+
+impl DerefMut for TestApp {
+	fn deref_mut(&mut self) -> &mut App {
+		&mut self.app
+	}
+}
+
+impl TestApp {
+	/// Creates a [`TestApp`] with the default stages of an [`App`], advancing its clock by 1/60th
+	/// of a second every frame.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the [`App`] of the test.
+	pub fn into_app(self) -> App {
+		self.app
+	}
+
+	/// The number of frames stepped so far.
+	pub fn frame(&self) -> u32 {
+		self.frame
+	}
+
+	/// The duration the [`MockClock`] is advanced by before every frame.
+	pub fn frame_delta(&self) -> Duration {
+		self.frame_delta
+	}
+
+	/// Sets the duration the [`MockClock`] is advanced by before every frame.
+	pub fn set_frame_delta(&mut self, frame_delta: Duration) -> &mut Self {
+		self.frame_delta = frame_delta;
+		self
+	}
+
+	/// Runs `frames` frames of the app, fewer if it exits.
+	pub fn step(&mut self, frames: u32) -> &mut Self {
+		for _ in 0..frames {
+			if !self.step_frame() {
+				break;
+			}
+		}
+		self
+	}
+
+	/// Runs as many frames as needed for the [`MockClock`] to advance by at least `duration`, and
+	/// returns the number of frames. Fewer frames run if the app exits.
+	///
+	/// # Panics
+	///
+	/// Panics if the [frame delta](Self::set_frame_delta) is zero.
+	pub fn step_for(&mut self, duration: Duration) -> u32 {
+		assert!(
+			!self.frame_delta.is_zero(),
+			"cannot step for a duration with a zero frame delta"
+		);
+		let mut elapsed = Duration::ZERO;
+		let mut frames = 0;
+		while elapsed < duration && self.step_frame() {
+			elapsed += self.frame_delta;
+			frames += 1;
+		}
+		frames
+	}
+
+	/// Runs frames until `condition` returns `true` for the world after a frame, at most
+	/// `max_frames` of them. Returns the number of frames, or `None` if the condition was never
+	/// met before the app exited or the last frame.
+	pub fn step_until(
+		&mut self,
+		max_frames: u32,
+		mut condition: impl FnMut(&World) -> bool,
+	) -> Option<u32> {
+		for frames in 1..=max_frames {
+			if !self.step_frame() {
+				break;
+			}
+			if condition(&self.app.world) {
+				return Some(frames);
+			}
+		}
+		None
+	}
+
+	/// Runs a frame, returning `false` without running it if the app exited.
+	fn step_frame(&mut self) -> bool {
+		if self.exit.is_none() {
+			self.exit = ScheduleRunnerPlugin::read_exit(&self.app, &mut self.app_exit_event_reader);
+		}
+		if self.exit.is_some() {
+			return false;
+		}
+		let frame_delta = self.frame_delta;
+		self
+			.app
+			.world
+			.get_resource_or_insert_with(MockClock::default)
+			.advance(frame_delta);
+		self.app.update();
+		for capture in &mut self.captures {
+			capture(&mut self.app.world);
+		}
+		self.frame += 1;
+		self.exit = ScheduleRunnerPlugin::read_exit(&self.app, &mut self.app_exit_event_reader);
+		true
+	}
+
+	/// Sends an event, read by the systems during the next frame.
+	///
+	/// # Panics
+	///
+	/// Panics if the event type `E` wasn't added to the app.
+	pub fn send_event<E: Event>(&mut self, event: E) -> &mut Self {
+		self.send_events([event])
+	}
+
+	/// Sends events, read by the systems during the next frame, for example the input events of
+	/// a key press and release.
+	///
+	/// # Panics
+	///
+	/// Panics if the event type `E` wasn't added to the app.
+	pub fn send_events<E: Event>(&mut self, events: impl IntoIterator<Item = E>) -> &mut Self {
+		self
+			.app
+			.world
+			.get_resource_mut::<Events<E>>()
+			.unwrap_or_else(|| {
+				panic!(
+					"event `{}` wasn't added to the app, consider calling `add_event`",
+					std::any::type_name::<E>()
+				)
+			})
+			.extend(events);
+		self
+	}
+
+	/// Captures the events of type `E` sent during the frames stepped from now on, so they can
+	/// be checked with [`captured_events`](Self::captured_events) even after they were dropped
+	/// from the [`Events`] resource.
+	///
+	/// Capturing the same event type again has no effect.
+	pub fn capture_events<E: Event + Clone>(&mut self) -> &mut Self {
+		if self
+			.app
+			.world
+			.contains_resource::<CapturedEvents<E>>()
+		{
+			return self;
+		}
+		// skip the events already sent
+		let mut reader = self
+			.app
+			.world
+			.get_resource::<Events<E>>()
+			.map(|events| events.get_reader_current())
+			.unwrap_or_default();
+		self
+			.app
+			.world
+			.insert_resource(CapturedEvents::<E> { events: Vec::new() });
+		self
+			.captures
+			.push(Box::new(move |world: &mut World| {
+				world.resource_scope(|world, mut captured: Mut<CapturedEvents<E>>| {
+					if let Some(events) = world.get_resource::<Events<E>>() {
+						captured
+							.events
+							.extend(reader.iter(events).cloned());
+					}
+				});
+			}));
+		self
+	}
+
+	/// The events of type `E` captured so far, in the order they were sent.
+	///
+	/// # Panics
+	///
+	/// Panics if the events of type `E` aren't [captured](Self::capture_events).
+	pub fn captured_events<E: Event>(&self) -> &[E] {
+		&self.captured::<E>().events
+	}
+
+	/// Takes the events of type `E` captured so far, in the order they were sent.
+	///
+	/// # Panics
+	///
+	/// Panics if the events of type `E` aren't [captured](Self::capture_events).
+	pub fn take_captured_events<E: Event>(&mut self) -> Vec<E> {
+		self.captured::<E>();
+		std::mem::take(
+			&mut self
+				.app
+				.world
+				.resource_mut::<CapturedEvents<E>>()
+				.events,
+		)
+	}
+
+	fn captured<E: Event>(&self) -> &CapturedEvents<E> {
+		self
+			.app
+			.world
+			.get_resource::<CapturedEvents<E>>()
+			.unwrap_or_else(|| {
+				panic!(
+					"event `{}` isn't captured, consider calling `capture_events`",
+					std::any::type_name::<E>()
+				)
+			})
+	}
+
+	/// Returns `true` if an [`AppExit`] event was sent during a frame, after which no frame
+	/// runs.
+	pub fn exit_requested(&self) -> bool {
+		self.exit.is_some()
+	}
+
+	/// Returns the components `Q` of every entity matching the filter `F`.
+	pub fn query<Q, F>(&mut self) -> Vec<ROQueryItem<'_, Q>>
+	where
+		Q: WorldQuery,
+		F: WorldQuery,
+	{
+		let mut query = self.app.world.query_filtered::<Q, F>();
+		query.iter(&self.app.world).collect()
+	}
+
+	/// Returns the number of entities matching the filter `F`.
+	pub fn count<F: WorldQuery>(&mut self) -> usize {
+		self
+			.app
+			.world
+			.query_filtered::<Entity, F>()
+			.iter(&self.app.world)
+			.count()
+	}
+
+	/// Asserts that `expected` entities match the filter `F`.
+	#[track_caller]
+	pub fn assert_count<F: WorldQuery>(&mut self, expected: usize) -> &mut Self {
+		let count = self.count::<F>();
+		assert_eq!(
+			count,
+			expected,
+			"expected {} entities matching `{}` at frame {}, found {}",
+			expected,
+			std::any::type_name::<F>(),
+			self.frame,
+			count
+		);
+		self
+	}
+
+	/// Asserts that the resource `R` equals `expected`.
+	#[track_caller]
+	pub fn assert_resource<R: Resource + PartialEq + Debug>(&self, expected: &R) -> &Self {
+		assert_eq!(
+			self.app.world.resource::<R>(),
+			expected,
+			"unexpected value of resource `{}` at frame {}",
+			std::any::type_name::<R>(),
+			self.frame
+		);
+		self
+	}
+
+	/// Asserts that the component `C` of `entity` equals `expected`.
+	#[track_caller]
+	pub fn assert_component<C: Component + PartialEq + Debug>(
+		&self,
+		entity: Entity,
+		expected: &C,
+	) -> &Self {
+		let component = self
+			.app
+			.world
+			.get::<C>(entity)
+			.unwrap_or_else(|| {
+				panic!(
+					"entity {:?} has no component `{}` at frame {}",
+					entity,
+					std::any::type_name::<C>(),
+					self.frame
+				)
+			});
+		assert_eq!(
+			component,
+			expected,
+			"unexpected value of component `{}` of entity {:?} at frame {}",
+			std::any::type_name::<C>(),
+			entity,
+			self.frame
+		);
+		self
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy_ecs::{
+		event::{EventReader, EventWriter},
+		prelude::With,
+		system::{Commands, ResMut},
+	};
+
+	#[derive(Debug, Clone, PartialEq)]
+	struct Ping(u32);
+
+	#[derive(Component, Debug, PartialEq)]
+	struct Value(u32);
+
+	#[test]
+	fn step_and_clock() {
+		let mut app = TestApp::new();
+		app.set_frame_delta(Duration::from_millis(10));
+		let start = app.world.resource::<MockClock>().now();
+
+		app.step(3);
+		assert_eq!(app.frame(), 3);
+		assert_eq!(
+			app.world.resource::<MockClock>().now() - start,
+			Duration::from_millis(30)
+		);
+
+		assert_eq!(app.step_for(Duration::from_millis(45)), 5);
+		assert_eq!(app.frame(), 8);
+		assert_eq!(
+			app.world.resource::<MockClock>().now() - start,
+			Duration::from_millis(80)
+		);
+	}
+
+	#[test]
+	fn events_and_assertions() {
+		let mut app = TestApp::new();
+		app.add_event::<Ping>();
+		app.insert_resource(0u32);
+		app.add_system(
+			|mut commands: Commands, mut pings: EventReader<Ping>, mut total: ResMut<u32>| {
+				for ping in pings.iter() {
+					*total += ping.0;
+					commands.spawn().insert(Value(ping.0));
+				}
+			},
+		);
+
+		app.send_events([Ping(1), Ping(2)]);
+		app.step(1);
+		app.assert_resource(&3u32);
+		app.assert_count::<With<Value>>(2);
+
+		let mut values: Vec<(Entity, u32)> = app
+			.query::<(Entity, &Value), ()>()
+			.into_iter()
+			.map(|(entity, value)| (entity, value.0))
+			.collect();
+		values.sort_by_key(|(_, value)| *value);
+		app.assert_component(values[1].0, &Value(2));
+	}
+
+	#[test]
+	fn capture_events_across_frames() {
+		let mut app = TestApp::new();
+		app.add_event::<Ping>();
+		app.insert_resource(0u32);
+		app.add_system(|mut frame: ResMut<u32>, mut pings: EventWriter<Ping>| {
+			*frame += 1;
+			pings.send(Ping(*frame));
+		});
+		app.capture_events::<Ping>();
+
+		app.step(5);
+		assert_eq!(
+			app.captured_events::<Ping>(),
+			&[Ping(1), Ping(2), Ping(3), Ping(4), Ping(5)]
+		);
+		assert_eq!(app.take_captured_events::<Ping>().len(), 5);
+		assert_eq!(
+			app.step_until(10, |world| *world.resource::<u32>() == 7),
+			Some(2)
+		);
+		assert_eq!(app.take_captured_events::<Ping>(), vec![Ping(6), Ping(7)]);
+
+		assert!(!app.exit_requested());
+		app.add_system(|mut exit: EventWriter<AppExit>| exit.send(AppExit));
+		app.step(1);
+		assert!(app.exit_requested());
+
+		// like `RunMode::Loop`, no frame runs after the app exited
+		app.step(3);
+		assert_eq!(app.frame(), 8);
+		assert_eq!(app.step_until(10, |_| true), None);
+	}
+}
@@ -1,17 +1,31 @@
 use crate::{
+	meta_path,
 	path::{AssetPath, AssetPathId, SourcePathId},
-	Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-	AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState, RefChange,
-	RefChangeChannel, SourceInfo,
+	Asset, AssetDependencyGraph, AssetDependencyNode, AssetDuplicate, AssetDynamic, AssetIo,
+	AssetIoError, AssetLifecycleChannel, AssetLifecycleEvent, AssetLoader, AssetProcessor,
+	AssetSaver, AssetSources, Assets, ErasedAssetLoader, ErasedAssetProcessor, ErasedAssetSaver,
+	Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState, LoaderSettings, Reader,
+	RefChange, RefChangeChannel, ServerAssetLifecycle, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
-use bevy_ecs::system::{Res, ResMut};
+use bevy_ecs::{
+	event::EventWriter,
+	system::{Res, ResMut},
+};
 use bevy_log::warn;
 use bevy_tasks::IoTaskPool;
-use bevy_utils::{Entry, HashMap, Uuid};
+use bevy_utils::{Duration, Entry, FixedState, HashMap, HashSet, Instant, Uuid};
 use crossbeam_channel::TryRecvError;
+use futures_lite::AsyncReadExt;
 use parking_lot::{Mutex, RwLock};
-use std::{path::Path, sync::Arc};
+use std::{
+	hash::{BuildHasher, Hasher},
+	path::{Path, PathBuf},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an `AssetServer`.
@@ -41,6 +55,33 @@ pub enum AssetServerError {
 	/// Encountered an error while reading an asset from disk.
 	#[error("encountered an error while reading an asset: {0}")]
 	AssetIoError(#[from] AssetIoError),
+
+	/// The settings given to load an asset are not the settings of its asset loader.
+	#[error("the given settings do not match the settings type of the asset loader: {0}")]
+	IncorrectSettingsType(&'static str),
+
+	/// The loader settings of the `.meta` file of an asset could not be parsed.
+	#[error("invalid loader settings in the meta file of the asset: {0}")]
+	InvalidLoaderSettings(ron::Error),
+
+	/// No asset saver was found for the asset type and the extensions of the path.
+	#[error("no `AssetSaver` found for {0:?}")]
+	MissingAssetSaver(PathBuf),
+
+	/// The asset to save is not in its asset storage.
+	#[error("the asset to save to {0:?} is not loaded")]
+	AssetNotLoaded(PathBuf),
+
+	/// Encountered an error while serializing an asset.
+	#[error("encountered an error while saving an asset: {0}")]
+	AssetSaverError(anyhow::Error),
+}
+
+impl AssetServerError {
+	/// Returns `true` if loading the asset again may succeed, see [`AssetIoError::is_transient`].
+	pub fn is_transient(&self) -> bool {
+		matches!(self, AssetServerError::AssetIoError(err) if err.is_transient())
+	}
 }
 
 fn format_missing_asset_ext(exts: &[String]) -> String {
@@ -62,17 +103,82 @@ pub(crate) struct AssetRefCounter {
 	pub(crate) mark_unused_assets: Arc<Mutex<Vec<HandleId>>>,
 }
 
+/// Creates the fallback asset of an asset type.
+type FallbackAsset = Arc<dyn Fn() -> Box<dyn AssetDynamic> + Send + Sync>;
+
 /// Internal data for the asset server.
 ///
 /// [`AssetServer`] is the public API for interacting with the asset server.
 pub struct AssetServerInternal {
-	pub(crate) asset_io: Box<dyn AssetIo>,
+	pub(crate) asset_io: AssetSources,
 	pub(crate) asset_ref_counter: AssetRefCounter,
 	pub(crate) asset_sources: Arc<RwLock<HashMap<SourcePathId, SourceInfo>>>,
-	pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
-	loaders: RwLock<Vec<Arc<dyn AssetLoader>>>,
+	pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn ServerAssetLifecycle>>>>,
+	loaders: RwLock<Vec<Arc<dyn ErasedAssetLoader>>>,
 	extension_to_loader_index: RwLock<HashMap<String, usize>>,
+	loader_settings: RwLock<HashMap<SourcePathId, Arc<dyn LoaderSettings>>>,
+	processors: RwLock<HashMap<String, Arc<dyn ErasedAssetProcessor>>>,
+	/// Closed once the asset sources are processed at startup, loads wait for it meanwhile.
+	processing: RwLock<Option<async_channel::Receiver<()>>>,
 	handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
+	/// The assets being loaded, until they and their dependencies are loaded.
+	dependencies_pending: Mutex<HashMap<AssetPathId, PendingDependencies>>,
+	/// The sources whose load state changed since [`dependencies_loaded_system`] last ran.
+	load_state_changes: Mutex<HashSet<SourcePathId>>,
+	/// The sources queued for loading, until their load is finished.
+	queued_loads: Mutex<HashSet<SourcePathId>>,
+	savers: RwLock<Vec<Arc<dyn ErasedAssetSaver>>>,
+	/// The assets to save with the type of their asset storage, until it is updated.
+	pending_saves: Mutex<Vec<(Uuid, HandleId, AssetPath<'static>)>>,
+	/// The hash of the contents of the saved assets, to skip reloading the files they were saved
+	/// to.
+	saved_hashes: Mutex<HashMap<PathBuf, u64>>,
+	/// The files which changed with the time of their last change, until they stop changing.
+	#[cfg(all(
+		feature = "filesystem_watcher",
+		all(not(target_arch = "wasm32"), not(target_os = "android"))
+	))]
+	changed_files: Mutex<HashMap<PathBuf, Instant>>,
+	/// The fallback asset of each asset type, see [`AssetServer::set_fallback_asset`].
+	fallbacks: RwLock<HashMap<Uuid, FallbackAsset>>,
+	/// The type of the assets loaded with typed handles, if their type has a fallback asset.
+	handle_types: Mutex<HashMap<HandleId, Uuid>>,
+	retry_settings: RwLock<LoadRetrySettings>,
+	load_failures: Mutex<LoadFailures>,
+	content_deduplication: AtomicBool,
+	/// The sources loaded with each content hash, with their loader and settings, see
+	/// [`SourceInfo::content_hash`].
+	content_hashes: Mutex<HashMap<u64, Vec<(SourcePathId, String)>>>,
+	duplicates: Mutex<HashMap<SourcePathId, AssetDuplicate>>,
+}
+
+/// How the [`AssetServer`] retries loading assets which failed with a transient error, see
+/// [`AssetIoError::is_transient`].
+#[derive(Debug, Clone)]
+pub struct LoadRetrySettings {
+	/// The number of times a load is retried before it fails.
+	pub max_retries: u32,
+	/// The delay before retrying a load.
+	pub delay: Duration,
+}
+
+impl Default for LoadRetrySettings {
+	fn default() -> Self {
+		Self {
+			max_retries: 3,
+			delay: Duration::from_millis(500),
+		}
+	}
+}
+
+/// The failed loads, until they are reported or retried by [`asset_load_failures_system`].
+#[derive(Default)]
+struct LoadFailures {
+	failed: Vec<AssetLoadFailed>,
+	/// The loads to retry, with the time they are retried at.
+	retries: Vec<(AssetPath<'static>, Instant)>,
+	/// The number of times each failing load was retried.
+	attempts: HashMap<AssetPathId, u32>,
 }
 
 /// Loads assets from the filesystem in the background.
@@ -108,24 +214,57 @@ pub struct AssetServer {
 }
 
 impl AssetServer {
-	/// Creates a new asset server with a boxed asset I/O.
+	/// Creates a new asset server with a boxed asset I/O as its default asset source.
+	///
+	/// If the asset I/O is an [`AssetSources`], its sources are used as they are.
 	pub fn with_boxed_io(asset_io: Box<dyn AssetIo>) -> Self {
+		let asset_io = match asset_io.downcast::<AssetSources>() {
+			Ok(asset_sources) => *asset_sources,
+			Err(asset_io) => AssetSources::with_boxed_io(asset_io),
+		};
 		AssetServer {
 			server: Arc::new(AssetServerInternal {
 				loaders: Default::default(),
 				extension_to_loader_index: Default::default(),
+				loader_settings: Default::default(),
+				processors: Default::default(),
+				processing: Default::default(),
 				asset_sources: Default::default(),
 				asset_ref_counter: Default::default(),
 				handle_to_path: Default::default(),
+				dependencies_pending: Default::default(),
+				load_state_changes: Default::default(),
+				queued_loads: Default::default(),
+				savers: Default::default(),
+				pending_saves: Default::default(),
+				saved_hashes: Default::default(),
+				#[cfg(all(
+					feature = "filesystem_watcher",
+					all(not(target_arch = "wasm32"), not(target_os = "android"))
+				))]
+				changed_files: Default::default(),
+				fallbacks: Default::default(),
+				handle_types: Default::default(),
+				retry_settings: Default::default(),
+				load_failures: Default::default(),
+				content_deduplication: Default::default(),
+				content_hashes: Default::default(),
+				duplicates: Default::default(),
 				asset_lifecycles: Default::default(),
 				asset_io,
 			}),
 		}
 	}
 
-	/// Returns the associated asset I/O.
+	/// Returns the associated asset I/O, which routes paths to the [`AssetSources`].
 	pub fn asset_io(&self) -> &dyn AssetIo {
-		&*self.server.asset_io
+		&self.server.asset_io
+	}
+
+	/// Returns the asset sources the assets are loaded from, where more sources and overlays can
+	/// be added.
+	pub fn asset_sources(&self) -> &AssetSources {
+		&self.server.asset_io
 	}
 
 	pub(crate) fn register_asset_type<T: Asset>(&self) -> Assets<T> {
@@ -160,6 +299,7 @@ impl AssetServer {
 	where
 		T: AssetLoader,
 	{
+		let loader: Arc<dyn ErasedAssetLoader> = Arc::new(loader);
 		let mut loaders = self.server.loaders.write();
 		let loader_index = loaders.len();
 		for extension in loader.extensions() {
@@ -169,7 +309,117 @@ impl AssetServer {
 				.write()
 				.insert(extension.to_string(), loader_index);
 		}
-		loaders.push(Arc::new(loader));
+		loaders.push(loader);
+	}
+
+	/// Adds the provided asset saver to the server.
+	///
+	/// If `saver` saves the same asset type with one or more extensions of savers that came
+	/// before it, it takes precedence over them for these extensions.
+	pub fn add_saver<T>(&self, saver: T)
+	where
+		T: AssetSaver,
+	{
+		self.server.savers.write().push(Arc::new(saver));
+	}
+
+	/// Sets the asset given to the typed handles of type `T` whose asset failed to load, so missing
+	/// content is visible instead of silently absent.
+	///
+	/// Only assets loaded with a typed handle, like with [`AssetServer::load`], get the fallback
+	/// asset. Their load state stays [`LoadState::Failed`], and the fallback asset is replaced
+	/// once the asset is reloaded successfully.
+	pub fn set_fallback_asset<T: Asset + Clone>(&self, asset: T) {
+		self
+			.server
+			.fallbacks
+			.write()
+			.insert(T::TYPE_UUID, Arc::new(move || Box::new(asset.clone())));
+	}
+
+	/// Enables or disables deduplicating assets by the contents of their sources, disabled by
+	/// default.
+	///
+	/// When enabled, a source with the same contents and loader settings as a source loaded
+	/// already isn't loaded again: the handles of its assets point to the assets of the original
+	/// source instead, see [`Assets::get_alias`]. This avoids duplicate GPU uploads for example
+	/// when the same texture is loaded from different paths. The duplicates are listed by
+	/// [`AssetServer::get_duplicates`].
+	///
+	/// A source is read into memory once to be hashed, compared and loaded, so it isn't
+	/// streamed to its loader. The source it duplicates is read again in chunks to compare their
+	/// contents. Sources whose loader settings can't be serialized are never deduplicated.
+	pub fn set_content_deduplication(&self, enabled: bool) {
+		self
+			.server
+			.content_deduplication
+			.store(enabled, Ordering::Relaxed);
+	}
+
+	/// Gets the sources found to be duplicates of other sources, see
+	/// [`AssetServer::set_content_deduplication`].
+	pub fn get_duplicates(&self) -> Vec<AssetDuplicate> {
+		self
+			.server
+			.duplicates
+			.lock()
+			.values()
+			.cloned()
+			.collect()
+	}
+
+	/// Sets how loads failing with a transient error are retried.
+	pub fn set_load_retry_settings(&self, settings: LoadRetrySettings) {
+		*self.server.retry_settings.write() = settings;
+	}
+
+	/// Adds the provided asset processor to the server.
+	///
+	/// `.meta` files pick the processor by its type name, see [`ProcessMeta`](crate::ProcessMeta).
+	/// If a processor with the same type was already added, it is replaced.
+	pub fn add_processor<T>(&self, processor: T)
+	where
+		T: AssetProcessor,
+	{
+		self
+			.server
+			.processors
+			.write()
+			.insert(std::any::type_name::<T>().to_string(), Arc::new(processor));
+	}
+
+	pub(crate) fn get_processor(&self, name: &str) -> Option<Arc<dyn ErasedAssetProcessor>> {
+		self.server.processors.read().get(name).cloned()
+	}
+
+	/// Makes the loads wait until the channel of `processing` is closed, once the asset sources
+	/// are processed.
+	pub(crate) fn wait_for_processing(&self, processing: async_channel::Receiver<()>) {
+		*self.server.processing.write() = Some(processing);
+	}
+
+	/// Runs `loader` on the `bytes` of the source at `path` without creating the loaded assets, to
+	/// get their metadata.
+	pub(crate) async fn load_source_meta(
+		&self,
+		loader: &dyn ErasedAssetLoader,
+		settings: Arc<dyn LoaderSettings>,
+		path: &Path,
+		bytes: &[u8],
+		asset_io: &dyn AssetIo,
+	) -> Result<SourceMeta, AssetServerError> {
+		let mut load_context = LoadContext::new(
+			path,
+			&self.server.asset_ref_counter.channel,
+			asset_io,
+			0,
+			settings,
+		);
+		loader
+			.load(bytes, &mut load_context)
+			.await
+			.map_err(AssetServerError::AssetLoaderError)?;
+		Ok(load_context.get_asset_metas())
 	}
 
 	/// Enable watching of the filesystem for changes, if support is available, starting from after
@@ -201,7 +451,10 @@ impl AssetServer {
 		HandleUntyped::strong(id.into(), sender)
 	}
 
-	fn get_asset_loader(&self, extension: &str) -> Result<Arc<dyn AssetLoader>, AssetServerError> {
+	fn get_asset_loader(
+		&self,
+		extension: &str,
+	) -> Result<Arc<dyn ErasedAssetLoader>, AssetServerError> {
 		let index = {
 			// scope map to drop lock as soon as possible
 			let map = self.server.extension_to_loader_index.read();
@@ -214,10 +467,10 @@ impl AssetServer {
 			})
 	}
 
-	fn get_path_asset_loader<P: AsRef<Path>>(
+	pub(crate) fn get_path_asset_loader<P: AsRef<Path>>(
 		&self,
 		path: P,
-	) -> Result<Arc<dyn AssetLoader>, AssetServerError> {
+	) -> Result<Arc<dyn ErasedAssetLoader>, AssetServerError> {
 		let s = path
 			.as_ref()
 			.file_name()
@@ -255,15 +508,42 @@ impl AssetServer {
 	}
 
 	/// Gets the load state of an asset from the provided handle.
+	///
+	/// An asset whose load is queued is [`LoadState::Loading`].
 	pub fn get_load_state<H: Into<HandleId>>(&self, handle: H) -> LoadState {
+		match handle.into() {
+			HandleId::AssetPathId(id) => {
+				let asset_sources = self.server.asset_sources.read();
+				match asset_sources.get(&id.source_path_id()) {
+					Some(info) => info.load_state,
+					// the load didn't start yet
+					None
+						if self
+							.server
+							.queued_loads
+							.lock()
+							.contains(&id.source_path_id()) =>
+					{
+						LoadState::Loading
+					},
+					None => LoadState::NotLoaded,
+				}
+			},
+			HandleId::Id(_, _) => LoadState::NotLoaded,
+		}
+	}
+
+	/// Gets the error the asset of the provided handle last failed to load with, if its load
+	/// state is [`LoadState::Failed`].
+	pub fn get_load_error<H: Into<HandleId>>(&self, handle: H) -> Option<Arc<AssetServerError>> {
 		match handle.into() {
 			HandleId::AssetPathId(id) => {
 				let asset_sources = self.server.asset_sources.read();
 				asset_sources
 					.get(&id.source_path_id())
-					.map_or(LoadState::NotLoaded, |info| info.load_state)
+					.and_then(|info| info.load_error.clone())
 			},
-			HandleId::Id(_, _) => LoadState::NotLoaded,
+			HandleId::Id(_, _) => None,
 		}
 	}
 
@@ -291,6 +571,94 @@ impl AssetServer {
 		load_state
 	}
 
+	/// Gets the load state of an asset and all its dependencies, recursively, from the provided
+	/// handle.
+	///
+	/// This method will only return [`LoadState::Loaded`] if the asset and all its dependencies
+	/// were loaded successfully. An [`AssetDependenciesLoaded`] event is also sent when that
+	/// happens.
+	pub fn get_recursive_load_state<H: Into<HandleId>>(&self, handle: H) -> LoadState {
+		self.get_dependency_graph(handle).load_state()
+	}
+
+	/// Gets the paths of the direct dependencies of an asset from the provided handle, once its
+	/// source is loaded.
+	pub fn get_dependencies<H: Into<HandleId>>(&self, handle: H) -> Vec<AssetPath<'static>> {
+		let id = match handle.into() {
+			HandleId::AssetPathId(id) => id,
+			HandleId::Id(_, _) => return Vec::new(),
+		};
+		let asset_sources = self.server.asset_sources.read();
+		asset_sources
+			.get(&id.source_path_id())
+			.and_then(|source_info| source_info.meta.as_ref())
+			.and_then(|meta| {
+				meta
+					.assets
+					.iter()
+					.find(|asset| LabelId::from(asset.label.as_deref()) == id.label_id())
+			})
+			.map_or_else(Vec::new, |asset| asset.dependencies.clone())
+	}
+
+	/// Gets the graph of the sources an asset depends on, directly or indirectly, from the
+	/// provided handle.
+	///
+	/// The graph only contains the sources known so far: the dependencies of a source are known
+	/// once it is loaded.
+	pub fn get_dependency_graph<H: Into<HandleId>>(&self, handle: H) -> AssetDependencyGraph {
+		let mut graph = AssetDependencyGraph::default();
+		let id = match handle.into() {
+			HandleId::AssetPathId(id) => id.source_path_id(),
+			HandleId::Id(_, _) => return graph,
+		};
+		let asset_sources = self.server.asset_sources.read();
+		let queued_loads = self.server.queued_loads.lock();
+		let node = |source_info: &SourceInfo| AssetDependencyNode {
+			path: source_info.path.clone(),
+			load_state: source_info.load_state,
+			load_error: source_info.load_error.clone(),
+			dependencies: source_info
+				.meta
+				.as_ref()
+				.map_or_else(Vec::new, SourceMeta::dependencies),
+		};
+		match asset_sources.get(&id) {
+			Some(source_info) => graph.nodes.push(node(source_info)),
+			None => return graph,
+		}
+		let mut visited = HashSet::default();
+		visited.insert(id);
+		let mut index = 0;
+		while index < graph.nodes.len() {
+			for dependency in graph.nodes[index].dependencies.clone() {
+				let dependency_id = SourcePathId::from(dependency.as_path());
+				if !visited.insert(dependency_id) {
+					continue;
+				}
+				graph
+					.nodes
+					.push(match asset_sources.get(&dependency_id) {
+						Some(source_info) => node(source_info),
+						// dependencies are queued by the source depending on them, a dependency
+						// which isn't won't be loaded
+						None => AssetDependencyNode {
+							path: dependency,
+							load_state: if queued_loads.contains(&dependency_id) {
+								LoadState::Loading
+							} else {
+								LoadState::NotLoaded
+							},
+							load_error: None,
+							dependencies: Vec::new(),
+						},
+					});
+			}
+			index += 1;
+		}
+		graph
+	}
+
 	/// Queues an [`Asset`] at the provided relative path for asynchronous loading.
 	///
 	/// The absolute path to the asset is `"ROOT/ASSET_FOLDER_NAME/path"`. Its extension is then
@@ -316,9 +684,101 @@ impl AssetServer {
 	/// [asset loader]: AssetLoader
 	#[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
 	pub fn load<'a, T: Asset, P: Into<AssetPath<'a>>>(&self, path: P) -> Handle<T> {
+		let path = path.into();
+		self.track_handle_type::<T>(path.get_id().into());
+		self.load_untyped(path).typed()
+	}
+
+	/// Queues an [`Asset`] at the provided relative path for asynchronous loading, with the given
+	/// [`AssetLoader::Settings`] instead of the `loader_settings` of its `.meta` file.
+	///
+	/// The settings are kept and used again when the asset source is reloaded, until its assets
+	/// are unloaded. If the source was already loaded, it has to be reloaded with
+	/// [`AssetServer::reload_asset`] for the settings to apply. The asset fails to load if `S` is
+	/// not the settings type of its loader.
+	///
+	/// See [`load`](AssetServer::load).
+	#[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+	pub fn load_with_settings<'a, T: Asset, P: Into<AssetPath<'a>>, S: LoaderSettings>(
+		&self,
+		path: P,
+		settings: S,
+	) -> Handle<T> {
+		let path = path.into();
+		self
+			.server
+			.loader_settings
+			.write()
+			.insert(path.get_id().source_path_id(), Arc::new(settings));
+		self.track_handle_type::<T>(path.get_id().into());
 		self.load_untyped(path).typed()
 	}
 
+	/// Remembers the type of a handle about to be loaded, to give it the fallback asset of its type if
+	/// the load fails.
+	fn track_handle_type<T: Asset>(&self, id: HandleId) {
+		if self
+			.server
+			.fallbacks
+			.read()
+			.contains_key(&T::TYPE_UUID)
+		{
+			self
+				.server
+				.handle_types
+				.lock()
+				.insert(id, T::TYPE_UUID);
+		}
+	}
+
+	/// Gets the settings the asset source at `asset_path` is loaded with: the settings given to
+	/// [`AssetServer::load_with_settings`], or else the ones of its `.meta` file. Also returns
+	/// whether the source has a `.meta` file.
+	///
+	/// The `.meta` file is only read if the loader has settings and none were given, so loading
+	/// assets without settings doesn't request a missing file, which is costly on the web.
+	async fn get_loader_settings(
+		&self,
+		loader: &dyn ErasedAssetLoader,
+		asset_path: &AssetPath<'_>,
+	) -> Result<(Arc<dyn LoaderSettings>, bool), AssetServerError> {
+		let settings = self
+			.server
+			.loader_settings
+			.read()
+			.get(&asset_path.get_id().source_path_id())
+			.cloned();
+		match settings {
+			Some(settings) if loader.is_settings(&*settings) => return Ok((settings, false)),
+			Some(_) => {
+				return Err(AssetServerError::IncorrectSettingsType(
+					loader.settings_type_name(),
+				))
+			},
+			None if !loader.has_settings() => {
+				return loader
+					.read_settings(None)
+					.map(|settings| (settings, false))
+					.map_err(AssetServerError::InvalidLoaderSettings)
+			},
+			None => {},
+		}
+		let meta = match self
+			.asset_io()
+			.load_path(&meta_path(asset_path.path()))
+			.await
+		{
+			Ok(meta) => Some(String::from_utf8_lossy(&meta).into_owned()),
+			Err(AssetIoError::NotFound(_)) => None,
+			Err(err) => return Err(err.into()),
+		};
+		let has_meta = meta.is_some();
+		loader
+			.read_settings(meta.as_deref())
+			.map(|settings| (settings, has_meta))
+			.map_err(AssetServerError::InvalidLoaderSettings)
+	}
+
 	async fn load_async(
 		&self,
 		asset_path: AssetPath<'_>,
@@ -326,8 +786,15 @@ impl AssetServer {
 	) -> Result<AssetPathId, AssetServerError> {
 		let asset_path_id: AssetPathId = asset_path.get_id();
 
+		// the processed assets are loaded once they are processed
+		let processing = self.server.processing.read().clone();
+		if let Some(processing) = processing {
+			let _ = processing.recv().await;
+		}
+
 		// load metadata and update source info. this is done in a scope to ensure we release the
 		// locks before loading
+		let mut duplicates_to_reload = Vec::new();
 		let version = {
 			let mut asset_sources = self.server.asset_sources.write();
 			let source_info = match asset_sources.entry(asset_path_id.source_path_id()) {
@@ -336,12 +803,29 @@ impl AssetServer {
 					asset_types: Default::default(),
 					committed_assets: Default::default(),
 					load_state: LoadState::NotLoaded,
+					load_error: None,
+					content_hash: None,
 					meta: None,
 					path: asset_path.path().to_owned(),
 					version: 0,
 				}),
 			};
 
+			// the asset will be ready once it is loaded, unless it is loaded already
+			if force
+				|| !source_info
+					.committed_assets
+					.contains(&asset_path_id.label_id())
+			{
+				self.server.dependencies_pending.lock().insert(
+					asset_path_id,
+					PendingDependencies {
+						path: asset_path.to_owned(),
+						waiting: None,
+					},
+				);
+			}
+
 			// if asset is already loaded or is loading, don't load again
 			if !force
 				&& (source_info
@@ -353,11 +837,31 @@ impl AssetServer {
 			}
 
 			source_info.load_state = LoadState::Loading;
+			self.load_state_changed(asset_path_id.source_path_id());
 			source_info.committed_assets.clear();
 			source_info.version += 1;
 			source_info.meta = None;
+			source_info.load_error = None;
+			if let Some(content_hash) = source_info.content_hash.take() {
+				self.forget_content_hash(asset_path_id.source_path_id(), content_hash);
+			}
+
+			// the duplicates of a changed source have to be loaded again
+			let mut duplicates = self.server.duplicates.lock();
+			duplicates.remove(&asset_path_id.source_path_id());
+			duplicates.retain(|_, duplicate| {
+				if duplicate.original == source_info.path {
+					duplicates_to_reload.push(duplicate.path.clone());
+					false
+				} else {
+					true
+				}
+			});
 			source_info.version
 		};
+		for path in duplicates_to_reload {
+			self.load_untracked(AssetPath::new(path, None), true);
+		}
 
 		let set_asset_failed = || {
 			let mut asset_sources = self.server.asset_sources.write();
@@ -365,6 +869,7 @@ impl AssetServer {
 				.get_mut(&asset_path_id.source_path_id())
 				.expect("`AssetSource` should exist at this point.");
 			source_info.load_state = LoadState::Failed;
+			self.load_state_changed(asset_path_id.source_path_id());
 		};
 
 		// get the according asset loader
@@ -376,29 +881,77 @@ impl AssetServer {
 			},
 		};
 
-		// load the asset bytes
-		let bytes = match self
+		let (settings, has_meta) = match self
+			.get_loader_settings(&*asset_loader, &asset_path)
+			.await
+		{
+			Ok(settings) => settings,
+			Err(err) => {
+				set_asset_failed();
+				return Err(err);
+			},
+		};
+
+		// open the asset source
+		let mut reader = match self
 			.asset_io()
-			.load_path(asset_path.path())
+			.read_path(asset_path.path())
 			.await
 		{
-			Ok(bytes) => bytes,
+			Ok(reader) => reader,
 			Err(err) => {
 				set_asset_failed();
 				return Err(AssetServerError::AssetIoError(err));
 			},
 		};
 
+		// look for a loaded source with the same contents and loader settings. settings which
+		// can't be serialized can't be compared, so their sources aren't deduplicated
+		let mut content_key = None;
+		let settings_key = self
+			.server
+			.content_deduplication
+			.load(Ordering::Relaxed)
+			.then(|| loader_settings_key(&*asset_loader, &*settings))
+			.flatten();
+		if let Some(settings_key) = settings_key {
+			// the source is kept in memory so it's only read once to hash it, compare it and
+			// load it
+			let mut contents = Vec::new();
+			if let Err(err) = reader.read_to_end(&mut contents).await {
+				set_asset_failed();
+				return Err(AssetServerError::AssetIoError(AssetIoError::Io(err)));
+			}
+			let content_hash = source_content_hash(&contents, &settings_key);
+			if let Some(original_id) = self
+				.find_duplicate(&asset_path, content_hash, &settings_key, &contents)
+				.await
+			{
+				if self.alias_duplicate(
+					&asset_path,
+					version,
+					original_id,
+					content_hash,
+					contents.len(),
+				) {
+					return Ok(asset_path_id);
+				}
+			}
+			reader = Box::new(futures_lite::io::Cursor::new(contents));
+			content_key = Some((content_hash, settings_key));
+		}
+
 		// load the asset source using the corresponding AssetLoader
 		let mut load_context = LoadContext::new(
 			asset_path.path(),
 			&self.server.asset_ref_counter.channel,
 			self.asset_io(),
 			version,
+			settings,
 		);
 
 		if let Err(err) = asset_loader
-			.load(&bytes, &mut load_context)
+			.load_reader(&mut reader, &mut load_context)
 			.await
 			.map_err(AssetServerError::AssetLoaderError)
 		{
@@ -420,6 +973,8 @@ impl AssetServer {
 		if source_info.is_loaded() {
 			source_info.load_state = LoadState::Loaded;
 		}
+		// the dependencies are known now
+		self.load_state_changed(asset_path_id.source_path_id());
 
 		// reset relevant SourceInfo fields
 		source_info.committed_assets.clear();
@@ -427,6 +982,16 @@ impl AssetServer {
 		source_info.asset_types.clear();
 
 		source_info.meta = Some(load_context.get_asset_metas());
+		if let Some((content_hash, settings_key)) = content_key {
+			source_info.content_hash = Some(content_hash);
+			self
+				.server
+				.content_hashes
+				.lock()
+				.entry(content_hash)
+				.or_default()
+				.push((asset_path_id.source_path_id(), settings_key));
+		}
 
 		// load asset dependencies and prepare asset type hashmap
 		for (label, loaded_asset) in &mut load_context.labeled_assets {
@@ -440,14 +1005,155 @@ impl AssetServer {
 			}
 		}
 
-		self
-			.asset_io()
-			.watch_path_for_changes(asset_path.path())
-			.unwrap();
+		self.watch_path_for_changes(asset_path.path());
+		if has_meta {
+			self.watch_path_for_changes(&meta_path(asset_path.path()));
+		}
 		self.create_assets_in_load_context(&mut load_context);
 		Ok(asset_path_id)
 	}
 
+	/// Watches the file at `path` for changes, if the asset I/O supports it.
+	fn watch_path_for_changes(&self, path: &Path) {
+		if let Err(err) = self.asset_io().watch_path_for_changes(path) {
+			warn!("failed to watch {:?} for changes: {}", path, err);
+		}
+	}
+
+	/// Finds a loaded source with the same contents and loader settings as the source at
+	/// `asset_path`, whose contents are given. The contents of the sources with the same hash are
+	/// compared, as hashes can collide.
+	async fn find_duplicate(
+		&self,
+		asset_path: &AssetPath<'_>,
+		content_hash: u64,
+		settings_key: &str,
+		contents: &[u8],
+	) -> Option<SourcePathId> {
+		let source_path_id = asset_path.get_id().source_path_id();
+		let candidates: Vec<_> = self
+			.server
+			.content_hashes
+			.lock()
+			.get(&content_hash)
+			.into_iter()
+			.flatten()
+			.filter(|(id, key)| *id != source_path_id && key == settings_key)
+			.map(|(id, _)| *id)
+			.collect();
+		for original_id in candidates {
+			let original_path = match self
+				.server
+				.asset_sources
+				.read()
+				.get(&original_id)
+			{
+				Some(original) if original.content_hash == Some(content_hash) => original.path.clone(),
+				_ => continue,
+			};
+			if self
+				.same_contents(contents, &original_path)
+				.await
+			{
+				return Some(original_id);
+			}
+		}
+		None
+	}
+
+	/// Returns `true` if the file at `other_path` has the given contents, reading it in chunks.
+	async fn same_contents(&self, contents: &[u8], other_path: &Path) -> bool {
+		let mut other_reader = match self.asset_io().read_path(other_path).await {
+			Ok(other_reader) => other_reader,
+			Err(_) => return false,
+		};
+		let mut other_chunk = vec![0; CONTENT_CHUNK_SIZE];
+		let mut chunks = contents.chunks(CONTENT_CHUNK_SIZE);
+		loop {
+			match read_chunk(&mut other_reader, &mut other_chunk).await {
+				Ok(0) => return chunks.next().is_none(),
+				Ok(len) if chunks.next() == Some(&other_chunk[..len]) => {},
+				_ => return false,
+			}
+		}
+	}
+
+	/// Removes a source from the sources loaded with a content hash.
+	fn forget_content_hash(&self, source_path_id: SourcePathId, content_hash: u64) {
+		let mut content_hashes = self.server.content_hashes.lock();
+		if let Some(sources) = content_hashes.get_mut(&content_hash) {
+			sources.retain(|(id, _)| *id != source_path_id);
+			if sources.is_empty() {
+				content_hashes.remove(&content_hash);
+			}
+		}
+	}
+
+	/// Makes the assets of the source at `asset_path` aliases of the assets of the source
+	/// `original_id` with the same contents, returning `false` if it isn't loaded anymore.
+	fn alias_duplicate(
+		&self,
+		asset_path: &AssetPath<'_>,
+		version: usize,
+		original_id: SourcePathId,
+		content_hash: u64,
+		size: usize,
+	) -> bool {
+		let source_path_id = asset_path.get_id().source_path_id();
+		let mut asset_sources = self.server.asset_sources.write();
+		let original = match asset_sources.get(&original_id) {
+			// the original may have changed or been unloaded since
+			Some(original)
+				if original.content_hash == Some(content_hash)
+					&& original.load_state == LoadState::Loaded =>
+			{
+				original.clone()
+			},
+			_ => return false,
+		};
+		let source_info = asset_sources
+			.get_mut(&source_path_id)
+			.expect("`AssetSource` should exist at this point.");
+		// a newer version is being loaded
+		if version != source_info.version {
+			return true;
+		}
+
+		source_info.meta = original.meta;
+		source_info.asset_types = original.asset_types;
+		source_info.content_hash = Some(content_hash);
+		if source_info.is_loaded() {
+			source_info.load_state = LoadState::Loaded;
+		}
+		self.load_state_changed(source_path_id);
+		let asset_lifecycles = self.server.asset_lifecycles.read();
+		for (label_id, type_uuid) in &source_info.asset_types {
+			if let Some(asset_lifecycle) = asset_lifecycles.get(type_uuid) {
+				asset_lifecycle.alias_asset(
+					AssetPathId::new(source_path_id, *label_id).into(),
+					AssetPathId::new(original_id, *label_id).into(),
+					version,
+				);
+			}
+		}
+
+		bevy_log::info!(
+			"{:?} has the same contents as {:?}, using its assets",
+			asset_path.path(),
+			original.path
+		);
+		self.server.duplicates.lock().insert(
+			source_path_id,
+			AssetDuplicate {
+				path: asset_path.path().to_owned(),
+				original: original.path,
+				size,
+			},
+		);
+		self.watch_path_for_changes(asset_path.path());
+		true
+	}
+
 	/// Queues the [`Asset`] at the provided path for loading and returns an untyped handle.
 	///
 	/// See [`load`](AssetServer::load).
@@ -457,36 +1163,360 @@ impl AssetServer {
 		self.get_handle_untyped(handle_id)
 	}
 
-	/// Force an [`Asset`] to be reloaded.
+	/// Queues the [`Asset`] of the provided handle to be saved to the provided path.
 	///
-	/// This is useful for custom hot-reloading or for supporting `watch_for_changes`
-	/// in custom [`AssetIo`] implementations.
-	pub fn reload_asset<'a, P: Into<AssetPath<'a>>>(&self, path: P) {
-		self.load_untracked(path.into(), true);
+	/// The [`AssetSaver`] of the asset type is picked by the extension of the path, then the
+	/// asset is serialized the next time its [`Assets`] collection is updated, and written
+	/// asynchronously through the [`AssetIo`]. Saving an asset to the path it was loaded from
+	/// doesn't reload it when watching for changes. Errors are logged, including when the asset
+	/// type wasn't [added](crate::AddAsset::add_asset), as its collection is never updated.
+	pub fn save<'a, T: Asset, P: Into<AssetPath<'a>>>(&self, handle: &Handle<T>, path: P) {
+		if !self
+			.server
+			.asset_lifecycles
+			.read()
+			.contains_key(&T::TYPE_UUID)
+		{
+			warn!(
+				"Cannot save {:?}: the asset type {} was not added",
+				path.into().path(),
+				std::any::type_name::<T>()
+			);
+			return;
+		}
+		self
+			.server
+			.pending_saves
+			.lock()
+			.push((T::TYPE_UUID, handle.id, path.into().to_owned()));
 	}
 
-	pub(crate) fn load_untracked(&self, asset_path: AssetPath<'_>, force: bool) -> HandleId {
-		let server = self.clone();
-		let owned_path = asset_path.to_owned();
-		IoTaskPool::get()
-			.spawn(async move {
-				if let Err(err) = server.load_async(owned_path, force).await {
+	fn get_path_asset_saver(
+		&self,
+		type_uuid: Uuid,
+		path: &Path,
+	) -> Result<Arc<dyn ErasedAssetSaver>, AssetServerError> {
+		let file_name = path
+			.file_name()
+			.and_then(|file_name| file_name.to_str())
+			.map(str::to_lowercase)
+			.ok_or_else(|| AssetServerError::MissingAssetSaver(path.to_owned()))?;
+		let savers = self.server.savers.read();
+		let mut extension = file_name.as_str();
+		while let Some(index) = extension.find('.') {
+			extension = &extension[index + 1..];
+			if let Some(saver) = savers.iter().rev().find(|saver| {
+				saver.asset_type_uuid() == type_uuid && saver.extensions().contains(&extension)
+			}) {
+				return Ok(saver.clone());
+			}
+		}
+		Err(AssetServerError::MissingAssetSaver(path.to_owned()))
+	}
+
+	/// Serializes the assets of the storage queued to be saved, and writes them.
+	pub(crate) fn save_assets<T: Asset>(&self, assets: &Assets<T>) {
+		let saves: Vec<_> = {
+			let mut pending_saves = self.server.pending_saves.lock();
+			if pending_saves.is_empty() {
+				return;
+			}
+			let (saves, others) = pending_saves
+				.drain(..)
+				.partition(|(type_uuid, ..)| *type_uuid == T::TYPE_UUID);
+			*pending_saves = others;
+			saves
+		};
+		for (_, handle_id, path) in saves {
+			let bytes = self
+				.get_path_asset_saver(T::TYPE_UUID, path.path())
+				.and_then(|saver| {
+					let asset = assets
+						.get(&Handle::weak(handle_id))
+						.ok_or_else(|| AssetServerError::AssetNotLoaded(path.path().to_owned()))?;
+					saver
+						.save(asset)
+						.map_err(AssetServerError::AssetSaverError)
+				});
+			let bytes = match bytes {
+				Ok(bytes) => bytes,
+				Err(err) => {
 					warn!("{}", err);
-				}
-			})
-			.detach();
+					continue;
+				},
+			};
 
-		let handle_id = asset_path.get_id().into();
+			self
+				.server
+				.saved_hashes
+				.lock()
+				.insert(path.path().to_owned(), content_hash(&bytes));
+			let server = self.clone();
+			IoTaskPool::get()
+				.spawn(async move {
+					if let Err(err) = server
+						.asset_io()
+						.write_path(path.path(), &bytes)
+						.await
+					{
+						warn!("{}", AssetServerError::AssetIoError(err));
+					}
+				})
+				.detach();
+		}
+	}
+
+	/// Records a change of the file at `path`, whose asset is reloaded by
+	/// [`reload_changed_files`](Self::reload_changed_files) once the file stops changing.
+	#[cfg(all(
+		feature = "filesystem_watcher",
+		all(not(target_arch = "wasm32"), not(target_os = "android"))
+	))]
+	pub(crate) fn file_changed(&self, path: PathBuf) {
 		self
 			.server
-			.handle_to_path
-			.write()
+			.changed_files
+			.lock()
+			.insert(path, Instant::now());
+	}
+
+	/// Reloads the assets of the changed files which didn't change again for
+	/// [`FILE_CHANGE_SETTLE_TIME`], so a file still being written isn't loaded partially.
+	#[cfg(all(
+		feature = "filesystem_watcher",
+		all(not(target_arch = "wasm32"), not(target_os = "android"))
+	))]
+	pub(crate) fn reload_changed_files(&self) {
+		let now = Instant::now();
+		let mut settled = Vec::new();
+		self
+			.server
+			.changed_files
+			.lock()
+			.retain(|path, changed| {
+				if now - *changed < FILE_CHANGE_SETTLE_TIME {
+					return true;
+				}
+				settled.push(path.clone());
+				false
+			});
+		for path in settled {
+			match path.extension() {
+				Some(extension) if extension == "meta" => {
+					self.reload_changed_meta_file(path.with_extension(""));
+				},
+				_ => self.reload_changed_file(path),
+			}
+		}
+	}
+
+	/// Reloads a loaded asset source with the loader settings of its changed `.meta` file. The
+	/// asset is kept if the new settings are invalid.
+	#[cfg(all(
+		feature = "filesystem_watcher",
+		all(not(target_arch = "wasm32"), not(target_os = "android"))
+	))]
+	fn reload_changed_meta_file(&self, path: PathBuf) {
+		if !self
+			.server
+			.asset_sources
+			.read()
+			.contains_key(&SourcePathId::from(path.as_path()))
+		{
+			return;
+		}
+		let server = self.clone();
+		IoTaskPool::get()
+			.spawn(async move {
+				let asset_path = AssetPath::from(path.as_path());
+				let settings = async {
+					let loader = server.get_path_asset_loader(&path)?;
+					server
+						.get_loader_settings(&*loader, &asset_path)
+						.await
+				};
+				match settings.await {
+					Ok(_) => {
+						server.load_untracked(asset_path, true);
+					},
+					Err(err) => warn!(
+						"not reloading {:?} with the settings of its changed meta file: {}",
+						path, err
+					),
+				}
+			})
+			.detach();
+	}
+
+	/// Reloads the asset of a changed file, unless the file changed because an asset was saved
+	/// to it.
+	///
+	/// The contents of a saved file are compared with the hash recorded when saving it on the
+	/// [`IoTaskPool`], as the file can be modified again by another program.
+	#[cfg(all(
+		feature = "filesystem_watcher",
+		all(not(target_arch = "wasm32"), not(target_os = "android"))
+	))]
+	pub(crate) fn reload_changed_file(&self, path: PathBuf) {
+		let saved_hash = match self.server.saved_hashes.lock().get(&path) {
+			Some(saved_hash) => *saved_hash,
+			None => {
+				self.load_untracked(path.as_path().into(), true);
+				return;
+			},
+		};
+		let server = self.clone();
+		IoTaskPool::get()
+			.spawn(async move {
+				let bytes = server.asset_io().load_path(&path).await;
+				if matches!(bytes, Ok(bytes) if content_hash(&bytes) == saved_hash) {
+					return;
+				}
+				server.server.saved_hashes.lock().remove(&path);
+				server.load_untracked(path.as_path().into(), true);
+			})
+			.detach();
+	}
+
+	/// Force an [`Asset`] to be reloaded.
+	///
+	/// This is useful for custom hot-reloading or for supporting `watch_for_changes`
+	/// in custom [`AssetIo`] implementations.
+	pub fn reload_asset<'a, P: Into<AssetPath<'a>>>(&self, path: P) {
+		self.load_untracked(path.into(), true);
+	}
+
+	pub(crate) fn load_untracked(&self, asset_path: AssetPath<'_>, force: bool) -> HandleId {
+		self
+			.server
+			.queued_loads
+			.lock()
+			.insert(asset_path.get_id().source_path_id());
+		let server = self.clone();
+		let owned_path = asset_path.to_owned();
+		IoTaskPool::get()
+			.spawn(async move {
+				let result = server
+					.load_async(owned_path.clone(), force)
+					.await;
+				server.finish_load(owned_path, result);
+			})
+			.detach();
+
+		let handle_id = asset_path.get_id().into();
+		self
+			.server
+			.handle_to_path
+			.write()
 			.entry(handle_id)
 			.or_insert_with(|| asset_path.to_owned());
 
 		asset_path.into()
 	}
 
+	/// Records the result of a load started by [`load_untracked`](Self::load_untracked),
+	/// retrying it or reporting its failure.
+	fn finish_load(
+		&self,
+		asset_path: AssetPath<'static>,
+		result: Result<AssetPathId, AssetServerError>,
+	) {
+		let path_id = asset_path.get_id();
+		let handle_id = HandleId::from(path_id);
+		self
+			.server
+			.queued_loads
+			.lock()
+			.remove(&path_id.source_path_id());
+		// an unqueued dependency is considered not loaded, the load state has to be checked again
+		self.load_state_changed(path_id.source_path_id());
+		let err = match result {
+			Ok(_) => {
+				self
+					.server
+					.load_failures
+					.lock()
+					.attempts
+					.remove(&path_id);
+				self
+					.server
+					.handle_types
+					.lock()
+					.remove(&handle_id);
+				return;
+			},
+			Err(err) => err,
+		};
+
+		let mut asset_sources = self.server.asset_sources.write();
+		let source_info = asset_sources.get_mut(&path_id.source_path_id());
+		let mut load_failures = self.server.load_failures.lock();
+		if err.is_transient() {
+			let settings = self.server.retry_settings.read().clone();
+			let attempts = load_failures
+				.attempts
+				.entry(path_id)
+				.or_insert(0);
+			if *attempts < settings.max_retries {
+				*attempts += 1;
+				warn!("{}, retrying ({}/{})", err, attempts, settings.max_retries);
+				// the asset is still loading until it is retried
+				if let Some(source_info) = source_info {
+					source_info.load_state = LoadState::Loading;
+				}
+				self
+					.server
+					.queued_loads
+					.lock()
+					.insert(path_id.source_path_id());
+				load_failures
+					.retries
+					.push((asset_path, Instant::now() + settings.delay));
+				return;
+			}
+		}
+		load_failures.attempts.remove(&path_id);
+
+		warn!("{}", err);
+		let error = Arc::new(err);
+		if let Some(source_info) = source_info {
+			source_info.load_error = Some(error.clone());
+		}
+		load_failures.failed.push(AssetLoadFailed {
+			handle: HandleUntyped::weak(handle_id),
+			path: asset_path,
+			error,
+		});
+		drop(load_failures);
+		drop(asset_sources);
+
+		let type_uuid = self
+			.server
+			.handle_types
+			.lock()
+			.remove(&handle_id);
+		let fallback = type_uuid.and_then(|type_uuid| {
+			self
+				.server
+				.fallbacks
+				.read()
+				.get(&type_uuid)
+				.map(|fallback| (type_uuid, fallback.clone()))
+		});
+		if let Some((type_uuid, fallback)) = fallback {
+			// the version of a source is never 0 once it is loading, so the fallback asset isn't
+			// committed and the asset stays failed
+			if let Some(asset_lifecycle) = self
+				.server
+				.asset_lifecycles
+				.read()
+				.get(&type_uuid)
+			{
+				asset_lifecycle.create_asset(handle_id, fallback(), 0);
+			}
+		}
+	}
+
 	/// Loads assets from the specified folder recursively.
 	///
 	/// # Errors
@@ -539,8 +1569,10 @@ impl AssetServer {
 			let ref_counts = self.server.asset_ref_counter.ref_counts.read();
 			let asset_sources = self.server.asset_sources.read();
 			let asset_lifecycles = self.server.asset_lifecycles.read();
+			let mut handle_types = self.server.handle_types.lock();
 			for potential_free in potential_frees.drain(..) {
 				if let Some(&0) = ref_counts.get(&potential_free) {
+					handle_types.remove(&potential_free);
 					let type_uuid = match potential_free {
 						HandleId::Id(type_uuid, _) => Some(type_uuid),
 						HandleId::AssetPathId(id) => asset_sources
@@ -612,6 +1644,15 @@ impl AssetServer {
 		}
 	}
 
+	/// Records that the load state of a source changed, for [`dependencies_loaded_system`].
+	fn load_state_changed(&self, source_path_id: SourcePathId) {
+		self
+			.server
+			.load_state_changes
+			.lock()
+			.insert(source_path_id);
+	}
+
 	// Note: this takes a `ResMut<Assets<T>>` to ensure change detection does not get
 	// triggered unless the `Assets` collection is actually updated.
 	pub(crate) fn update_asset_storage<T: Asset>(&self, mut assets: ResMut<Assets<T>>) {
@@ -636,6 +1677,7 @@ impl AssetServer {
 									.insert(id.label_id());
 								if source_info.is_loaded() {
 									source_info.load_state = LoadState::Loaded;
+									self.load_state_changed(id.source_path_id());
 								}
 							}
 						}
@@ -643,6 +1685,29 @@ impl AssetServer {
 
 					assets.set_untracked(result.id, *result.asset);
 				},
+				Ok(AssetLifecycleEvent::Alias {
+					id,
+					original,
+					version,
+				}) => {
+					if let HandleId::AssetPathId(path_id) = id {
+						let asset_sources =
+							asset_sources_guard.get_or_insert_with(|| self.server.asset_sources.write());
+						if let Some(source_info) = asset_sources.get_mut(&path_id.source_path_id()) {
+							if source_info.version == version {
+								source_info
+									.committed_assets
+									.insert(path_id.label_id());
+								if source_info.is_loaded() {
+									source_info.load_state = LoadState::Loaded;
+									self.load_state_changed(path_id.source_path_id());
+								}
+							}
+						}
+					}
+
+					assets.set_alias(id, &Handle::weak(original));
+				},
 				Ok(AssetLifecycleEvent::Free(handle_id)) => {
 					if let HandleId::AssetPathId(id) = handle_id {
 						let asset_sources =
@@ -652,6 +1717,22 @@ impl AssetServer {
 								.committed_assets
 								.remove(&id.label_id());
 							source_info.load_state = LoadState::Unloaded;
+							self.load_state_changed(id.source_path_id());
+							// the settings of an unloaded source are forgotten, unless it's loading
+							// again with them
+							if source_info.committed_assets.is_empty()
+								&& !self
+									.server
+									.queued_loads
+									.lock()
+									.contains(&id.source_path_id())
+							{
+								self
+									.server
+									.loader_settings
+									.write()
+									.remove(&id.source_path_id());
+							}
 						}
 					}
 					assets.remove(handle_id);
@@ -677,6 +1758,161 @@ fn free_unused_assets_system_impl(asset_server: &AssetServer) {
 	asset_server.mark_unused_assets();
 }
 
+fn content_hash(bytes: &[u8]) -> u64 {
+	let mut hasher = FixedState.build_hasher();
+	hasher.write(bytes);
+	hasher.finish()
+}
+
+/// How long a changed file has to stay unchanged before its asset is reloaded.
+#[cfg(all(
+	feature = "filesystem_watcher",
+	all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+const FILE_CHANGE_SETTLE_TIME: Duration = Duration::from_millis(100);
+
+/// The size of the chunks sources are read in when deduplicating them.
+const CONTENT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Identifies the loader and loader settings of a source, or returns `None` if the settings can't
+/// be serialized.
+fn loader_settings_key(
+	loader: &dyn ErasedAssetLoader,
+	settings: &dyn LoaderSettings,
+) -> Option<String> {
+	let settings = ron::to_string(settings).ok()?;
+	Some(format!("{}{}", loader.type_name(), settings))
+}
+
+/// Hashes the contents of a source with its loader and loader settings.
+fn source_content_hash(contents: &[u8], settings_key: &str) -> u64 {
+	let mut hasher = FixedState.build_hasher();
+	hasher.write(settings_key.as_bytes());
+	hasher.write(contents);
+	hasher.finish()
+}
+
+/// Fills `chunk` from `reader`, returning the number of bytes read, which is less than the size
+/// of the chunk only at the end of the reader.
+async fn read_chunk(reader: &mut Reader, chunk: &mut [u8]) -> std::io::Result<usize> {
+	let mut len = 0;
+	while len < chunk.len() {
+		match reader.read(&mut chunk[len..]).await? {
+			0 => break,
+			read => len += read,
+		}
+	}
+	Ok(len)
+}
+
+/// An event sent once an asset and all its dependencies, recursively, are loaded.
+///
+/// It is sent for every asset loaded by the [`AssetServer`], including the dependencies, and
+/// again when an asset is reloaded. See [`AssetServer::get_recursive_load_state`].
+#[derive(Debug, Clone)]
+pub struct AssetDependenciesLoaded {
+	/// The id of the handles of the asset.
+	pub id: HandleId,
+	/// The path of the asset.
+	pub path: AssetPath<'static>,
+}
+
+/// An asset waiting for its dependencies to load.
+struct PendingDependencies {
+	path: AssetPath<'static>,
+	/// The sources in the dependency graph of the asset that are still loading, `None` until
+	/// the graph is first checked.
+	waiting: Option<HashSet<SourcePathId>>,
+}
+
+/// A system sending an [`AssetDependenciesLoaded`] event for the assets whose dependencies
+/// finished loading.
+///
+/// The dependency graph of an asset is only checked again once the load state of one of the
+/// sources it is waiting for changed. A dependency which failed to load, or whose load was never
+/// started, stops the wait without an event.
+pub fn dependencies_loaded_system(
+	asset_server: Res<AssetServer>,
+	mut events: EventWriter<AssetDependenciesLoaded>,
+) {
+	let changes = std::mem::take(&mut *asset_server.server.load_state_changes.lock());
+	// the lock isn't held while checking the load states, which locks the asset sources
+	let pending = std::mem::take(&mut *asset_server.server.dependencies_pending.lock());
+	let mut still_pending = HashMap::default();
+	for (id, mut pending) in pending {
+		if let Some(waiting) = &pending.waiting {
+			if waiting.is_disjoint(&changes) {
+				still_pending.insert(id, pending);
+				continue;
+			}
+		}
+		let graph = asset_server.get_dependency_graph(id);
+		match graph.load_state() {
+			LoadState::Loaded => events.send(AssetDependenciesLoaded {
+				id: id.into(),
+				path: pending.path,
+			}),
+			LoadState::Loading => {
+				pending.waiting = Some(
+					graph
+						.nodes
+						.iter()
+						.filter(|node| node.load_state == LoadState::Loading)
+						.map(|node| SourcePathId::from(node.path.as_path()))
+						.collect(),
+				);
+				still_pending.insert(id, pending);
+			},
+			LoadState::NotLoaded | LoadState::Failed | LoadState::Unloaded => {},
+		}
+	}
+	// assets queued again meanwhile are checked from scratch
+	let mut dependencies_pending = asset_server.server.dependencies_pending.lock();
+	for (id, pending) in still_pending {
+		dependencies_pending
+			.entry(id)
+			.or_insert(pending);
+	}
+}
+
+/// An event sent when an asset fails to load, once the load isn't retried anymore.
+///
+/// The error is also kept until the asset is loaded again, see
+/// [`AssetServer::get_load_error`].
+#[derive(Debug, Clone)]
+pub struct AssetLoadFailed {
+	/// A weak handle to the asset.
+	pub handle: HandleUntyped,
+	/// The path of the asset.
+	pub path: AssetPath<'static>,
+	/// The error the asset failed to load with.
+	pub error: Arc<AssetServerError>,
+}
+
+/// A system sending an [`AssetLoadFailed`] event for the assets which failed to load, and
+/// retrying the loads which failed with a transient error.
+pub fn asset_load_failures_system(
+	asset_server: Res<AssetServer>,
+	mut events: EventWriter<AssetLoadFailed>,
+) {
+	let mut load_failures = asset_server.server.load_failures.lock();
+	events.send_batch(load_failures.failed.drain(..));
+	let now = Instant::now();
+	let mut retries = Vec::new();
+	load_failures
+		.retries
+		.retain(|(path, retry_at)| {
+			if *retry_at <= now {
+				retries.push(path.clone());
+			}
+			*retry_at > now
+		});
+	drop(load_failures);
+	for path in retries {
+		asset_server.load_untracked(path, true);
+	}
+}
+
 /// A system for freeing assets that have no active handles.
 pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
 	free_unused_assets_system_impl(&asset_server);
@@ -685,18 +1921,22 @@ pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
 #[cfg(test)]
 mod test {
 	use super::*;
-	use crate::{loader::LoadedAsset, update_asset_storage_system};
+	use crate::{loader::LoadedAsset, update_asset_storage_system, MemoryAssetIo, Reader};
 	use bevy_app::App;
 	use bevy_ecs::prelude::*;
 	use bevy_reflect::TypeUuid;
 	use bevy_utils::BoxedFuture;
+	use futures_lite::{AsyncReadExt, AsyncSeekExt};
+	use std::io::SeekFrom;
 
-	#[derive(Debug, TypeUuid)]
+	#[derive(Debug, Clone, TypeUuid)]
 	#[uuid = "a5189b72-0572-4290-a2e0-96f73a491c44"]
 	struct PngAsset;
 
 	struct FakePngLoader;
 	impl AssetLoader for FakePngLoader {
+		type Settings = ();
+
 		fn load<'a>(
 			&'a self,
 			_: &'a [u8],
@@ -713,6 +1953,8 @@ mod test {
 
 	struct FailingLoader;
 	impl AssetLoader for FailingLoader {
+		type Settings = ();
+
 		fn load<'a>(
 			&'a self,
 			_: &'a [u8],
@@ -728,6 +1970,8 @@ mod test {
 
 	struct FakeMultipleDotLoader;
 	impl AssetLoader for FakeMultipleDotLoader {
+		type Settings = ();
+
 		fn load<'a>(
 			&'a self,
 			_: &'a [u8],
@@ -741,6 +1985,146 @@ mod test {
 		}
 	}
 
+	#[derive(Default, serde::Serialize, serde::Deserialize)]
+	struct ScaleSettings {
+		scale: u32,
+	}
+
+	/// Records the scale it loaded each source with.
+	#[derive(Default)]
+	struct ScaleLoader(Arc<Mutex<Vec<u32>>>);
+	impl AssetLoader for ScaleLoader {
+		type Settings = ScaleSettings;
+
+		fn load<'a>(
+			&'a self,
+			_: &'a [u8],
+			ctx: &'a mut LoadContext,
+		) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+			self
+				.0
+				.lock()
+				.push(ctx.settings::<ScaleSettings>().unwrap().scale);
+			ctx.set_default_asset(LoadedAsset::from(PngAsset));
+			Box::pin(async move { Ok(()) })
+		}
+
+		fn extensions(&self) -> &[&str] {
+			&["scale"]
+		}
+	}
+
+	/// Loads files listing the paths of their dependencies, one per line.
+	struct DependenciesLoader;
+	impl AssetLoader for DependenciesLoader {
+		type Settings = ();
+
+		fn load<'a>(
+			&'a self,
+			bytes: &'a [u8],
+			ctx: &'a mut LoadContext,
+		) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+			let mut asset = LoadedAsset::from(PngAsset);
+			for dependency in std::str::from_utf8(bytes).unwrap().lines() {
+				asset.add_dependency(dependency.into());
+			}
+			ctx.set_default_asset(asset);
+			Box::pin(async move { Ok(()) })
+		}
+
+		fn extensions(&self) -> &[&str] {
+			&["deps"]
+		}
+	}
+
+	/// Reads the first 4 bytes of its sources from the reader, and the last 4 bytes as a range.
+	#[derive(Default)]
+	struct HeaderLoader(Arc<Mutex<Vec<Vec<u8>>>>);
+	impl AssetLoader for HeaderLoader {
+		type Settings = ();
+
+		fn load<'a>(
+			&'a self,
+			_: &'a [u8],
+			_: &'a mut LoadContext,
+		) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+			unreachable!("the loader streams its sources")
+		}
+
+		fn load_reader<'a>(
+			&'a self,
+			reader: &'a mut Reader,
+			ctx: &'a mut LoadContext,
+		) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+			Box::pin(async move {
+				let mut header = vec![0; 4];
+				reader.read_exact(&mut header).await?;
+				let len = reader.seek(SeekFrom::End(0)).await?;
+				let footer = ctx
+					.read_asset_range(ctx.path(), len - 4..len)
+					.await?;
+				self.0.lock().extend([header, footer]);
+				ctx.set_default_asset(LoadedAsset::from(PngAsset));
+				Ok(())
+			})
+		}
+
+		fn extensions(&self) -> &[&str] {
+			&["header"]
+		}
+	}
+
+	struct FakePngSaver;
+	impl AssetSaver for FakePngSaver {
+		type Asset = PngAsset;
+
+		fn save(&self, _: &PngAsset) -> Result<Vec<u8>, anyhow::Error> {
+			Ok(b"saved".to_vec())
+		}
+
+		fn extensions(&self) -> &[&str] {
+			&["png"]
+		}
+	}
+
+	/// Fails to read its files with a timeout a given number of times.
+	#[derive(Default)]
+	struct FlakyAssetIo {
+		files: MemoryAssetIo,
+		failures: Mutex<HashMap<PathBuf, u32>>,
+	}
+	impl AssetIo for FlakyAssetIo {
+		fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+			if let Some(failures) = self.failures.lock().get_mut(path) {
+				if *failures > 0 {
+					*failures -= 1;
+					let err = std::io::Error::from(std::io::ErrorKind::TimedOut);
+					return Box::pin(async move { Err(err.into()) });
+				}
+			}
+			self.files.load_path(path)
+		}
+
+		fn read_directory(
+			&self,
+			path: &Path,
+		) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+			self.files.read_directory(path)
+		}
+
+		fn get_metadata(&self, path: &Path) -> Result<crate::Metadata, AssetIoError> {
+			self.files.get_metadata(path)
+		}
+
+		fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+			Ok(())
+		}
+
+		fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+			Ok(())
+		}
+	}
+
 	fn setup(asset_path: impl AsRef<Path>) -> AssetServer {
 		use crate::FileAssetIo;
 		IoTaskPool::init(Default::default);
@@ -882,6 +2266,274 @@ mod test {
 		assert_eq!(asset_server.get_load_state(handle), LoadState::Failed);
 	}
 
+	#[test]
+	fn test_streaming_loader() {
+		let dir = tempfile::tempdir().unwrap();
+		std::fs::write(dir.path().join("a.header"), b"HEAD and the body TAIL").unwrap();
+		let asset_server = setup(dir.path());
+		let loader = HeaderLoader::default();
+		let read = loader.0.clone();
+		asset_server.add_loader(loader);
+		let _assets = asset_server.register_asset_type::<PngAsset>();
+
+		futures_lite::future::block_on(asset_server.load_async("a.header".into(), true)).unwrap();
+		assert_eq!(*read.lock(), [b"HEAD".to_vec(), b"TAIL".to_vec()]);
+	}
+
+	#[test]
+	fn test_load_failures() {
+		let dir = create_dir_and_file("fake.fail");
+		let asset_server = setup(dir.path());
+		asset_server.add_loader(FailingLoader);
+		let assets = asset_server.register_asset_type::<PngAsset>();
+		asset_server.set_fallback_asset(PngAsset);
+
+		let mut app = App::new();
+		app.insert_resource(assets);
+		app.insert_resource(asset_server.clone());
+		app.add_event::<AssetLoadFailed>();
+		app.add_system(update_asset_storage_system::<PngAsset>);
+		app.add_system(asset_load_failures_system);
+
+		let handle: Handle<PngAsset> = asset_server.load("fake.fail");
+		let mut reader = app
+			.world
+			.resource::<Events<AssetLoadFailed>>()
+			.get_reader();
+		let mut failed = Vec::new();
+		for _ in 0..500 {
+			app.update();
+			let events = app.world.resource::<Events<AssetLoadFailed>>();
+			failed.extend(reader.iter(events).cloned());
+			if !failed.is_empty()
+				&& app
+					.world
+					.resource::<Assets<PngAsset>>()
+					.contains(&handle)
+			{
+				break;
+			}
+			std::thread::sleep(std::time::Duration::from_millis(10));
+		}
+
+		assert_eq!(failed.len(), 1);
+		assert_eq!(failed[0].handle.id, handle.id);
+		assert_eq!(failed[0].path.path(), Path::new("fake.fail"));
+		assert!(matches!(
+			*failed[0].error,
+			AssetServerError::AssetLoaderError(_)
+		));
+		assert!(matches!(
+			asset_server.get_load_error(&handle).as_deref(),
+			Some(AssetServerError::AssetLoaderError(_))
+		));
+		assert_eq!(asset_server.get_load_state(&handle), LoadState::Failed);
+		// the fallback asset
+		assert!(app
+			.world
+			.resource::<Assets<PngAsset>>()
+			.contains(&handle));
+	}
+
+	#[test]
+	fn test_load_retries() {
+		let asset_io = FlakyAssetIo::default();
+		for (path, failures) in [("flaky.png", 2), ("broken.png", 4)] {
+			asset_io.files.insert(path, Vec::new());
+			asset_io
+				.failures
+				.lock()
+				.insert(PathBuf::from(path), failures);
+		}
+		IoTaskPool::init(Default::default);
+		let asset_server = AssetServer::from(asset_io);
+		asset_server.add_loader(FakePngLoader);
+		asset_server.set_load_retry_settings(LoadRetrySettings {
+			max_retries: 3,
+			delay: Duration::ZERO,
+		});
+		let assets = asset_server.register_asset_type::<PngAsset>();
+
+		let mut app = App::new();
+		app.insert_resource(assets);
+		app.insert_resource(asset_server.clone());
+		app.add_event::<AssetLoadFailed>();
+		app.add_system(update_asset_storage_system::<PngAsset>);
+		app.add_system(asset_load_failures_system);
+
+		let flaky: Handle<PngAsset> = asset_server.load("flaky.png");
+		let broken: Handle<PngAsset> = asset_server.load("broken.png");
+		for _ in 0..500 {
+			app.update();
+			if asset_server.get_load_state(&flaky) == LoadState::Loaded
+				&& asset_server.get_load_state(&broken) == LoadState::Failed
+			{
+				break;
+			}
+			std::thread::sleep(std::time::Duration::from_millis(10));
+		}
+
+		assert_eq!(asset_server.get_load_state(&flaky), LoadState::Loaded);
+		assert!(asset_server.get_load_error(&flaky).is_none());
+		assert_eq!(asset_server.get_load_state(&broken), LoadState::Failed);
+		assert!(asset_server
+			.get_load_error(&broken)
+			.unwrap()
+			.is_transient());
+	}
+
+	#[test]
+	fn test_content_deduplication() {
+		let dir = tempfile::tempdir().unwrap();
+		for (path, contents) in [("a.png", "a"), ("copy.png", "a"), ("b.png", "b")] {
+			std::fs::write(dir.path().join(path), contents).unwrap();
+		}
+		let asset_server = setup(dir.path());
+		asset_server.add_loader(FakePngLoader);
+		asset_server.set_content_deduplication(true);
+		let assets = asset_server.register_asset_type::<PngAsset>();
+
+		let mut app = App::new();
+		app.insert_resource(assets);
+		app.insert_resource(asset_server.clone());
+		app.add_system(update_asset_storage_system::<PngAsset>);
+		let load = |path: &str| {
+			futures_lite::future::block_on(asset_server.load_async(path.into(), false)).unwrap();
+			asset_server.get_handle::<PngAsset, _>(path)
+		};
+
+		let original = load("a.png");
+		app.update();
+		let copy = load("copy.png");
+		let other = load("b.png");
+		app.update();
+
+		assert_eq!(asset_server.get_load_state(&copy), LoadState::Loaded);
+		let assets = app.world.resource::<Assets<PngAsset>>();
+		assert_eq!(assets.get_alias(&copy), Some(&original));
+		assert!(assets.get(&copy).is_some());
+		assert_eq!(assets.get_alias(&other), None);
+		let duplicates = asset_server.get_duplicates();
+		assert_eq!(duplicates.len(), 1);
+		assert_eq!(duplicates[0].path, Path::new("copy.png"));
+		assert_eq!(duplicates[0].original, Path::new("a.png"));
+		assert_eq!(duplicates[0].size, 1);
+
+		// changing the duplicate would change the original
+		let mut assets = app.world.resource_mut::<Assets<PngAsset>>();
+		assert!(assets.get_mut(&copy).is_none());
+		assert!(assets.get_mut(&original).is_some());
+	}
+
+	#[test]
+	fn test_content_deduplication_compares_contents() {
+		let dir = tempfile::tempdir().unwrap();
+		for path in ["a.png", "copy.png"] {
+			std::fs::write(dir.path().join(path), "a").unwrap();
+		}
+		let asset_server = setup(dir.path());
+		asset_server.add_loader(FakePngLoader);
+		asset_server.set_content_deduplication(true);
+		let assets = asset_server.register_asset_type::<PngAsset>();
+
+		let mut app = App::new();
+		app.insert_resource(assets);
+		app.insert_resource(asset_server.clone());
+		app.add_system(update_asset_storage_system::<PngAsset>);
+		let load = |path: &str| {
+			futures_lite::future::block_on(asset_server.load_async(path.into(), false)).unwrap();
+			asset_server.get_handle::<PngAsset, _>(path)
+		};
+
+		load("a.png");
+		app.update();
+		// the hashes match, but the contents of the original changed since it was loaded
+		std::fs::write(dir.path().join("a.png"), "b").unwrap();
+		let copy = load("copy.png");
+		app.update();
+
+		assert_eq!(asset_server.get_load_state(&copy), LoadState::Loaded);
+		let assets = app.world.resource::<Assets<PngAsset>>();
+		assert_eq!(assets.get_alias(&copy), None);
+		assert!(asset_server.get_duplicates().is_empty());
+	}
+
+	#[test]
+	fn test_loader_settings() {
+		let dir = tempfile::tempdir().unwrap();
+		for file in ["a.scale", "b.scale", "c.scale"] {
+			std::fs::write(dir.path().join(file), b"").unwrap();
+		}
+		std::fs::write(
+			dir.path().join("b.scale.meta"),
+			"(loader_settings: (scale: 2))",
+		)
+		.unwrap();
+		let asset_server = setup(dir.path());
+		let loader = ScaleLoader::default();
+		let scales = loader.0.clone();
+		asset_server.add_loader(loader);
+		let _assets = asset_server.register_asset_type::<PngAsset>();
+		let load =
+			|path: &str| futures_lite::future::block_on(asset_server.load_async(path.into(), true));
+
+		// default settings
+		load("a.scale").unwrap();
+		assert_eq!(scales.lock().last(), Some(&0));
+
+		// settings of the meta file
+		load("b.scale").unwrap();
+		assert_eq!(scales.lock().last(), Some(&2));
+
+		// given settings, also used when reloading
+		let _handle: Handle<PngAsset> =
+			asset_server.load_with_settings("b.scale", ScaleSettings { scale: 3 });
+		load("b.scale").unwrap();
+		assert_eq!(scales.lock().last(), Some(&3));
+
+		let _handle: Handle<PngAsset> = asset_server.load_with_settings("c.scale", ());
+		assert!(matches!(
+			load("c.scale"),
+			Err(AssetServerError::IncorrectSettingsType(_))
+		));
+	}
+
+	#[test]
+	fn test_loader_settings_are_forgotten() {
+		let dir = create_dir_and_file("a.scale");
+		let asset_server = setup(dir.path());
+		asset_server.add_loader(ScaleLoader::default());
+		let assets = asset_server.register_asset_type::<PngAsset>();
+
+		let mut app = App::new();
+		app.insert_resource(assets);
+		app.insert_resource(asset_server.clone());
+		app.add_system(free_unused_assets_system.before(update_asset_storage_system::<PngAsset>));
+		app.add_system(update_asset_storage_system::<PngAsset>);
+
+		let handle: Handle<PngAsset> =
+			asset_server.load_with_settings("a.scale", ScaleSettings { scale: 3 });
+		wait_for_queued_loads(&asset_server);
+		app.update();
+		assert_eq!(asset_server.get_load_state(&handle), LoadState::Loaded);
+		assert_eq!(asset_server.server.loader_settings.read().len(), 1);
+
+		// the settings are forgotten once the asset is unloaded
+		let weak_handle = handle.clone_weak();
+		drop(handle);
+		app.update();
+		app.update();
+		assert_eq!(
+			asset_server.get_load_state(&weak_handle),
+			LoadState::Unloaded
+		);
+		assert!(asset_server
+			.server
+			.loader_settings
+			.read()
+			.is_empty());
+	}
+
 	#[test]
 	fn test_asset_lifecycle() {
 		let dir = create_dir_and_file("fake.png");
@@ -957,6 +2609,252 @@ mod test {
 		assert!(get_asset(&handle, &app.world).is_some());
 	}
 
+	/// Waits for the loads queued by the asset server, including the dependencies they queue.
+	/// Waits for the loads started in the background to finish, failing the test after 10
+	/// seconds.
+	fn wait_for_queued_loads(asset_server: &AssetServer) {
+		let start = Instant::now();
+		while !asset_server
+			.server
+			.queued_loads
+			.lock()
+			.is_empty()
+		{
+			assert!(
+				start.elapsed() < Duration::from_secs(10),
+				"the queued loads didn't finish in time"
+			);
+			std::thread::sleep(Duration::from_millis(1));
+		}
+	}
+
+	#[test]
+	fn test_recursive_load_state() {
+		let dir = tempfile::tempdir().unwrap();
+		for (path, contents) in [
+			("root.deps", "a.png\nmiddle.deps"),
+			("middle.deps", "b.png\nroot.deps"),
+			("broken.deps", "a.png\nmissing.png"),
+			("a.png", ""),
+			("b.png", ""),
+		] {
+			std::fs::write(dir.path().join(path), contents).unwrap();
+		}
+		let asset_server = setup(dir.path());
+		asset_server.add_loader(FakePngLoader);
+		asset_server.add_loader(DependenciesLoader);
+		let assets = asset_server.register_asset_type::<PngAsset>();
+
+		let mut app = App::new();
+		app.insert_resource(assets);
+		app.insert_resource(asset_server.clone());
+		app.add_event::<AssetDependenciesLoaded>();
+		app.add_system(update_asset_storage_system::<PngAsset>);
+		app.add_system(dependencies_loaded_system.after(update_asset_storage_system::<PngAsset>));
+
+		let root: Handle<PngAsset> = asset_server.load("root.deps");
+		let broken: Handle<PngAsset> = asset_server.load("broken.deps");
+		let mut reader = app
+			.world
+			.resource::<Events<AssetDependenciesLoaded>>()
+			.get_reader();
+		wait_for_queued_loads(&asset_server);
+		app.update();
+		let events = app
+			.world
+			.resource::<Events<AssetDependenciesLoaded>>();
+		let loaded: Vec<_> = reader
+			.iter(events)
+			.map(|event| event.id)
+			.collect();
+
+		assert_eq!(
+			asset_server.get_recursive_load_state(&root),
+			LoadState::Loaded
+		);
+		assert_eq!(
+			asset_server.get_recursive_load_state(&broken),
+			LoadState::Failed
+		);
+		assert_eq!(asset_server.get_load_state(&broken), LoadState::Loaded);
+		let dependencies: Vec<_> = asset_server
+			.get_dependencies(&root)
+			.into_iter()
+			.map(|dependency| dependency.get_id())
+			.collect();
+		assert_eq!(
+			dependencies,
+			[
+				AssetPath::from("a.png").get_id(),
+				AssetPath::from("middle.deps").get_id()
+			]
+		);
+
+		let graph = asset_server.get_dependency_graph(&root);
+		let paths: Vec<_> = graph
+			.nodes
+			.iter()
+			.map(|node| node.path.to_str().unwrap())
+			.collect();
+		assert_eq!(paths, ["root.deps", "a.png", "middle.deps", "b.png"]);
+		assert_eq!(graph.progress(), 1.0);
+		assert!(loaded.contains(&root.id));
+		assert!(!loaded.contains(&broken.id));
+	}
+
+	#[test]
+	fn test_wait_for_processing() {
+		let dir = tempfile::tempdir().unwrap();
+		std::fs::write(dir.path().join("a.png"), "").unwrap();
+		let asset_server = setup(dir.path());
+		asset_server.add_loader(FakePngLoader);
+		let _assets = asset_server.register_asset_type::<PngAsset>();
+		let (processing, receiver) = async_channel::bounded(1);
+		asset_server.wait_for_processing(receiver);
+
+		let handle: Handle<PngAsset> = asset_server.load("a.png");
+		std::thread::sleep(std::time::Duration::from_millis(50));
+		// the load waits until the sources are processed
+		assert!(!asset_server
+			.server
+			.queued_loads
+			.lock()
+			.is_empty());
+		assert_eq!(asset_server.get_load_state(&handle), LoadState::Loading);
+
+		drop(processing);
+		wait_for_queued_loads(&asset_server);
+		assert!(asset_server.get_load_error(&handle).is_none());
+	}
+
+	#[test]
+	fn test_unqueued_dependency() {
+		let dir = tempfile::tempdir().unwrap();
+		std::fs::write(dir.path().join("root.deps"), "a.png").unwrap();
+		std::fs::write(dir.path().join("a.png"), "").unwrap();
+		let asset_server = setup(dir.path());
+		asset_server.add_loader(FakePngLoader);
+		asset_server.add_loader(DependenciesLoader);
+		let assets = asset_server.register_asset_type::<PngAsset>();
+
+		let mut app = App::new();
+		app.insert_resource(assets);
+		app.insert_resource(asset_server.clone());
+		app.add_event::<AssetDependenciesLoaded>();
+		app.add_system(free_unused_assets_system.before(update_asset_storage_system::<PngAsset>));
+		app.add_system(update_asset_storage_system::<PngAsset>);
+
+		let root: Handle<PngAsset> = asset_server.load("root.deps");
+		wait_for_queued_loads(&asset_server);
+		app.update();
+		assert_eq!(
+			asset_server.get_recursive_load_state(&root),
+			LoadState::Loaded
+		);
+
+		// the dependency is unloaded before the dependencies of the root are checked, and isn't
+		// loaded again
+		let dependency: Handle<PngAsset> = asset_server.get_handle("a.png");
+		drop(dependency);
+		app.update();
+		app.update();
+		assert_eq!(asset_server.get_load_state("a.png"), LoadState::Unloaded);
+		assert_eq!(
+			asset_server.get_recursive_load_state(&root),
+			LoadState::Unloaded
+		);
+
+		// the root stops waiting for its dependencies without an event
+		app.add_system(dependencies_loaded_system.after(update_asset_storage_system::<PngAsset>));
+		app.update();
+		assert!(app
+			.world
+			.resource::<Events<AssetDependenciesLoaded>>()
+			.is_empty());
+		assert!(asset_server
+			.server
+			.dependencies_pending
+			.lock()
+			.is_empty());
+	}
+
+	#[test]
+	fn test_save_asset() {
+		let dir = create_dir_and_file("fake.png");
+		let asset_server = setup(dir.path());
+		asset_server.add_loader(FakePngLoader);
+		asset_server.add_saver(FakePngSaver);
+		let assets = asset_server.register_asset_type::<PngAsset>();
+
+		let mut app = App::new();
+		app.insert_resource(assets);
+		app.insert_resource(asset_server.clone());
+		app.add_system(update_asset_storage_system::<PngAsset>);
+
+		let id =
+			futures_lite::future::block_on(asset_server.load_async("fake.png".into(), false)).unwrap();
+		let handle: Handle<PngAsset> = asset_server.get_handle(id);
+		app.update();
+
+		asset_server.save(&handle, "saved/copy.png");
+		asset_server.save(&handle, "no_saver.txt");
+		app.update();
+
+		let saved_path = dir.path().join("saved/copy.png");
+		for _ in 0..500 {
+			if std::fs::read(&saved_path).ok().as_deref() == Some(&b"saved"[..]) {
+				break;
+			}
+			std::thread::sleep(std::time::Duration::from_millis(10));
+		}
+		assert_eq!(std::fs::read(saved_path).unwrap(), b"saved");
+		assert!(!dir.path().join("no_saver.txt").exists());
+
+		// never saved, as there is no collection of the type to save from
+		#[derive(Debug, TypeUuid)]
+		#[uuid = "5f1f4ac9-1dc4-4f5c-8d0b-f1a0c0d8a3e2"]
+		struct UnaddedAsset;
+		asset_server.save(&Handle::<UnaddedAsset>::weak(id.into()), "unadded.png");
+		assert!(asset_server
+			.server
+			.pending_saves
+			.lock()
+			.is_empty());
+	}
+
+	#[cfg(all(
+		feature = "filesystem_watcher",
+		all(not(target_arch = "wasm32"), not(target_os = "android"))
+	))]
+	#[test]
+	fn test_reload_changed_file_once_settled() {
+		let dir = create_dir_and_file("a.scale");
+		let asset_server = setup(dir.path());
+		let loader = ScaleLoader::default();
+		let loads = loader.0.clone();
+		asset_server.add_loader(loader);
+		let _assets = asset_server.register_asset_type::<PngAsset>();
+		futures_lite::future::block_on(asset_server.load_async("a.scale".into(), false)).unwrap();
+		let is_changed = || {
+			asset_server
+				.server
+				.changed_files
+				.lock()
+				.contains_key(Path::new("a.scale"))
+		};
+
+		// the file may still be written
+		asset_server.file_changed("a.scale".into());
+		asset_server.reload_changed_files();
+		assert!(is_changed());
+
+		std::thread::sleep(FILE_CHANGE_SETTLE_TIME);
+		asset_server.reload_changed_files();
+		assert!(!is_changed());
+		wait_for_queued_loads(&asset_server);
+		assert_eq!(loads.lock().len(), 2);
+	}
+
 	#[test]
 	fn test_get_handle_path() {
 		const PATH: &str = "path/file.png";
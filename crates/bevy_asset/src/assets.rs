@@ -1,6 +1,6 @@
 use crate::{
-	update_asset_storage_system, Asset, AssetLoader, AssetServer, AssetStage, Handle, HandleId,
-	RefChange,
+	update_asset_storage_system, Asset, AssetIo, AssetLoader, AssetProcessor, AssetSaver,
+	AssetServer, AssetStage, Handle, HandleId, RefChange,
 };
 use bevy_app::App;
 use bevy_ecs::{
@@ -69,6 +69,8 @@ impl<T: Asset> Debug for AssetEvent<T> {
 #[derive(Debug)]
 pub struct Assets<T: Asset> {
 	assets: HashMap<HandleId, T>,
+	/// The handles pointing to the asset of another handle, see [`Assets::get_alias`].
+	aliases: HashMap<HandleId, Handle<T>>,
 	events: Events<AssetEvent<T>>,
 	pub(crate) ref_change_sender: Sender<RefChange>,
 }
@@ -77,6 +79,7 @@ impl<T: Asset> Assets<T> {
 	pub(crate) fn new(ref_change_sender: Sender<RefChange>) -> Self {
 		Assets {
 			assets: HashMap::default(),
+			aliases: HashMap::default(),
 			events: Events::default(),
 			ref_change_sender,
 		}
@@ -120,7 +123,8 @@ impl<T: Asset> Assets<T> {
 	/// * [`AssetEvent::Modified`]: Sent if the asset with given handle already existed.
 	pub fn set_untracked<H: Into<HandleId>>(&mut self, handle: H, asset: T) {
 		let id: HandleId = handle.into();
-		if self.assets.insert(id, asset).is_some() {
+		let was_alias = self.aliases.remove(&id).is_some();
+		if self.assets.insert(id, asset).is_some() || was_alias {
 			self.events.send(AssetEvent::Modified {
 				handle: Handle::weak(id),
 			});
@@ -131,25 +135,76 @@ impl<T: Asset> Assets<T> {
 		}
 	}
 
+	/// Makes the given handle point to the asset of the `original` handle, which is kept alive
+	/// until the alias is removed. Used by the [`AssetServer`](crate::AssetServer) for the
+	/// duplicate assets it finds when deduplicating assets.
+	///
+	/// Any asset with the given handle is replaced.
+	///
+	/// # Events
+	///
+	/// * [`AssetEvent::Created`]: Sent if no asset existed with the given handle.
+	/// * [`AssetEvent::Modified`]: Sent if an asset with the given handle already existed.
+	pub(crate) fn set_alias<H: Into<HandleId>>(&mut self, handle: H, original: &Handle<T>) {
+		let id: HandleId = handle.into();
+		let original = self.get_handle(self.resolve(original.id));
+		let had_asset = self.assets.remove(&id).is_some();
+		if self.aliases.insert(id, original).is_some() || had_asset {
+			self.events.send(AssetEvent::Modified {
+				handle: Handle::weak(id),
+			});
+		} else {
+			self.events.send(AssetEvent::Created {
+				handle: Handle::weak(id),
+			});
+		}
+	}
+
+	/// Gets the handle of the asset the given handle is an alias of.
+	///
+	/// When [deduplicating assets](crate::AssetServer::set_content_deduplication), the handles of
+	/// a duplicate asset point to the asset of the original handle, until the asset is set again
+	/// or removed.
+	pub fn get_alias(&self, handle: &Handle<T>) -> Option<&Handle<T>> {
+		self.aliases.get(&handle.id)
+	}
+
+	/// Gets the id of the asset a handle points to, following its alias.
+	fn resolve(&self, id: HandleId) -> HandleId {
+		self
+			.aliases
+			.get(&id)
+			.map_or(id, |original| original.id)
+	}
+
 	/// Gets the asset for the given handle.
 	///
 	/// This is the main method for accessing asset data from an [Assets] collection. If you need
 	/// mutable access to the asset, use [`get_mut`](Assets::get_mut).
 	pub fn get(&self, handle: &Handle<T>) -> Option<&T> {
-		self.assets.get(&handle.into())
+		self.assets.get(&self.resolve(handle.into()))
 	}
 
 	/// Checks if an asset exists for the given handle
 	pub fn contains(&self, handle: &Handle<T>) -> bool {
-		self.assets.contains_key(&handle.into())
+		self
+			.assets
+			.contains_key(&self.resolve(handle.into()))
 	}
 
 	/// Get mutable access to the asset for the given handle.
 	///
 	/// This is the main method for mutably accessing asset data from an [Assets] collection. If you
 	/// do not need mutable access to the asset, you may also use [get](Assets::get).
+	///
+	/// Returns `None` for a handle aliasing the asset of another handle (see
+	/// [`get_alias`](Assets::get_alias)), as the changes would apply to the asset of both
+	/// handles. [Set](Assets::set) the asset of the handle to change it separately.
 	pub fn get_mut(&mut self, handle: &Handle<T>) -> Option<&mut T> {
 		let id: HandleId = handle.into();
+		if self.aliases.contains_key(&id) {
+			return None;
+		}
 		self.events.send(AssetEvent::Modified {
 			handle: Handle::weak(id),
 		});
@@ -163,9 +218,13 @@ impl<T: Asset> Assets<T> {
 
 	/// Gets mutable access to an asset for the given handle, inserting a new value if none exists.
 	///
+	/// A handle aliasing the asset of another handle gets a new value too, see
+	/// [`get_mut`](Assets::get_mut).
+	///
 	/// # Events
 	///
 	/// * [`AssetEvent::Created`]: Sent if the asset did not yet exist with the given handle.
+	/// * [`AssetEvent::Modified`]: Sent if the handle aliased the asset of another handle.
 	pub fn get_or_insert_with<H: Into<HandleId>>(
 		&mut self,
 		handle: H,
@@ -173,9 +232,16 @@ impl<T: Asset> Assets<T> {
 	) -> &mut T {
 		let mut event = None;
 		let id: HandleId = handle.into();
+		let was_alias = self.aliases.remove(&id).is_some();
 		let borrowed = self.assets.entry(id).or_insert_with(|| {
-			event = Some(AssetEvent::Created {
-				handle: Handle::weak(id),
+			event = Some(if was_alias {
+				AssetEvent::Modified {
+					handle: Handle::weak(id),
+				}
+			} else {
+				AssetEvent::Created {
+					handle: Handle::weak(id),
+				}
 			});
 			insert_fn()
 		});
@@ -186,7 +252,7 @@ impl<T: Asset> Assets<T> {
 		borrowed
 	}
 
-	/// Gets an iterator over all assets in the collection.
+	/// Gets an iterator over all assets in the collection, without the aliases.
 	pub fn iter(&self) -> impl Iterator<Item = (HandleId, &T)> {
 		self.assets.iter().map(|(k, v)| (*k, v))
 	}
@@ -215,8 +281,10 @@ impl<T: Asset> Assets<T> {
 	/// * [`AssetEvent::Removed`]
 	pub fn remove<H: Into<HandleId>>(&mut self, handle: H) -> Option<T> {
 		let id: HandleId = handle.into();
+		// removing an alias keeps the asset it points to
+		let was_alias = self.aliases.remove(&id).is_some();
 		let asset = self.assets.remove(&id);
-		if asset.is_some() {
+		if asset.is_some() || was_alias {
 			self.events.send(AssetEvent::Removed {
 				handle: Handle::weak(id),
 			});
@@ -229,6 +297,7 @@ impl<T: Asset> Assets<T> {
 	/// Keeps the allocated memory for reuse.
 	pub fn clear(&mut self) {
 		self.assets.clear();
+		self.aliases.clear();
 	}
 
 	/// Reserves capacity for at least additional more elements to be inserted into the assets.
@@ -307,6 +376,51 @@ pub trait AddAsset {
 	fn add_asset_loader<T>(&mut self, loader: T)
 	where
 		T: AssetLoader;
+
+	/// Adds an asset saver `T` using default values.
+	///
+	/// The default values may come from the `World` or from `T::default()`.
+	fn init_asset_saver<T>(&mut self)
+	where
+		T: AssetSaver + FromWorld;
+
+	/// Adds the provided asset saver to the application.
+	fn add_asset_saver<T>(&mut self, saver: T)
+	where
+		T: AssetSaver;
+
+	/// Sets the asset given to the handles of type `T` whose asset failed to load.
+	///
+	/// See [`AssetServer::set_fallback_asset`].
+	fn set_fallback_asset<T>(&mut self, asset: T)
+	where
+		T: Asset + Clone;
+
+	/// Adds an asset processor `T` using default values.
+	///
+	/// The default values may come from the `World` or from `T::default()`.
+	fn init_asset_processor<T>(&mut self)
+	where
+		T: AssetProcessor + FromWorld;
+
+	/// Adds the provided asset processor to the application.
+	fn add_asset_processor<T>(&mut self, processor: T)
+	where
+		T: AssetProcessor;
+
+	/// Adds the asset source `name`, for asset paths like `name://path`.
+	///
+	/// See [`AssetSources::add_source`](crate::AssetSources::add_source).
+	fn add_asset_source<T>(&mut self, name: &str, asset_io: T)
+	where
+		T: AssetIo;
+
+	/// Adds an overlay on top of the asset source `name`, shadowing its files.
+	///
+	/// See [`AssetSources::add_overlay`](crate::AssetSources::add_overlay).
+	fn add_asset_overlay<T>(&mut self, name: &str, asset_io: T)
+	where
+		T: AssetIo;
 }
 
 impl AddAsset for App {
@@ -373,6 +487,101 @@ impl AddAsset for App {
 			.resource_mut::<AssetServer>()
 			.add_loader(loader);
 	}
+
+	fn init_asset_saver<T>(&mut self)
+	where
+		T: AssetSaver + FromWorld,
+	{
+		let result = T::from_world(&mut self.world);
+		self.add_asset_saver(result)
+	}
+
+	fn add_asset_saver<T>(&mut self, saver: T)
+	where
+		T: AssetSaver,
+	{
+		self
+			.world
+			.resource_mut::<AssetServer>()
+			.add_saver(saver);
+	}
+
+	fn set_fallback_asset<T>(&mut self, asset: T)
+	where
+		T: Asset + Clone,
+	{
+		self
+			.world
+			.resource::<AssetServer>()
+			.set_fallback_asset(asset);
+	}
+
+	fn init_asset_processor<T>(&mut self)
+	where
+		T: AssetProcessor + FromWorld,
+	{
+		let result = T::from_world(&mut self.world);
+		self.add_asset_processor(result)
+	}
+
+	fn add_asset_processor<T>(&mut self, processor: T)
+	where
+		T: AssetProcessor,
+	{
+		self
+			.world
+			.resource::<AssetServer>()
+			.add_processor(processor);
+	}
+
+	fn add_asset_source<T>(&mut self, name: &str, asset_io: T)
+	where
+		T: AssetIo,
+	{
+		self
+			.world
+			.resource::<AssetServer>()
+			.asset_sources()
+			.add_source(name, asset_io);
+	}
+
+	fn add_asset_overlay<T>(&mut self, name: &str, asset_io: T)
+	where
+		T: AssetIo,
+	{
+		self
+			.world
+			.resource::<AssetServer>()
+			.asset_sources()
+			.add_overlay(name, asset_io);
+	}
+}
+
+/// Embeds a file into the binary and adds it to the `embedded` asset source.
+///
+/// The file is found relative to the source file calling the macro, like with `include_bytes!`,
+/// and is loaded from `embedded://crate_name/path`, with `path` relative to the `src` folder of
+/// the crate. For example calling `embedded_asset!(app, "shaders/foo.wgsl")` in
+/// `my_crate/src/render/mod.rs` embeds `my_crate/src/render/shaders/foo.wgsl`, loaded from
+/// `embedded://my_crate/render/shaders/foo.wgsl`.
+///
+/// In debug builds, the file is reloaded from its original source path when it changes if the
+/// [`AssetServerSettings`](crate::AssetServerSettings) watch for changes.
+#[macro_export]
+macro_rules! embedded_asset {
+	($app: ident, $path: expr) => {{
+		let crate_name = module_path!().split(':').next().unwrap();
+		$crate::embed_asset(
+			$app.world.resource::<$crate::AssetServer>(),
+			$crate::embedded_path(crate_name, file!(), $path),
+			include_bytes!($path),
+			if cfg!(debug_assertions) {
+				$crate::embedded_file_path(env!("CARGO_MANIFEST_DIR"), file!(), $path)
+			} else {
+				None
+			},
+		);
+	}};
 }
 
 /// Loads an internal asset.
@@ -434,4 +643,22 @@ mod tests {
 		let assets_after = app.world.resource_mut::<Assets<MyAsset>>();
 		assert!(assets_after.get(&handle).is_some());
 	}
+
+	#[test]
+	fn embedded_asset() {
+		let mut app = App::new();
+		app.add_plugin(bevy_core::CorePlugin);
+		app.add_plugin(crate::AssetPlugin);
+		crate::embedded_asset!(app, "path.rs");
+
+		let asset_io = app
+			.world
+			.resource::<crate::AssetServer>()
+			.asset_io();
+		let bytes = futures_lite::future::block_on(
+			asset_io.load_path(std::path::Path::new("embedded://bevy_asset/path.rs")),
+		)
+		.unwrap();
+		assert_eq!(bytes, include_bytes!("path.rs"));
+	}
 }
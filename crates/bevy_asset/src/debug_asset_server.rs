@@ -16,8 +16,8 @@ use std::{
 };
 
 use crate::{
-	Asset, AssetEvent, AssetPlugin, AssetServer, AssetServerSettings, Assets, FileAssetIo, Handle,
-	HandleUntyped,
+	Asset, AssetEvent, AssetPlugin, AssetServer, AssetServerSettings, AssetSources, Assets,
+	FileAssetIo, Handle, HandleUntyped,
 };
 
 /// A helper [`App`] used for hot reloading internal assets, which are compiled-in to Bevy plugins.
@@ -129,9 +129,12 @@ pub fn register_handle_with_loader<A: Asset>(
 	let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
 	let manifest_dir_path = Path::new(&manifest_dir);
 	let (mut handle_map, asset_server) = state.get_mut(&mut app.world);
-	let asset_io = asset_server
-		.asset_io()
-		.downcast_ref::<FileAssetIo>()
+	let default_source = asset_server
+		.asset_sources()
+		.layers(AssetSources::DEFAULT);
+	let asset_io = default_source
+		.first()
+		.and_then(|asset_io| asset_io.downcast_ref::<FileAssetIo>())
 		.expect("The debug AssetServer only works with FileAssetIo-backed AssetServers");
 	let absolute_file_path = manifest_dir_path.join(
 		Path::new(file_path)
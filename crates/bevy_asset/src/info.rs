@@ -1,7 +1,7 @@
-use crate::{path::AssetPath, LabelId};
+use crate::{path::AssetPath, AssetServerError, LabelId};
 use bevy_utils::{HashMap, HashSet, Uuid};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 /// Metadata for an asset source.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -10,6 +10,21 @@ pub struct SourceMeta {
 	pub assets: Vec<AssetMeta>,
 }
 
+impl SourceMeta {
+	/// The paths of the sources the assets depend on, without duplicates and in order.
+	pub fn dependencies(&self) -> Vec<PathBuf> {
+		let mut dependencies: Vec<PathBuf> = self
+			.assets
+			.iter()
+			.flat_map(|asset| &asset.dependencies)
+			.map(|dependency| dependency.path().to_owned())
+			.collect();
+		dependencies.sort();
+		dependencies.dedup();
+		dependencies
+	}
+}
+
 /// Metadata for an asset.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AssetMeta {
@@ -21,6 +36,62 @@ pub struct AssetMeta {
 	pub type_uuid: Uuid,
 }
 
+/// The contents of the `.meta` file stored next to an asset source, for example `image.png.meta`
+/// next to `image.png`.
+///
+/// It picks the [`AssetProcessor`](crate::AssetProcessor) of the source and its settings `S`,
+/// and holds the [`AssetLoader::Settings`](crate::AssetLoader::Settings) `L` the source is loaded
+/// with. Processed assets are written along with a copy of their `.meta` file, which also records
+/// the [`ProcessedInfo`] of the processed asset.
+///
+/// ```ron
+/// (
+///     processor: Some("my_game::CompressImage"),
+///     settings: (quality: 80),
+///     loader_settings: (is_srgb: false),
+/// )
+/// ```
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ProcessMeta<S, L> {
+	/// The type name of the [`AssetProcessor`](crate::AssetProcessor) of the source, or `None` to
+	/// copy the source as is.
+	#[serde(default)]
+	pub processor: Option<String>,
+	/// The settings of the processor. Missing settings use their default values.
+	#[serde(default)]
+	pub settings: S,
+	/// The settings of the [`AssetLoader`](crate::AssetLoader) of the source. Missing settings use
+	/// their default values.
+	#[serde(default)]
+	pub loader_settings: L,
+	/// Information about the processed asset, only present next to processed assets.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub processed: Option<ProcessedInfo>,
+}
+
+/// Information recorded next to a processed asset, used to know when it needs to be processed
+/// again.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProcessedInfo {
+	/// The hash of the source, its processor, the processor settings and the loader settings.
+	pub hash: u64,
+	/// The combination of [`ProcessedInfo::hash`] with the full hash of every dependency.
+	pub full_hash: u64,
+	/// Metadata of the assets loaded from the processed asset, if it has an
+	/// [`AssetLoader`](crate::AssetLoader).
+	pub meta: Option<SourceMeta>,
+}
+
+impl ProcessedInfo {
+	/// The paths of the sources the processed asset depends on, without duplicates and in order.
+	pub fn dependencies(&self) -> Vec<PathBuf> {
+		self
+			.meta
+			.as_ref()
+			.map_or_else(Vec::new, SourceMeta::dependencies)
+	}
+}
+
 /// Information about an asset source, such as its path, load state and asset metadata.
 #[derive(Clone, Debug)]
 pub struct SourceInfo {
@@ -32,6 +103,11 @@ pub struct SourceInfo {
 	pub asset_types: HashMap<LabelId, Uuid>,
 	/// The load state of the source.
 	pub load_state: LoadState,
+	/// The error the source last failed to load with.
+	pub load_error: Option<Arc<AssetServerError>>,
+	/// The hash of the contents and loader settings of the source, if it was loaded with content
+	/// deduplication.
+	pub content_hash: Option<u64>,
 	/// A collection to track which assets were sent to their asset storages.
 	pub committed_assets: HashSet<LabelId>,
 	/// Current version of the source.
@@ -67,3 +143,83 @@ pub enum LoadState {
 	/// from the [`Assets`](crate::Assets) collection.
 	Unloaded,
 }
+
+/// A source found to have the same contents and loader settings as another source when
+/// deduplicating assets, see [`AssetServer::set_content_deduplication`].
+///
+/// [`AssetServer::set_content_deduplication`]: crate::AssetServer::set_content_deduplication
+#[derive(Clone, Debug)]
+pub struct AssetDuplicate {
+	/// The path of the duplicate source.
+	pub path: PathBuf,
+	/// The path of the source it is a duplicate of, whose assets it uses.
+	pub original: PathBuf,
+	/// The size of the contents of the source, in bytes.
+	pub size: usize,
+}
+
+/// A source in an [`AssetDependencyGraph`].
+#[derive(Clone, Debug)]
+pub struct AssetDependencyNode {
+	/// The path of the source.
+	pub path: PathBuf,
+	/// The load state of the source.
+	pub load_state: LoadState,
+	/// The error the source last failed to load with.
+	pub load_error: Option<Arc<AssetServerError>>,
+	/// The paths of the sources the assets of this source depend on, empty until it is loaded.
+	pub dependencies: Vec<PathBuf>,
+}
+
+/// The sources an asset depends on, directly or indirectly, with their load states.
+///
+/// All the assets of a source are loaded together, so the graph is made of sources and tracks
+/// the dependencies of every asset of a source. See
+/// [`AssetServer::get_dependency_graph`](crate::AssetServer::get_dependency_graph).
+#[derive(Clone, Debug, Default)]
+pub struct AssetDependencyGraph {
+	/// The sources of the graph, starting with the source of the asset, then breadth first.
+	pub nodes: Vec<AssetDependencyNode>,
+}
+
+impl AssetDependencyGraph {
+	/// The number of sources of the graph which are loaded.
+	pub fn loaded_count(&self) -> usize {
+		self
+			.nodes
+			.iter()
+			.filter(|node| node.load_state == LoadState::Loaded)
+			.count()
+	}
+
+	/// The fraction of the sources of the graph which are loaded, from 0 to 1, for example to
+	/// show the progress of a loading screen.
+	///
+	/// More sources can be discovered while the dependencies load, so the progress can go down.
+	pub fn progress(&self) -> f32 {
+		if self.nodes.is_empty() {
+			return 0.0;
+		}
+		self.loaded_count() as f32 / self.nodes.len() as f32
+	}
+
+	/// The overall load state of the sources of the graph.
+	///
+	/// This is only [`LoadState::Loaded`] if all the sources were loaded successfully, and
+	/// [`LoadState::Failed`] if any of them failed to load.
+	pub fn load_state(&self) -> LoadState {
+		if self.nodes.is_empty() {
+			return LoadState::NotLoaded;
+		}
+		let states = || self.nodes.iter().map(|node| node.load_state);
+		[
+			LoadState::Failed,
+			LoadState::NotLoaded,
+			LoadState::Unloaded,
+			LoadState::Loading,
+		]
+		.into_iter()
+		.find(|state| states().any(|node_state| node_state == *state))
+		.unwrap_or(LoadState::Loaded)
+	}
+}
@@ -0,0 +1,647 @@
+use crate::{AssetIo, AssetIoError, FileType, Metadata};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use std::{
+	borrow::Cow,
+	fs::File,
+	io::{self, BufReader, Read, Seek, SeekFrom, Write},
+	ops::Range,
+	path::{Component, Path, PathBuf},
+};
+use thiserror::Error;
+
+const MAGIC: &[u8; 8] = b"BEVYPACK";
+const VERSION: u32 = 1;
+
+/// Errors that occur while reading an asset archive.
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+	/// The data doesn't start with the archive magic bytes.
+	#[error("not an asset archive")]
+	InvalidMagic,
+	/// The archive was written by an unsupported version of the format.
+	#[error("unsupported asset archive version {0}, expected {}", VERSION)]
+	UnsupportedVersion(u32),
+	/// The index of the archive is malformed.
+	#[error("corrupted asset archive index: {0}")]
+	CorruptedIndex(String),
+	/// The contents of an entry don't match its hash.
+	#[error("contents of {0:?} don't match their hash")]
+	HashMismatch(PathBuf),
+	/// Encountered an I/O error while reading the archive.
+	#[error("encountered an io error while reading asset archive: {0}")]
+	Io(#[from] io::Error),
+}
+
+impl From<ArchiveError> for AssetIoError {
+	fn from(err: ArchiveError) -> Self {
+		match err {
+			ArchiveError::Io(err) => AssetIoError::Io(err),
+			err => AssetIoError::Io(io::Error::new(io::ErrorKind::InvalidData, err)),
+		}
+	}
+}
+
+/// How the contents of an archive entry are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveCompression {
+	/// Stored as is.
+	None,
+	/// Compressed with deflate.
+	Deflate,
+}
+
+impl ArchiveCompression {
+	fn from_u8(value: u8) -> Option<Self> {
+		match value {
+			0 => Some(ArchiveCompression::None),
+			1 => Some(ArchiveCompression::Deflate),
+			_ => None,
+		}
+	}
+
+	fn to_u8(self) -> u8 {
+		match self {
+			ArchiveCompression::None => 0,
+			ArchiveCompression::Deflate => 1,
+		}
+	}
+}
+
+/// A file in an asset archive.
+#[derive(Debug, Clone)]
+struct ArchiveEntry {
+	/// The offset of the stored contents from the start of the data section.
+	offset: u64,
+	stored_size: u64,
+	size: u64,
+	compression: ArchiveCompression,
+	/// The CRC-32 of the contents.
+	hash: u32,
+}
+
+/// Where the data of an archive is read from.
+enum ArchiveData {
+	File {
+		path: PathBuf,
+		data_offset: u64,
+	},
+	Memory {
+		bytes: Cow<'static, [u8]>,
+		data_offset: u64,
+	},
+}
+
+/// I/O implementation reading assets from a single archive file, written by an
+/// [`ArchiveWriter`].
+///
+/// Packing thousands of small files into one archive makes loading them much faster on
+/// platforms where opening files is slow. An archive contains an index of its files, each of
+/// them optionally compressed and checked against the hash of its contents when read. Ranges of
+/// uncompressed files are read on their own, without checking the hash.
+///
+/// An archive is usually used as the default asset source, or as an overlay on top of it:
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_asset::{AddAsset, ArchiveAssetIo, AssetSources};
+/// # let mut app = App::new();
+/// app.add_asset_overlay(
+///     AssetSources::DEFAULT,
+///     ArchiveAssetIo::open("assets.pack").unwrap(),
+/// );
+/// ```
+pub struct ArchiveAssetIo {
+	data: ArchiveData,
+	entries: HashMap<String, ArchiveEntry>,
+	/// The entries of each directory, including the implied parent directories of the files.
+	directories: HashMap<String, Vec<PathBuf>>,
+}
+
+impl ArchiveAssetIo {
+	/// Opens the archive file at `path`, reading its index. The contents of the files are read
+	/// from the archive file when they are loaded.
+	pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, ArchiveError> {
+		let path = path.as_ref();
+		let file = File::open(path)?;
+		let archive_len = file.metadata()?.len();
+		let mut reader = BufReader::new(file);
+		let entries = read_index(&mut reader, archive_len)?;
+		let data_offset = reader.stream_position()?;
+		Ok(Self::with_entries(
+			ArchiveData::File {
+				path: path.to_owned(),
+				data_offset,
+			},
+			entries,
+		))
+	}
+
+	/// Reads an archive kept in memory, for example on platforms without a filesystem or when
+	/// the archive is compiled into the executable with `include_bytes!`.
+	pub fn from_bytes(bytes: impl Into<Cow<'static, [u8]>>) -> Result<Self, ArchiveError> {
+		let bytes = bytes.into();
+		let mut reader = io::Cursor::new(&bytes[..]);
+		let entries = read_index(&mut reader, bytes.len() as u64)?;
+		let data_offset = reader.position();
+		Ok(Self::with_entries(
+			ArchiveData::Memory { bytes, data_offset },
+			entries,
+		))
+	}
+
+	fn with_entries(data: ArchiveData, entries: HashMap<String, ArchiveEntry>) -> Self {
+		let mut directories: HashMap<String, Vec<PathBuf>> = HashMap::default();
+		directories.insert(String::new(), Vec::new());
+		for path in entries.keys() {
+			let mut child = path.as_str();
+			loop {
+				let parent = child
+					.rfind('/')
+					.map_or("", |separator| &child[..separator]);
+				let parent_exists = directories.contains_key(parent);
+				directories
+					.entry(parent.to_string())
+					.or_default()
+					.push(PathBuf::from(child));
+				// the ancestors of an existing directory are already known
+				if parent_exists {
+					break;
+				}
+				child = parent;
+			}
+		}
+		Self {
+			data,
+			entries,
+			directories,
+		}
+	}
+
+	/// The paths of the files in the archive, in no particular order.
+	pub fn paths(&self) -> impl Iterator<Item = &str> {
+		self.entries.keys().map(String::as_str)
+	}
+
+	/// Reads the bytes in `range` of the stored contents of an entry, which the index checked
+	/// to be within the archive.
+	fn read_stored(&self, entry: &ArchiveEntry, range: Range<u64>) -> Result<Vec<u8>, ArchiveError> {
+		let mut stored = vec![0; (range.end - range.start) as usize];
+		match &self.data {
+			ArchiveData::File { path, data_offset } => {
+				let mut file = File::open(path)?;
+				file.seek(SeekFrom::Start(data_offset + entry.offset + range.start))?;
+				file.read_exact(&mut stored)?;
+			},
+			ArchiveData::Memory { bytes, data_offset } => {
+				let start = (data_offset + entry.offset + range.start) as usize;
+				let stored_bytes = bytes
+					.get(start..start + stored.len())
+					.ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+				stored.copy_from_slice(stored_bytes);
+			},
+		}
+		Ok(stored)
+	}
+
+	fn read_entry(&self, path: &Path, entry: &ArchiveEntry) -> Result<Vec<u8>, ArchiveError> {
+		let stored = self.read_stored(entry, 0..entry.stored_size)?;
+		let bytes = match entry.compression {
+			ArchiveCompression::None => stored,
+			ArchiveCompression::Deflate => {
+				// the size comes from the index, don't trust it for allocations
+				let mut bytes = Vec::new();
+				DeflateDecoder::new(&stored[..])
+					.take(entry.size + 1)
+					.read_to_end(&mut bytes)?;
+				bytes
+			},
+		};
+		if bytes.len() as u64 != entry.size || content_hash(&bytes) != entry.hash {
+			return Err(ArchiveError::HashMismatch(path.to_owned()));
+		}
+		Ok(bytes)
+	}
+}
+
+impl AssetIo for ArchiveAssetIo {
+	fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+		Box::pin(async move {
+			let entry = self
+				.entries
+				.get(&archive_path(path))
+				.ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+			Ok(self.read_entry(path, entry)?)
+		})
+	}
+
+	fn load_path_range<'a>(
+		&'a self,
+		path: &'a Path,
+		range: Range<u64>,
+	) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+		Box::pin(async move {
+			let entry = self
+				.entries
+				.get(&archive_path(path))
+				.ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+			let end = range.end.min(entry.size);
+			let start = range.start.min(end);
+			match entry.compression {
+				ArchiveCompression::None => Ok(self.read_stored(entry, start..end)?),
+				ArchiveCompression::Deflate => {
+					let bytes = self.read_entry(path, entry)?;
+					Ok(bytes[start as usize..end as usize].to_vec())
+				},
+			}
+		})
+	}
+
+	fn read_directory(&self, path: &Path) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+		let entries = self
+			.directories
+			.get(&archive_path(path))
+			.ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+		Ok(Box::new(entries.clone().into_iter()))
+	}
+
+	fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+		let path_in_archive = archive_path(path);
+		if self.entries.contains_key(&path_in_archive) {
+			Ok(Metadata::new(FileType::File))
+		} else if self.directories.contains_key(&path_in_archive) {
+			Ok(Metadata::new(FileType::Directory))
+		} else {
+			Err(AssetIoError::NotFound(path.to_owned()))
+		}
+	}
+
+	fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+		Ok(())
+	}
+
+	fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+		bevy_log::warn!("Watching for changes is not supported by asset archives");
+		Ok(())
+	}
+}
+
+/// Writes an asset archive read by an [`ArchiveAssetIo`].
+///
+/// Files with the same contents are stored once, and compressed files are only kept compressed
+/// if it makes them smaller.
+#[derive(Default)]
+pub struct ArchiveWriter {
+	files: HashMap<String, (ArchiveCompression, Vec<u8>)>,
+}
+
+impl ArchiveWriter {
+	/// Creates an empty archive.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds the file at `path` with its contents, replacing any previous file at that path.
+	pub fn add(
+		&mut self,
+		path: impl AsRef<Path>,
+		bytes: impl Into<Vec<u8>>,
+		compression: ArchiveCompression,
+	) {
+		self
+			.files
+			.insert(archive_path(path.as_ref()), (compression, bytes.into()));
+	}
+
+	/// Adds all the files in `directory` and its subdirectories, with paths relative to
+	/// `directory`.
+	pub fn add_directory(
+		&mut self,
+		directory: impl AsRef<Path>,
+		compression: ArchiveCompression,
+	) -> io::Result<()> {
+		let directory = directory.as_ref();
+		let mut pending = vec![directory.to_owned()];
+		while let Some(current) = pending.pop() {
+			for entry in std::fs::read_dir(&current)? {
+				let path = entry?.path();
+				if path.is_dir() {
+					pending.push(path);
+				} else {
+					let bytes = std::fs::read(&path)?;
+					self.add(path.strip_prefix(directory).unwrap(), bytes, compression);
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// The number of files in the archive.
+	pub fn len(&self) -> usize {
+		self.files.len()
+	}
+
+	/// Returns `true` if the archive has no files.
+	pub fn is_empty(&self) -> bool {
+		self.files.is_empty()
+	}
+
+	/// Writes the archive.
+	pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+		let mut data = Vec::new();
+		// the hash only narrows down the files with the same contents, which are compared
+		let mut stored_by_hash: HashMap<u32, Vec<(&[u8], ArchiveEntry)>> = HashMap::default();
+		let mut index = Vec::new();
+		let mut files: Vec<_> = self.files.iter().collect();
+		files.sort_by_key(|(path, _)| *path);
+		for (path, (compression, bytes)) in files {
+			let hash = content_hash(bytes);
+			let same_contents = stored_by_hash.get(&hash).and_then(|stored| {
+				stored
+					.iter()
+					.find(|(stored, _)| stored == bytes)
+			});
+			let entry = match same_contents {
+				Some((_, entry)) => entry.clone(),
+				None => {
+					let (compression, stored) = match compression {
+						ArchiveCompression::None => (ArchiveCompression::None, Cow::Borrowed(bytes)),
+						ArchiveCompression::Deflate => {
+							let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+							encoder.write_all(bytes)?;
+							let compressed = encoder.finish()?;
+							if compressed.len() < bytes.len() {
+								(ArchiveCompression::Deflate, Cow::Owned(compressed))
+							} else {
+								(ArchiveCompression::None, Cow::Borrowed(bytes))
+							}
+						},
+					};
+					let entry = ArchiveEntry {
+						offset: data.len() as u64,
+						stored_size: stored.len() as u64,
+						size: bytes.len() as u64,
+						compression,
+						hash,
+					};
+					data.extend_from_slice(&stored);
+					stored_by_hash
+						.entry(hash)
+						.or_default()
+						.push((bytes, entry.clone()));
+					entry
+				},
+			};
+			index.push((path, entry));
+		}
+
+		writer.write_all(MAGIC)?;
+		writer.write_all(&VERSION.to_le_bytes())?;
+		writer.write_all(&(index.len() as u32).to_le_bytes())?;
+		for (path, entry) in index {
+			writer.write_all(&(path.len() as u32).to_le_bytes())?;
+			writer.write_all(path.as_bytes())?;
+			writer.write_all(&entry.offset.to_le_bytes())?;
+			writer.write_all(&entry.stored_size.to_le_bytes())?;
+			writer.write_all(&entry.size.to_le_bytes())?;
+			writer.write_all(&[entry.compression.to_u8()])?;
+			writer.write_all(&entry.hash.to_le_bytes())?;
+		}
+		writer.write_all(&data)
+	}
+}
+
+/// The CRC-32 of the contents of a file, as specified and stable across platforms and versions.
+fn content_hash(bytes: &[u8]) -> u32 {
+	crc32fast::hash(bytes)
+}
+
+/// Converts a path to its representation in the index, with `/` separators.
+fn archive_path(path: &Path) -> String {
+	let mut archive_path = String::new();
+	for component in path.components() {
+		if let Component::Normal(name) = component {
+			if !archive_path.is_empty() {
+				archive_path.push('/');
+			}
+			archive_path.push_str(&name.to_string_lossy());
+		}
+	}
+	archive_path
+}
+
+/// Reads the index of an archive of `archive_len` bytes, checking that its entries fit in it.
+fn read_index<R: Read + Seek>(
+	reader: &mut R,
+	archive_len: u64,
+) -> Result<HashMap<String, ArchiveEntry>, ArchiveError> {
+	let mut magic = [0; 8];
+	reader.read_exact(&mut magic)?;
+	if &magic != MAGIC {
+		return Err(ArchiveError::InvalidMagic);
+	}
+	let version = read_u32(reader)?;
+	if version != VERSION {
+		return Err(ArchiveError::UnsupportedVersion(version));
+	}
+	let count = read_u32(reader)?;
+	let mut entries = HashMap::default();
+	for _ in 0..count {
+		let path_len = read_u32(reader)? as u64;
+		if path_len > archive_len {
+			return Err(ArchiveError::CorruptedIndex(
+				"path is longer than the archive".to_string(),
+			));
+		}
+		let mut path = vec![0; path_len as usize];
+		reader.read_exact(&mut path)?;
+		let path = String::from_utf8(path)
+			.map_err(|_| ArchiveError::CorruptedIndex("path is not valid UTF-8".to_string()))?;
+		let offset = read_u64(reader)?;
+		let stored_size = read_u64(reader)?;
+		let size = read_u64(reader)?;
+		let mut compression = [0];
+		reader.read_exact(&mut compression)?;
+		let compression = ArchiveCompression::from_u8(compression[0]).ok_or_else(|| {
+			ArchiveError::CorruptedIndex(format!("unknown compression {}", compression[0]))
+		})?;
+		let hash = read_u32(reader)?;
+		if compression == ArchiveCompression::None && stored_size != size {
+			return Err(ArchiveError::CorruptedIndex(format!(
+				"size of uncompressed {:?} doesn't match its stored size",
+				path
+			)));
+		}
+		entries.insert(
+			path,
+			ArchiveEntry {
+				offset,
+				stored_size,
+				size,
+				compression,
+				hash,
+			},
+		);
+	}
+
+	let data_len = archive_len.saturating_sub(reader.stream_position()?);
+	for (path, entry) in &entries {
+		if !matches!(entry.offset.checked_add(entry.stored_size), Some(end) if end <= data_len) {
+			return Err(ArchiveError::CorruptedIndex(format!(
+				"{:?} is outside of the archive",
+				path
+			)));
+		}
+	}
+	Ok(entries)
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+	let mut bytes = [0; 4];
+	reader.read_exact(&mut bytes)?;
+	Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+	let mut bytes = [0; 8];
+	reader.read_exact(&mut bytes)?;
+	Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures_lite::future::block_on;
+
+	fn archive() -> ArchiveAssetIo {
+		let mut writer = ArchiveWriter::new();
+		writer.add("a.txt", "a".repeat(100), ArchiveCompression::Deflate);
+		writer.add("textures/b.png", b"b".to_vec(), ArchiveCompression::Deflate);
+		writer.add("textures/c.png", b"c".to_vec(), ArchiveCompression::None);
+		writer.add("textures/copy.png", b"c".to_vec(), ArchiveCompression::None);
+		let mut bytes = Vec::new();
+		writer.write(&mut bytes).unwrap();
+		ArchiveAssetIo::from_bytes(bytes).unwrap()
+	}
+
+	#[test]
+	fn read_archive() {
+		let archive = archive();
+		let read = |path: &str| block_on(archive.load_path(Path::new(path)));
+
+		assert_eq!(read("a.txt").unwrap(), "a".repeat(100).as_bytes());
+		assert_eq!(read("textures/b.png").unwrap(), b"b");
+		assert_eq!(read("textures/copy.png").unwrap(), b"c");
+		assert!(matches!(
+			read("missing.png"),
+			Err(AssetIoError::NotFound(_))
+		));
+
+		let entry = &archive.entries["a.txt"];
+		assert_eq!(entry.compression, ArchiveCompression::Deflate);
+		assert!(entry.stored_size < entry.size);
+		// not worth compressing
+		assert_eq!(
+			archive.entries["textures/b.png"].compression,
+			ArchiveCompression::None
+		);
+		// stored once
+		assert_eq!(
+			archive.entries["textures/c.png"].offset,
+			archive.entries["textures/copy.png"].offset
+		);
+	}
+
+	#[test]
+	fn read_archive_ranges() {
+		let mut writer = ArchiveWriter::new();
+		writer.add("a.txt", "abc".repeat(100), ArchiveCompression::Deflate);
+		writer.add("b.txt", b"0123456789".to_vec(), ArchiveCompression::None);
+		let mut bytes = Vec::new();
+		writer.write(&mut bytes).unwrap();
+		let archive = ArchiveAssetIo::from_bytes(bytes).unwrap();
+		let read = |path: &str, range| block_on(archive.load_path_range(Path::new(path), range));
+
+		assert_eq!(read("a.txt", 1..4).unwrap(), b"bca");
+		assert_eq!(read("b.txt", 2..5).unwrap(), b"234");
+		assert_eq!(read("b.txt", 8..20).unwrap(), b"89");
+		assert_eq!(read("b.txt", 20..30).unwrap(), b"");
+	}
+
+	#[test]
+	fn archive_directories() {
+		let archive = archive();
+		assert!(archive.is_dir(Path::new("")));
+		assert!(archive.is_dir(Path::new("textures")));
+		assert!(archive.is_file(Path::new("textures/b.png")));
+
+		let mut entries: Vec<_> = archive
+			.read_directory(Path::new(""))
+			.unwrap()
+			.collect();
+		entries.sort();
+		assert_eq!(entries, [PathBuf::from("a.txt"), PathBuf::from("textures")]);
+		assert_eq!(
+			archive
+				.read_directory(Path::new("textures"))
+				.unwrap()
+				.count(),
+			3
+		);
+	}
+
+	#[test]
+	fn reject_invalid_archives() {
+		assert!(matches!(
+			ArchiveAssetIo::from_bytes(&b"NOTAPACK"[..]),
+			Err(ArchiveError::InvalidMagic)
+		));
+
+		let mut writer = ArchiveWriter::new();
+		writer.add("a.txt", b"a".to_vec(), ArchiveCompression::None);
+		let mut bytes = Vec::new();
+		writer.write(&mut bytes).unwrap();
+		*bytes.last_mut().unwrap() = b'b';
+		let archive = ArchiveAssetIo::from_bytes(bytes).unwrap();
+		assert!(block_on(archive.load_path(Path::new("a.txt"))).is_err());
+	}
+
+	#[test]
+	fn reject_truncated_archives() {
+		let mut writer = ArchiveWriter::new();
+		writer.add("a.txt", b"abc".to_vec(), ArchiveCompression::None);
+		let mut bytes = Vec::new();
+		writer.write(&mut bytes).unwrap();
+		bytes.pop();
+		assert!(matches!(
+			ArchiveAssetIo::from_bytes(bytes),
+			Err(ArchiveError::CorruptedIndex(_))
+		));
+
+		// a huge path length, right after the magic, version and count
+		let mut bytes = MAGIC.to_vec();
+		bytes.extend_from_slice(&VERSION.to_le_bytes());
+		bytes.extend_from_slice(&1u32.to_le_bytes());
+		bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+		assert!(matches!(
+			ArchiveAssetIo::from_bytes(bytes),
+			Err(ArchiveError::CorruptedIndex(_))
+		));
+	}
+
+	#[test]
+	fn replace_files() {
+		let mut writer = ArchiveWriter::new();
+		writer.add("a.txt", b"a".to_vec(), ArchiveCompression::None);
+		writer.add("./a.txt", b"b".to_vec(), ArchiveCompression::None);
+		assert_eq!(writer.len(), 1);
+		let mut bytes = Vec::new();
+		writer.write(&mut bytes).unwrap();
+		let archive = ArchiveAssetIo::from_bytes(bytes).unwrap();
+		assert_eq!(
+			block_on(archive.load_path(Path::new("a.txt"))).unwrap(),
+			b"b"
+		);
+	}
+}
@@ -0,0 +1,483 @@
+use crate::{path::split_source, AssetIo, AssetIoError, Metadata, Reader};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap, HashSet};
+use parking_lot::RwLock;
+use std::{
+	ops::Range,
+	path::{Path, PathBuf},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+};
+
+/// The layers of a source, from the bottom one to the top overlay.
+type Layers = Vec<Arc<dyn AssetIo>>;
+
+/// An asset I/O routing paths to named asset sources by their scheme.
+///
+/// A path like `mods://textures/grass.png` is read from the `textures/grass.png` path of the
+/// `mods` source, while paths without a scheme are read from the default source, named `""`.
+///
+/// Each source is a stack of layers: files of an overlay shadow the files with the same path in
+/// the layers below it, so for example a mod can replace some of the files of the game.
+///
+/// The [`AssetServer`](crate::AssetServer) loads all its assets through its `AssetSources`, see
+/// [`AssetServer::asset_sources`](crate::AssetServer::asset_sources).
+pub struct AssetSources {
+	sources: RwLock<HashMap<String, Layers>>,
+	watching_for_changes: AtomicBool,
+}
+
+impl AssetSources {
+	/// The name of the default source, used for paths without a scheme.
+	pub const DEFAULT: &'static str = "";
+
+	/// The name of the source of the files embedded with
+	/// [`embedded_asset!`](crate::embedded_asset), added by the [`AssetPlugin`](crate::AssetPlugin).
+	pub const EMBEDDED: &'static str = "embedded";
+
+	/// Creates asset sources with `asset_io` as the default source.
+	pub fn new<T: AssetIo>(asset_io: T) -> Self {
+		Self::with_boxed_io(Box::new(asset_io))
+	}
+
+	/// Creates asset sources with a boxed asset I/O as the default source.
+	pub fn with_boxed_io(asset_io: Box<dyn AssetIo>) -> Self {
+		let mut sources = HashMap::default();
+		sources.insert(Self::DEFAULT.to_string(), vec![Arc::from(asset_io)]);
+		Self {
+			sources: RwLock::new(sources),
+			watching_for_changes: AtomicBool::new(false),
+		}
+	}
+
+	/// Adds the source `name`, read through `asset_io`, replacing the previous source with the
+	/// same name and its overlays.
+	pub fn add_source<T: AssetIo>(&self, name: impl Into<String>, asset_io: T) {
+		let asset_io = self.prepare(asset_io);
+		self
+			.sources
+			.write()
+			.insert(name.into(), vec![asset_io]);
+	}
+
+	/// Adds `asset_io` as an overlay on top of the source `name`, shadowing the files with the
+	/// same path in the source and its previous overlays. Adds the source if it doesn't exist.
+	pub fn add_overlay<T: AssetIo>(&self, name: &str, asset_io: T) {
+		let asset_io = self.prepare(asset_io);
+		self
+			.sources
+			.write()
+			.entry(name.to_string())
+			.or_default()
+			.push(asset_io);
+	}
+
+	/// Removes the source `name` with its overlays, returning `true` if it existed.
+	pub fn remove_source(&self, name: &str) -> bool {
+		self.sources.write().remove(name).is_some()
+	}
+
+	/// Returns `true` if the source `name` exists.
+	pub fn contains_source(&self, name: &str) -> bool {
+		self.sources.read().contains_key(name)
+	}
+
+	/// The names of the sources, in no particular order.
+	pub fn source_names(&self) -> Vec<String> {
+		self.sources.read().keys().cloned().collect()
+	}
+
+	/// The layers of the source `name`, from the bottom one to the top overlay. Returns an empty
+	/// list if there is no such source.
+	pub fn layers(&self, name: &str) -> Vec<Arc<dyn AssetIo>> {
+		self
+			.sources
+			.read()
+			.get(name)
+			.cloned()
+			.unwrap_or_default()
+	}
+
+	fn prepare<T: AssetIo>(&self, asset_io: T) -> Arc<dyn AssetIo> {
+		if self
+			.watching_for_changes
+			.load(Ordering::Relaxed)
+		{
+			if let Err(err) = asset_io.watch_for_changes() {
+				bevy_log::warn!("{}", err);
+			}
+		}
+		Arc::new(asset_io)
+	}
+
+	/// Returns the layers of the source of `path` from the top overlay, and the path inside of
+	/// the source.
+	fn route<'a>(&self, path: &'a Path) -> Result<(Layers, &'a Path), AssetIoError> {
+		let (source, source_path) = split_source(path);
+		let mut layers = self.layers(source.unwrap_or(Self::DEFAULT));
+		if layers.is_empty() {
+			return Err(AssetIoError::NotFound(path.to_owned()));
+		}
+		layers.reverse();
+		Ok((layers, source_path))
+	}
+}
+
+/// Prefixes `path` with the scheme of the source `name`.
+fn with_source(name: &str, path: &Path) -> PathBuf {
+	if name == AssetSources::DEFAULT {
+		path.to_owned()
+	} else {
+		PathBuf::from(format!("{}://{}", name, path.display()))
+	}
+}
+
+impl AssetIo for AssetSources {
+	fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+		Box::pin(async move {
+			let (layers, source_path) = self.route(path)?;
+			for layer in layers {
+				match layer.load_path(source_path).await {
+					Err(AssetIoError::NotFound(_)) => continue,
+					result => return result,
+				}
+			}
+			Err(AssetIoError::NotFound(path.to_owned()))
+		})
+	}
+
+	fn read_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Reader, AssetIoError>> {
+		Box::pin(async move {
+			let (layers, source_path) = self.route(path)?;
+			for layer in layers {
+				match layer.read_path(source_path).await {
+					Err(AssetIoError::NotFound(_)) => continue,
+					result => return result,
+				}
+			}
+			Err(AssetIoError::NotFound(path.to_owned()))
+		})
+	}
+
+	fn load_path_range<'a>(
+		&'a self,
+		path: &'a Path,
+		range: Range<u64>,
+	) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+		Box::pin(async move {
+			let (layers, source_path) = self.route(path)?;
+			for layer in layers {
+				match layer
+					.load_path_range(source_path, range.clone())
+					.await
+				{
+					Err(AssetIoError::NotFound(_)) => continue,
+					result => return result,
+				}
+			}
+			Err(AssetIoError::NotFound(path.to_owned()))
+		})
+	}
+
+	fn read_directory(&self, path: &Path) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+		let (source, _) = split_source(path);
+		let (layers, source_path) = self.route(path)?;
+		let mut found = false;
+		let mut seen = HashSet::default();
+		let mut entries = Vec::new();
+		for layer in layers {
+			if !layer.is_dir(source_path) {
+				continue;
+			}
+			found = true;
+			for entry in layer.read_directory(source_path)? {
+				if seen.insert(entry.clone()) {
+					entries.push(with_source(source.unwrap_or(Self::DEFAULT), &entry));
+				}
+			}
+		}
+		if !found {
+			return Err(AssetIoError::NotFound(path.to_owned()));
+		}
+		Ok(Box::new(entries.into_iter()))
+	}
+
+	fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+		let (layers, source_path) = self.route(path)?;
+		for layer in layers {
+			match layer.get_metadata(source_path) {
+				Err(AssetIoError::NotFound(_)) => continue,
+				result => return result,
+			}
+		}
+		Err(AssetIoError::NotFound(path.to_owned()))
+	}
+
+	/// Writes to the top layer of the source which isn't read-only.
+	fn write_path<'a>(
+		&'a self,
+		path: &'a Path,
+		bytes: &'a [u8],
+	) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+		Box::pin(async move {
+			let (layers, source_path) = self.route(path)?;
+			for layer in layers {
+				match layer.write_path(source_path, bytes).await {
+					Err(AssetIoError::ReadOnly(_)) => continue,
+					result => return result,
+				}
+			}
+			Err(AssetIoError::ReadOnly(path.to_owned()))
+		})
+	}
+
+	/// Removes the file from the top layer where it exists.
+	fn remove_path(&self, path: &Path) -> Result<(), AssetIoError> {
+		let (layers, source_path) = self.route(path)?;
+		for layer in layers {
+			if layer.is_file(source_path) {
+				return layer.remove_path(source_path);
+			}
+		}
+		Err(AssetIoError::NotFound(path.to_owned()))
+	}
+
+	/// Renames the file in the top layer where it exists, inside of a single source.
+	fn rename(&self, from: &Path, to: &Path) -> Result<(), AssetIoError> {
+		let (layers, source_from) = self.route(from)?;
+		let (from_source, _) = split_source(from);
+		let (to_source, source_to) = split_source(to);
+		if from_source != to_source {
+			return Err(AssetIoError::Io(std::io::Error::new(
+				std::io::ErrorKind::InvalidInput,
+				format!("cannot rename {:?} to another source", from),
+			)));
+		}
+		for layer in layers {
+			if layer.is_file(source_from) {
+				return layer.rename(source_from, source_to);
+			}
+		}
+		Err(AssetIoError::NotFound(from.to_owned()))
+	}
+
+	fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+		let (layers, source_path) = self.route(path)?;
+		for layer in layers {
+			if layer.get_metadata(source_path).is_ok() {
+				layer.watch_path_for_changes(source_path)?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns the paths changed in all the layers of the sources since the last call, with the
+	/// scheme of their source.
+	fn changed_paths(&self) -> Vec<PathBuf> {
+		let mut changed = Vec::new();
+		for (name, layers) in self.sources.read().iter() {
+			for layer in layers {
+				changed.extend(
+					layer
+						.changed_paths()
+						.into_iter()
+						.map(|path| with_source(name, &path)),
+				);
+			}
+		}
+		changed
+	}
+
+	fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+		self
+			.watching_for_changes
+			.store(true, Ordering::Relaxed);
+		for layers in self.sources.read().values() {
+			for layer in layers {
+				layer.watch_for_changes()?;
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(all(test, not(target_arch = "wasm32"), not(target_os = "android")))]
+mod tests {
+	use super::*;
+	use crate::FileAssetIo;
+	use futures_lite::future::block_on;
+
+	fn folder(files: &[(&str, &str)]) -> tempfile::TempDir {
+		let dir = tempfile::tempdir().unwrap();
+		for (path, contents) in files {
+			let path = dir.path().join(path);
+			std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+			std::fs::write(path, contents).unwrap();
+		}
+		dir
+	}
+
+	fn read(sources: &AssetSources, path: &str) -> Result<String, AssetIoError> {
+		block_on(sources.load_path(Path::new(path))).map(|bytes| String::from_utf8(bytes).unwrap())
+	}
+
+	#[test]
+	fn route_by_scheme() {
+		let base = folder(&[("a.txt", "base a")]);
+		let mods = folder(&[("a.txt", "mods a")]);
+		let sources = AssetSources::new(FileAssetIo::new(base.path(), false));
+		sources.add_source("mods", FileAssetIo::new(mods.path(), false));
+
+		assert_eq!(read(&sources, "a.txt").unwrap(), "base a");
+		assert_eq!(read(&sources, "mods://a.txt").unwrap(), "mods a");
+		assert!(matches!(
+			read(&sources, "dlc://a.txt"),
+			Err(AssetIoError::NotFound(_))
+		));
+		assert_eq!(
+			crate::AssetPath::from("mods://a.txt#label").source(),
+			Some("mods")
+		);
+	}
+
+	#[test]
+	fn route_relative_paths_of_root_files() {
+		let base = folder(&[("b.txt", "base b")]);
+		let mods = folder(&[("a.txt", "mods a"), ("b.txt", "mods b")]);
+		let sources = AssetSources::new(FileAssetIo::new(base.path(), false));
+		sources.add_source("mods", FileAssetIo::new(mods.path(), false));
+
+		// a dependency of `mods://a.txt`, relative to its parent
+		let dependency = Path::new("mods://a.txt")
+			.parent()
+			.unwrap()
+			.join("b.txt");
+		assert_eq!(dependency, Path::new("mods:/b.txt"));
+		assert_eq!(block_on(sources.load_path(&dependency)).unwrap(), b"mods b");
+		assert_eq!(
+			crate::AssetPath::from(dependency.as_path()).get_id(),
+			crate::AssetPath::from("mods://b.txt").get_id()
+		);
+		assert_eq!(
+			split_source(Path::new("C:/b.txt")),
+			(None, Path::new("C:/b.txt"))
+		);
+	}
+
+	/// An asset I/O reporting a changed file on each call to `changed_paths`.
+	struct ChangingAssetIo;
+
+	impl AssetIo for ChangingAssetIo {
+		fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+			Box::pin(async move { Err(AssetIoError::NotFound(path.to_owned())) })
+		}
+
+		fn read_directory(
+			&self,
+			path: &Path,
+		) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+			Err(AssetIoError::NotFound(path.to_owned()))
+		}
+
+		fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+			Err(AssetIoError::NotFound(path.to_owned()))
+		}
+
+		fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+			Ok(())
+		}
+
+		fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+			Ok(())
+		}
+
+		fn changed_paths(&self) -> Vec<PathBuf> {
+			vec![PathBuf::from("changed.txt")]
+		}
+	}
+
+	#[test]
+	fn changed_paths_of_all_sources() {
+		let sources = AssetSources::new(ChangingAssetIo);
+		sources.add_source("mods", ChangingAssetIo);
+		let mut changed = sources.changed_paths();
+		changed.sort();
+		assert_eq!(
+			changed,
+			[
+				PathBuf::from("changed.txt"),
+				PathBuf::from("mods://changed.txt")
+			]
+		);
+	}
+
+	#[test]
+	fn write_to_sources() {
+		let base = folder(&[("a.txt", "base a")]);
+		let sources = AssetSources::new(FileAssetIo::new(base.path(), false));
+		sources.add_overlay(AssetSources::DEFAULT, crate::MemoryAssetIo::new());
+
+		block_on(sources.write_path(Path::new("dir/b.txt"), b"b")).unwrap();
+		assert_eq!(read(&sources, "dir/b.txt").unwrap(), "b");
+		assert!(!base.path().join("dir/b.txt").exists());
+
+		sources
+			.rename(Path::new("dir/b.txt"), Path::new("c.txt"))
+			.unwrap();
+		assert_eq!(read(&sources, "c.txt").unwrap(), "b");
+		sources.remove_path(Path::new("c.txt")).unwrap();
+		assert!(matches!(
+			read(&sources, "c.txt"),
+			Err(AssetIoError::NotFound(_))
+		));
+
+		// the base layer only has `a.txt`
+		sources.remove_path(Path::new("a.txt")).unwrap();
+		assert!(!base.path().join("a.txt").exists());
+
+		sources.add_source("pack", FileAssetIo::new(base.path(), false));
+		block_on(sources.write_path(Path::new("pack://nested/d.txt"), b"d")).unwrap();
+		assert_eq!(
+			std::fs::read(base.path().join("nested/d.txt")).unwrap(),
+			b"d"
+		);
+		assert!(sources
+			.rename(Path::new("pack://nested/d.txt"), Path::new("d.txt"))
+			.is_err());
+	}
+
+	#[test]
+	fn overlays_shadow_lower_layers() {
+		let base = folder(&[("a.txt", "base a"), ("dir/b.txt", "base b")]);
+		let overlay = folder(&[("dir/b.txt", "overlay b"), ("dir/c.txt", "overlay c")]);
+		let sources = AssetSources::new(FileAssetIo::new(base.path(), false));
+		sources.add_overlay(
+			AssetSources::DEFAULT,
+			FileAssetIo::new(overlay.path(), false),
+		);
+		sources.add_source("mods", FileAssetIo::new(overlay.path(), false));
+
+		assert_eq!(read(&sources, "a.txt").unwrap(), "base a");
+		assert_eq!(read(&sources, "dir/b.txt").unwrap(), "overlay b");
+		assert_eq!(read(&sources, "dir/c.txt").unwrap(), "overlay c");
+		assert!(sources.is_file(Path::new("dir/c.txt")));
+
+		let mut entries: Vec<PathBuf> = sources
+			.read_directory(Path::new("dir"))
+			.unwrap()
+			.collect();
+		entries.sort();
+		assert_eq!(
+			entries,
+			[PathBuf::from("dir/b.txt"), PathBuf::from("dir/c.txt")]
+		);
+
+		let entries: Vec<PathBuf> = sources
+			.read_directory(Path::new("mods://dir"))
+			.unwrap()
+			.collect();
+		assert!(entries.contains(&PathBuf::from("mods://dir/c.txt")));
+	}
+}
@@ -1,6 +1,6 @@
 #[cfg(feature = "filesystem_watcher")]
 use crate::{filesystem_watcher::FilesystemWatcher, AssetServer};
-use crate::{AssetIo, AssetIoError, Metadata};
+use crate::{AssetIo, AssetIoError, Metadata, Reader};
 use anyhow::Result;
 #[cfg(feature = "filesystem_watcher")]
 use bevy_ecs::system::Res;
@@ -10,6 +10,7 @@ use bevy_utils::HashSet;
 #[cfg(feature = "filesystem_watcher")]
 use crossbeam_channel::TryRecvError;
 use fs::File;
+use futures_lite::{AsyncRead, AsyncSeek};
 #[cfg(feature = "filesystem_watcher")]
 use parking_lot::RwLock;
 #[cfg(feature = "filesystem_watcher")]
@@ -17,8 +18,11 @@ use std::sync::Arc;
 use std::{
 	convert::TryFrom,
 	env, fs,
-	io::Read,
+	io::{self, Read, Seek, SeekFrom},
+	ops::Range,
 	path::{Path, PathBuf},
+	pin::Pin,
+	task::{Context, Poll},
 };
 
 /// I/O implementation for the local filesystem.
@@ -83,25 +87,47 @@ impl FileAssetIo {
 	pub fn root_path(&self) -> &PathBuf {
 		&self.root_path
 	}
+
+	fn open(&self, path: &Path) -> Result<File, AssetIoError> {
+		let full_path = self.root_path.join(path);
+		File::open(&full_path).map_err(|e| {
+			if e.kind() == std::io::ErrorKind::NotFound {
+				AssetIoError::NotFound(full_path)
+			} else {
+				e.into()
+			}
+		})
+	}
 }
 
 impl AssetIo for FileAssetIo {
 	fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
 		Box::pin(async move {
 			let mut bytes = Vec::new();
-			let full_path = self.root_path.join(path);
-			match File::open(&full_path) {
-				Ok(mut file) => {
-					file.read_to_end(&mut bytes)?;
-				},
-				Err(e) => {
-					return if e.kind() == std::io::ErrorKind::NotFound {
-						Err(AssetIoError::NotFound(full_path))
-					} else {
-						Err(e.into())
-					}
-				},
+			self.open(path)?.read_to_end(&mut bytes)?;
+			Ok(bytes)
+		})
+	}
+
+	fn read_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Reader, AssetIoError>> {
+		Box::pin(async move { Ok(Box::new(FileReader(self.open(path)?)) as Reader) })
+	}
+
+	fn load_path_range<'a>(
+		&'a self,
+		path: &'a Path,
+		range: Range<u64>,
+	) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+		Box::pin(async move {
+			let mut bytes = Vec::new();
+			if range.is_empty() {
+				return Ok(bytes);
 			}
+			let mut file = self.open(path)?;
+			file.seek(SeekFrom::Start(range.start))?;
+			file
+				.take(range.end - range.start)
+				.read_to_end(&mut bytes)?;
 			Ok(bytes)
 		})
 	}
@@ -147,6 +173,80 @@ impl AssetIo for FileAssetIo {
 		Ok(())
 	}
 
+	/// Returns the paths of the watched files modified since the last call, relative to the root
+	/// directory.
+	#[cfg(feature = "filesystem_watcher")]
+	fn changed_paths(&self) -> Vec<PathBuf> {
+		let mut changed = HashSet::default();
+		let mut changed_paths = Vec::new();
+		let watcher = self.filesystem_watcher.read();
+		if let Some(ref watcher) = *watcher {
+			loop {
+				let event = match watcher.receiver.try_recv() {
+					Ok(result) => result.unwrap(),
+					Err(TryRecvError::Empty) => break,
+					Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
+				};
+				if let notify::event::Event {
+					kind: notify::event::EventKind::Modify(_),
+					paths,
+					..
+				} = event
+				{
+					for path in paths {
+						if changed.insert(path.clone()) {
+							if let Ok(relative_path) = path.strip_prefix(&self.root_path) {
+								changed_paths.push(relative_path.to_owned());
+							}
+						}
+					}
+				}
+			}
+		}
+		changed_paths
+	}
+
+	fn write_path<'a>(
+		&'a self,
+		path: &'a Path,
+		bytes: &'a [u8],
+	) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+		Box::pin(async move {
+			let full_path = self.root_path.join(path);
+			if let Some(parent) = full_path.parent() {
+				fs::create_dir_all(parent)?;
+			}
+			fs::write(full_path, bytes)?;
+			Ok(())
+		})
+	}
+
+	fn remove_path(&self, path: &Path) -> Result<(), AssetIoError> {
+		let full_path = self.root_path.join(path);
+		fs::remove_file(&full_path).map_err(|e| {
+			if e.kind() == std::io::ErrorKind::NotFound {
+				AssetIoError::NotFound(full_path)
+			} else {
+				e.into()
+			}
+		})
+	}
+
+	fn rename(&self, from: &Path, to: &Path) -> Result<(), AssetIoError> {
+		let full_from = self.root_path.join(from);
+		let full_to = self.root_path.join(to);
+		if let Some(parent) = full_to.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		fs::rename(&full_from, full_to).map_err(|e| {
+			if e.kind() == std::io::ErrorKind::NotFound {
+				AssetIoError::NotFound(full_from)
+			} else {
+				e.into()
+			}
+		})
+	}
+
 	fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
 		let full_path = self.root_path.join(path);
 		full_path
@@ -162,44 +262,40 @@ impl AssetIo for FileAssetIo {
 	}
 }
 
+/// A [`Reader`] of a file, reading it with blocking calls like [`FileAssetIo::load_path`].
+struct FileReader(File);
+
+impl AsyncRead for FileReader {
+	fn poll_read(
+		self: Pin<&mut Self>,
+		_cx: &mut Context<'_>,
+		buf: &mut [u8],
+	) -> Poll<io::Result<usize>> {
+		Poll::Ready(self.get_mut().0.read(buf))
+	}
+}
+
+impl AsyncSeek for FileReader {
+	fn poll_seek(
+		self: Pin<&mut Self>,
+		_cx: &mut Context<'_>,
+		pos: SeekFrom,
+	) -> Poll<io::Result<u64>> {
+		Poll::Ready(self.get_mut().0.seek(pos))
+	}
+}
+
 /// Watches for file changes in the local file system.
 #[cfg(all(
 	feature = "filesystem_watcher",
 	all(not(target_arch = "wasm32"), not(target_os = "android"))
 ))]
+///
+/// The asset of a changed file is reloaded once the file stops changing, and a changed `.meta`
+/// file reloads its asset with its new loader settings.
 pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
-	let mut changed = HashSet::default();
-	let asset_io = if let Some(asset_io) = asset_server
-		.server
-		.asset_io
-		.downcast_ref::<FileAssetIo>()
-	{
-		asset_io
-	} else {
-		return;
-	};
-	let watcher = asset_io.filesystem_watcher.read();
-	if let Some(ref watcher) = *watcher {
-		loop {
-			let event = match watcher.receiver.try_recv() {
-				Ok(result) => result.unwrap(),
-				Err(TryRecvError::Empty) => break,
-				Err(TryRecvError::Disconnected) => panic!("FilesystemWatcher disconnected."),
-			};
-			if let notify::event::Event {
-				kind: notify::event::EventKind::Modify(_),
-				paths,
-				..
-			} = event
-			{
-				for path in &paths {
-					if !changed.contains(path) {
-						let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
-						let _ = asset_server.load_untracked(relative_path.into(), true);
-					}
-				}
-				changed.extend(paths);
-			}
-		}
+	for path in asset_server.server.asset_io.changed_paths() {
+		asset_server.file_changed(path);
 	}
+	asset_server.reload_changed_files();
 }
@@ -0,0 +1,361 @@
+use crate::{AssetIo, AssetIoError, AssetServer, AssetSources, FileType, Metadata, Reader};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap};
+use parking_lot::RwLock;
+use std::{
+	borrow::Cow,
+	ops::Range,
+	path::{Path, PathBuf},
+};
+
+#[cfg(all(
+	feature = "filesystem_watcher",
+	all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+use crate::filesystem_watcher::FilesystemWatcher;
+
+/// I/O implementation keeping files in memory, for example files compiled into the executable
+/// with [`embedded_asset!`](crate::embedded_asset).
+///
+/// Directories are implied by the paths of the files.
+#[derive(Default)]
+pub struct MemoryAssetIo {
+	files: RwLock<HashMap<PathBuf, Cow<'static, [u8]>>>,
+	#[cfg(all(
+		feature = "filesystem_watcher",
+		all(not(target_arch = "wasm32"), not(target_os = "android"))
+	))]
+	watcher: RwLock<MemoryWatcher>,
+}
+
+/// Watches the files on disk the files in memory were read from.
+#[cfg(all(
+	feature = "filesystem_watcher",
+	all(not(target_arch = "wasm32"), not(target_os = "android"))
+))]
+#[derive(Default)]
+struct MemoryWatcher {
+	filesystem_watcher: Option<FilesystemWatcher>,
+	/// The paths in memory by the path on disk they are read from.
+	sources: HashMap<PathBuf, PathBuf>,
+}
+
+impl MemoryAssetIo {
+	/// Creates an empty `MemoryAssetIo`.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds the file at `path` with its contents, replacing any previous file at that path.
+	pub fn insert(&self, path: impl Into<PathBuf>, bytes: impl Into<Cow<'static, [u8]>>) {
+		self
+			.files
+			.write()
+			.insert(path.into(), bytes.into());
+	}
+
+	/// Removes the file at `path`, returning `true` if it existed.
+	pub fn remove(&self, path: &Path) -> bool {
+		self.files.write().remove(path).is_some()
+	}
+
+	/// Returns `true` if there is a file at `path`.
+	pub fn contains(&self, path: &Path) -> bool {
+		self.files.read().contains_key(path)
+	}
+
+	/// Reads the file at `path` from `file_path` on disk again whenever it is modified, once
+	/// watching for changes. Requires the `filesystem_watcher` feature.
+	#[allow(unused_variables)]
+	pub fn watch_file(&self, path: impl Into<PathBuf>, file_path: impl Into<PathBuf>) {
+		#[cfg(all(
+			feature = "filesystem_watcher",
+			all(not(target_arch = "wasm32"), not(target_os = "android"))
+		))]
+		{
+			let file_path = file_path.into();
+			let mut watcher = self.watcher.write();
+			if let Some(filesystem_watcher) = &mut watcher.filesystem_watcher {
+				if let Err(err) = filesystem_watcher.watch(&file_path) {
+					bevy_log::warn!("failed to watch {:?}: {}", file_path, err);
+				}
+			}
+			watcher.sources.insert(file_path, path.into());
+		}
+	}
+}
+
+impl AssetIo for MemoryAssetIo {
+	fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+		Box::pin(async move {
+			self
+				.files
+				.read()
+				.get(path)
+				.map(|bytes| bytes.to_vec())
+				.ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+		})
+	}
+
+	fn read_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Reader, AssetIoError>> {
+		Box::pin(async move {
+			// embedded files are borrowed, so cloning them doesn't copy their data
+			let bytes = self
+				.files
+				.read()
+				.get(path)
+				.cloned()
+				.ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+			Ok(Box::new(futures_lite::io::Cursor::new(bytes)) as Reader)
+		})
+	}
+
+	fn load_path_range<'a>(
+		&'a self,
+		path: &'a Path,
+		range: Range<u64>,
+	) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+		Box::pin(async move {
+			let files = self.files.read();
+			let bytes = files
+				.get(path)
+				.ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+			let end = (range.end as usize).min(bytes.len());
+			let start = (range.start as usize).min(end);
+			Ok(bytes[start..end].to_vec())
+		})
+	}
+
+	fn read_directory(&self, path: &Path) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+		if !self.is_dir(path) {
+			return Err(AssetIoError::NotFound(path.to_owned()));
+		}
+		let mut entries = Vec::new();
+		for file in self.files.read().keys() {
+			let relative_path = match file.strip_prefix(path) {
+				Ok(relative_path) => relative_path,
+				Err(_) => continue,
+			};
+			if let Some(name) = relative_path.components().next() {
+				let entry = path.join(name);
+				if !entries.contains(&entry) {
+					entries.push(entry);
+				}
+			}
+		}
+		Ok(Box::new(entries.into_iter()))
+	}
+
+	fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+		let files = self.files.read();
+		if files.contains_key(path) {
+			Ok(Metadata::new(FileType::File))
+		} else if files.keys().any(|file| file.starts_with(path)) {
+			Ok(Metadata::new(FileType::Directory))
+		} else {
+			Err(AssetIoError::NotFound(path.to_owned()))
+		}
+	}
+
+	fn write_path<'a>(
+		&'a self,
+		path: &'a Path,
+		bytes: &'a [u8],
+	) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+		Box::pin(async move {
+			self.insert(path, bytes.to_vec());
+			Ok(())
+		})
+	}
+
+	fn remove_path(&self, path: &Path) -> Result<(), AssetIoError> {
+		if self.remove(path) {
+			Ok(())
+		} else {
+			Err(AssetIoError::NotFound(path.to_owned()))
+		}
+	}
+
+	fn rename(&self, from: &Path, to: &Path) -> Result<(), AssetIoError> {
+		let mut files = self.files.write();
+		let bytes = files
+			.remove(from)
+			.ok_or_else(|| AssetIoError::NotFound(from.to_owned()))?;
+		files.insert(to.to_owned(), bytes);
+		Ok(())
+	}
+
+	fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+		Ok(())
+	}
+
+	fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+		#[cfg(all(
+			feature = "filesystem_watcher",
+			all(not(target_arch = "wasm32"), not(target_os = "android"))
+		))]
+		{
+			let mut watcher = self.watcher.write();
+			if watcher.filesystem_watcher.is_none() {
+				let mut filesystem_watcher = FilesystemWatcher::default();
+				for file_path in watcher.sources.keys() {
+					filesystem_watcher
+						.watch(file_path)
+						.map_err(|_error| AssetIoError::PathWatchError(file_path.clone()))?;
+				}
+				watcher.filesystem_watcher = Some(filesystem_watcher);
+			}
+		}
+		Ok(())
+	}
+
+	/// Reads the watched files modified on disk since the last call into memory, returning their
+	/// paths in memory.
+	#[cfg(all(
+		feature = "filesystem_watcher",
+		all(not(target_arch = "wasm32"), not(target_os = "android"))
+	))]
+	fn changed_paths(&self) -> Vec<PathBuf> {
+		let watcher = self.watcher.read();
+		let filesystem_watcher = match &watcher.filesystem_watcher {
+			Some(filesystem_watcher) => filesystem_watcher,
+			None => return Vec::new(),
+		};
+		let mut changed_paths = Vec::new();
+		while let Ok(event) = filesystem_watcher.receiver.try_recv() {
+			if let Ok(notify::event::Event {
+				kind: notify::event::EventKind::Modify(_),
+				paths,
+				..
+			}) = event
+			{
+				for file_path in paths {
+					let path = match watcher.sources.get(&file_path) {
+						Some(path) => path,
+						None => continue,
+					};
+					match std::fs::read(&file_path) {
+						Ok(bytes) => {
+							self.insert(path.clone(), bytes);
+							if !changed_paths.contains(path) {
+								changed_paths.push(path.clone());
+							}
+						},
+						Err(err) => bevy_log::warn!("failed to read {:?}: {}", file_path, err),
+					}
+				}
+			}
+		}
+		changed_paths
+	}
+}
+
+/// Gets the path of a file embedded by [`embedded_asset!`](crate::embedded_asset) in the
+/// `embedded` asset source, `crate_name/path` with `path` relative to the `src` folder of the
+/// crate.
+#[doc(hidden)]
+pub fn embedded_path(crate_name: &str, file: &str, path: &str) -> PathBuf {
+	let directory = Path::new(file)
+		.parent()
+		.expect("file path must have a parent");
+	// the path of `file!()` is relative to the package or to the workspace
+	let components: Vec<_> = directory.components().collect();
+	let relative_directory = match components
+		.iter()
+		.rposition(|component| component.as_os_str() == "src")
+	{
+		Some(src) => components[src + 1..].iter().collect(),
+		None => directory.to_owned(),
+	};
+	let mut embedded_path = PathBuf::from(crate_name);
+	embedded_path.push(relative_directory);
+	embedded_path.push(path);
+	embedded_path
+}
+
+/// Finds the file on disk a file was embedded from, given the `file!()` of the embedding source
+/// file and the manifest directory of its crate.
+#[doc(hidden)]
+pub fn embedded_file_path(manifest_dir: &str, file: &str, path: &str) -> Option<PathBuf> {
+	let file = Path::new(file);
+	let directory = file.parent()?;
+	Path::new(manifest_dir)
+		.ancestors()
+		.map(|ancestor| ancestor.join(directory).join(path))
+		.find(|file_path| file_path.is_file())
+}
+
+/// Adds a file embedded by [`embedded_asset!`](crate::embedded_asset) to the `embedded` asset
+/// source, reloading it from `file_path` on disk when it changes.
+#[doc(hidden)]
+pub fn embed_asset(
+	asset_server: &AssetServer,
+	path: PathBuf,
+	bytes: &'static [u8],
+	file_path: Option<PathBuf>,
+) {
+	let layers = asset_server
+		.asset_sources()
+		.layers(AssetSources::EMBEDDED);
+	let memory_asset_io = layers
+		.iter()
+		.find_map(|layer| layer.downcast_ref::<MemoryAssetIo>())
+		.expect("the `embedded` asset source is added by the `AssetPlugin`");
+	if let Some(file_path) = file_path {
+		memory_asset_io.watch_file(path.clone(), file_path);
+	}
+	memory_asset_io.insert(path, bytes);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures_lite::future::block_on;
+
+	#[test]
+	fn memory_asset_io() {
+		let asset_io = MemoryAssetIo::new();
+		asset_io.insert("shaders/a.wgsl", &b"a"[..]);
+		asset_io.insert("shaders/nested/b.wgsl", b"b".to_vec());
+
+		assert_eq!(
+			block_on(asset_io.load_path(Path::new("shaders/a.wgsl"))).unwrap(),
+			b"a"
+		);
+		assert!(matches!(
+			block_on(asset_io.load_path(Path::new("shaders/c.wgsl"))),
+			Err(AssetIoError::NotFound(_))
+		));
+		assert_eq!(
+			block_on(asset_io.load_path_range(Path::new("shaders/nested/b.wgsl"), 0..10)).unwrap(),
+			b"b"
+		);
+		assert!(asset_io.is_file(Path::new("shaders/nested/b.wgsl")));
+		assert!(asset_io.is_dir(Path::new("shaders/nested")));
+
+		let mut entries: Vec<_> = asset_io
+			.read_directory(Path::new("shaders"))
+			.unwrap()
+			.collect();
+		entries.sort();
+		assert_eq!(
+			entries,
+			[
+				PathBuf::from("shaders/a.wgsl"),
+				PathBuf::from("shaders/nested")
+			]
+		);
+	}
+
+	#[test]
+	fn embedded_paths() {
+		assert_eq!(
+			embedded_path("my_crate", "crates/my_crate/src/render/mod.rs", "a.wgsl"),
+			Path::new("my_crate/render/a.wgsl")
+		);
+		assert_eq!(
+			embedded_path("my_crate", "src/lib.rs", "shaders/a.wgsl"),
+			Path::new("my_crate/shaders/a.wgsl")
+		);
+	}
+}
@@ -1,26 +1,36 @@
 #[cfg(target_os = "android")]
 mod android_asset_io;
+#[cfg(feature = "asset_archive")]
+mod archive_asset_io;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 mod file_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
+mod asset_sources;
+mod memory_asset_io;
 mod metadata;
 
 #[cfg(target_os = "android")]
 pub use android_asset_io::*;
+#[cfg(feature = "asset_archive")]
+pub use archive_asset_io::*;
 #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 pub use file_asset_io::*;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
+pub use asset_sources::*;
+pub use memory_asset_io::*;
 pub use metadata::*;
 
 use anyhow::Result;
 use bevy_utils::BoxedFuture;
 use downcast_rs::{impl_downcast, Downcast};
+use futures_lite::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
 use std::{
-	io,
+	io::{self, SeekFrom},
+	ops::Range,
 	path::{Path, PathBuf},
 };
 use thiserror::Error;
@@ -39,8 +49,48 @@ pub enum AssetIoError {
 	/// Failed to watch path.
 	#[error("failed to watch path: {0}")]
 	PathWatchError(PathBuf),
+
+	/// The asset I/O can't write to the path.
+	#[error("path is read-only: {0}")]
+	ReadOnly(PathBuf),
+}
+
+impl AssetIoError {
+	/// Returns `true` if the error may not happen again when retrying, such as a timeout or an
+	/// interrupted connection.
+	pub fn is_transient(&self) -> bool {
+		match self {
+			AssetIoError::Io(err) => matches!(
+				err.kind(),
+				io::ErrorKind::Interrupted
+					| io::ErrorKind::TimedOut
+					| io::ErrorKind::WouldBlock
+					| io::ErrorKind::ConnectionRefused
+					| io::ErrorKind::ConnectionReset
+					| io::ErrorKind::ConnectionAborted
+					| io::ErrorKind::NotConnected
+					| io::ErrorKind::BrokenPipe
+			),
+			_ => false,
+		}
+	}
 }
 
+/// An asynchronous reader with seeking, see [`Reader`].
+pub trait AsyncReadAndSeek: AsyncRead + AsyncSeek {}
+
+impl<T: AsyncRead + AsyncSeek + ?Sized> AsyncReadAndSeek for T {}
+
+/// A reader streaming the data of a file from an [`AssetIo`], returned by
+/// [`AssetIo::read_path`].
+///
+/// A loader only borrows the reader while loading, see [`AssetLoader::load_reader`]. An asset
+/// streaming more of its source once loaded reads it again, for example with
+/// [`AssetIo::load_path_range`].
+///
+/// [`AssetLoader::load_reader`]: crate::AssetLoader::load_reader
+pub type Reader = Box<dyn AsyncReadAndSeek + Send + Sync + Unpin>;
+
 /// A storage provider for an [`AssetServer`].
 ///
 /// An asset I/O is the backend actually providing data for the asset loaders managed by the asset
@@ -56,6 +106,32 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
 	/// Returns a future to load the full file data at the provided path.
 	fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>>;
 
+	/// Returns a future to open a [`Reader`] streaming the file data at the provided path.
+	///
+	/// By default the full file data is loaded with [`load_path`](AssetIo::load_path) and read
+	/// from memory. Asset I/Os able to read files in parts should implement this method.
+	fn read_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Reader, AssetIoError>> {
+		Box::pin(async move {
+			let bytes = self.load_path(path).await?;
+			Ok(Box::new(futures_lite::io::Cursor::new(bytes)) as Reader)
+		})
+	}
+
+	/// Returns a future to load the bytes in `range` of the file data at the provided path.
+	///
+	/// The range is truncated to the end of the file. By default the bytes are read from the
+	/// [`Reader`] of [`read_path`](AssetIo::read_path).
+	fn load_path_range<'a>(
+		&'a self,
+		path: &'a Path,
+		range: Range<u64>,
+	) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+		Box::pin(async move {
+			let mut reader = self.read_path(path).await?;
+			Ok(read_range(&mut reader, range).await?)
+		})
+	}
+
 	/// Returns an iterator of directory entry names at the provided path.
 	fn read_directory(&self, path: &Path) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError>;
 
@@ -68,6 +144,35 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
 	/// Enables change tracking in this asset I/O.
 	fn watch_for_changes(&self) -> Result<(), AssetIoError>;
 
+	/// Returns the paths of the watched files changed since the last call, once
+	/// [change tracking](AssetIo::watch_for_changes) is enabled. Asset I/Os without change
+	/// tracking return no paths.
+	fn changed_paths(&self) -> Vec<PathBuf> {
+		Vec::new()
+	}
+
+	/// Returns a future to write the full file data at the provided path, creating its parent
+	/// directories and replacing any existing file.
+	///
+	/// Asset I/Os are read-only unless they implement the writing methods.
+	fn write_path<'a>(
+		&'a self,
+		path: &'a Path,
+		_bytes: &'a [u8],
+	) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+		Box::pin(async move { Err(AssetIoError::ReadOnly(path.to_owned())) })
+	}
+
+	/// Removes the file at the provided path.
+	fn remove_path(&self, path: &Path) -> Result<(), AssetIoError> {
+		Err(AssetIoError::ReadOnly(path.to_owned()))
+	}
+
+	/// Moves the file at the path `from` to the path `to`, replacing any existing file.
+	fn rename(&self, from: &Path, _to: &Path) -> Result<(), AssetIoError> {
+		Err(AssetIoError::ReadOnly(from.to_owned()))
+	}
+
 	/// Returns `true` if the path is a directory.
 	fn is_dir(&self, path: &Path) -> bool {
 		self
@@ -88,3 +193,22 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
 }
 
 impl_downcast!(AssetIo);
+
+/// Reads the bytes in `range` of a reader, truncated to the end of its data.
+pub async fn read_range<R: AsyncRead + AsyncSeek + Unpin + ?Sized>(
+	reader: &mut R,
+	range: Range<u64>,
+) -> io::Result<Vec<u8>> {
+	let mut bytes = Vec::new();
+	if range.is_empty() {
+		return Ok(bytes);
+	}
+	reader
+		.seek(SeekFrom::Start(range.start))
+		.await?;
+	reader
+		.take(range.end - range.start)
+		.read_to_end(&mut bytes)
+		.await?;
+	Ok(bytes)
+}
@@ -1,25 +1,43 @@
-use crate::{AssetIo, AssetIoError, Metadata};
+use crate::{AssetIo, AssetIoError, Metadata, Reader};
 use anyhow::Result;
 use bevy_utils::BoxedFuture;
+use futures_lite::{ready, AsyncRead, AsyncSeek};
 use js_sys::Uint8Array;
 use std::{
 	convert::TryFrom,
+	future::Future,
+	io::{self, SeekFrom},
+	ops::Range,
 	path::{Path, PathBuf},
+	pin::Pin,
+	task::{Context, Poll},
 };
-use wasm_bindgen::JsCast;
+use wasm_bindgen::{JsCast, JsValue};
 use wasm_bindgen_futures::JsFuture;
-use web_sys::Response;
+use web_sys::{Request, Response};
+
+/// The number of bytes the readers of [`WasmAssetIo::read_path`] fetch at once.
+const READ_CHUNK_SIZE: u64 = 256 * 1024;
 
 /// I/O implementation for web builds.
 ///
 /// Implementation details:
 ///
 /// - `load_path` makes [fetch()] requests.
+/// - `load_path_range` makes [fetch()] requests with a [`Range`] header. Servers ignoring the
+///   header send the whole file, of which the range is kept.
+/// - `read_path` fetches the parts of the file being read, 256 KiB at a time, with [fetch()]
+///   requests with a [`Range`] header. Servers ignoring the header send the whole file, which
+///   is then read from memory.
 /// - `read_directory` always returns an empty iterator.
 /// - `get_metadata` will always return an error.
 /// - Watching for changes is not supported. The watcher methods will do nothing.
 ///
+/// Failed requests and server errors which may not happen again, such as timeouts, are
+/// [transient](AssetIoError::is_transient) errors.
+///
 /// [fetch()]: https://developer.mozilla.org/en-US/docs/Web/API/fetch
+/// [`Range`]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Range
 pub struct WasmAssetIo {
 	root_path: PathBuf,
 }
@@ -33,20 +51,274 @@ impl WasmAssetIo {
 	}
 }
 
+/// Fetches `request`, returning the response if it's successful or has one of the `accepted`
+/// statuses.
+async fn fetch(path: &Path, request: &Request, accepted: &[u16]) -> Result<Response, AssetIoError> {
+	let window = web_sys::window()
+		.ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "no window to fetch from"))?;
+	// the request failed to reach the server
+	let resp_value = JsFuture::from(window.fetch_with_request(request))
+		.await
+		.map_err(|err| js_error(io::ErrorKind::ConnectionRefused, err))?;
+	let resp: Response = resp_value
+		.dyn_into()
+		.map_err(|err| js_error(io::ErrorKind::InvalidData, err))?;
+	let status = resp.status();
+	if resp.ok() || accepted.contains(&status) {
+		return Ok(resp);
+	}
+	let kind = match status {
+		// missing files, such as the optional `.meta` files of assets
+		404 => return Err(AssetIoError::NotFound(path.to_owned())),
+		408 | 504 => io::ErrorKind::TimedOut,
+		429 | 502 | 503 => io::ErrorKind::ConnectionRefused,
+		_ => io::ErrorKind::Other,
+	};
+	Err(
+		io::Error::new(
+			kind,
+			format!("fetching {:?} failed with status {}", path, status),
+		)
+		.into(),
+	)
+}
+
+/// Reads the body of a response.
+async fn read_body(resp: &Response) -> Result<Vec<u8>, AssetIoError> {
+	let body = resp
+		.array_buffer()
+		.map_err(|err| js_error(io::ErrorKind::InvalidData, err))?;
+	// the connection was interrupted while receiving the body
+	let data = JsFuture::from(body)
+		.await
+		.map_err(|err| js_error(io::ErrorKind::ConnectionAborted, err))?;
+	Ok(Uint8Array::new(&data).to_vec())
+}
+
+/// Bytes of a file fetched with [`fetch_range`].
+struct FetchedRange {
+	/// The position of the bytes in the file.
+	start: u64,
+	bytes: Vec<u8>,
+	/// The length of the file, if the server sent it.
+	file_len: Option<u64>,
+}
+
+/// Fetches the bytes in the non-empty `range` of the file at `path`, or the whole file if the
+/// server ignores the [`Range`] header.
+///
+/// [`Range`]: https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Range
+async fn fetch_range(path: PathBuf, range: Range<u64>) -> Result<FetchedRange, AssetIoError> {
+	let request = request(&path)?;
+	request
+		.headers()
+		.set("Range", &format!("bytes={}-{}", range.start, range.end - 1))
+		.map_err(|err| js_error(io::ErrorKind::InvalidInput, err))?;
+	let resp = fetch(&path, &request, &[416]).await?;
+	// the range starts after the end of the file
+	if resp.status() == 416 {
+		return Ok(FetchedRange {
+			start: range.start,
+			bytes: Vec::new(),
+			file_len: None,
+		});
+	}
+	let bytes = read_body(&resp).await?;
+	// 206 Partial Content, otherwise the server sent the whole file
+	if resp.status() == 206 {
+		// `Content-Range: bytes <start>-<end>/<length>`, the length may be `*`
+		let file_len = resp
+			.headers()
+			.get("Content-Range")
+			.ok()
+			.flatten()
+			.and_then(|content_range| {
+				content_range
+					.rsplit('/')
+					.next()
+					.and_then(|len| len.parse().ok())
+			});
+		return Ok(FetchedRange {
+			start: range.start,
+			bytes,
+			file_len,
+		});
+	}
+	Ok(FetchedRange {
+		start: 0,
+		file_len: Some(bytes.len() as u64),
+		bytes,
+	})
+}
+
+/// Converts a JavaScript error to an [`AssetIoError`] of the given kind.
+fn js_error(kind: io::ErrorKind, err: JsValue) -> AssetIoError {
+	io::Error::new(kind, format!("{:?}", err)).into()
+}
+
+/// Converts an [`AssetIoError`] to the error of a reader.
+fn io_error(err: AssetIoError) -> io::Error {
+	match err {
+		AssetIoError::Io(err) => err,
+		err @ AssetIoError::NotFound(_) => io::Error::new(io::ErrorKind::NotFound, err.to_string()),
+		err => io::Error::new(io::ErrorKind::Other, err.to_string()),
+	}
+}
+
+/// Creates a request for the file at `path`.
+fn request(path: &Path) -> Result<Request, AssetIoError> {
+	let url = path
+		.to_str()
+		.ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+	Request::new_with_str(url).map_err(|err| js_error(io::ErrorKind::InvalidInput, err))
+}
+
+/// Reads a file fetched in chunks of [`READ_CHUNK_SIZE`] bytes, see [`WasmAssetIo::read_path`].
+struct WasmFileReader {
+	path: PathBuf,
+	position: u64,
+	/// The last chunk fetched.
+	chunk: FetchedRange,
+	/// The chunk being fetched, with its position.
+	pending: Option<(
+		u64,
+		Pin<Box<dyn Future<Output = Result<FetchedRange, AssetIoError>>>>,
+	)>,
+}
+
+// SAFETY: the web is single threaded, the JavaScript values of the pending fetch are never sent
+// to another thread
+unsafe impl Send for WasmFileReader {}
+unsafe impl Sync for WasmFileReader {}
+
+impl WasmFileReader {
+	/// Returns `true` if the byte at the position of the reader was fetched.
+	fn is_fetched(&self) -> bool {
+		self.position >= self.chunk.start
+			&& self.position - self.chunk.start < self.chunk.bytes.len() as u64
+	}
+
+	/// Returns `true` if the position of the reader is known to be at the end of the file.
+	fn is_at_end(&self) -> bool {
+		matches!(self.chunk.file_len, Some(file_len) if self.position >= file_len)
+	}
+
+	/// Fetches the chunk starting at `start`.
+	fn poll_fetch(&mut self, cx: &mut Context<'_>, start: u64) -> Poll<io::Result<()>> {
+		// the reader was moved since the fetch started
+		if !matches!(self.pending, Some((pending_start, _)) if pending_start == start) {
+			let fetched = fetch_range(self.path.clone(), start..start + READ_CHUNK_SIZE);
+			self.pending = Some((start, Box::pin(fetched)));
+		}
+		let (_, pending) = self.pending.as_mut().unwrap();
+		let fetched = ready!(pending.as_mut().poll(cx));
+		self.pending = None;
+		let fetched = fetched.map_err(io_error)?;
+		// a range after the end of the file doesn't tell the length of the file
+		let file_len = fetched.file_len.or(self.chunk.file_len);
+		self.chunk = FetchedRange {
+			file_len,
+			..fetched
+		};
+		Poll::Ready(Ok(()))
+	}
+}
+
+impl AsyncRead for WasmFileReader {
+	fn poll_read(
+		self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+		buf: &mut [u8],
+	) -> Poll<io::Result<usize>> {
+		let this = self.get_mut();
+		if buf.is_empty() || this.is_at_end() {
+			return Poll::Ready(Ok(0));
+		}
+		if !this.is_fetched() {
+			ready!(this.poll_fetch(cx, this.position))?;
+			// the position is after the end of the file
+			if !this.is_fetched() {
+				return Poll::Ready(Ok(0));
+			}
+		}
+		let offset = (this.position - this.chunk.start) as usize;
+		let len = buf.len().min(this.chunk.bytes.len() - offset);
+		buf[..len].copy_from_slice(&this.chunk.bytes[offset..offset + len]);
+		this.position += len as u64;
+		Poll::Ready(Ok(len))
+	}
+}
+
+impl AsyncSeek for WasmFileReader {
+	fn poll_seek(self: Pin<&mut Self>, cx: &mut Context<'_>, pos: SeekFrom) -> Poll<io::Result<u64>> {
+		let this = self.get_mut();
+		let (base, offset) = match pos {
+			SeekFrom::Start(position) => (position, 0),
+			SeekFrom::Current(offset) => (this.position, offset),
+			SeekFrom::End(offset) => {
+				if this.chunk.file_len.is_none() {
+					ready!(this.poll_fetch(cx, this.position))?;
+				}
+				match this.chunk.file_len {
+					Some(file_len) => (file_len, offset),
+					None => {
+						return Poll::Ready(Err(io::Error::new(
+							io::ErrorKind::Unsupported,
+							"the server didn't send the length of the file",
+						)))
+					},
+				}
+			},
+		};
+		let position = base as i128 + offset as i128;
+		if position < 0 {
+			return Poll::Ready(Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"seeking before the start of the file",
+			)));
+		}
+		this.position = position as u64;
+		Poll::Ready(Ok(this.position))
+	}
+}
+
 impl AssetIo for WasmAssetIo {
 	fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
 		Box::pin(async move {
 			let path = self.root_path.join(path);
-			let window = web_sys::window().unwrap();
-			let resp_value = JsFuture::from(window.fetch_with_str(path.to_str().unwrap()))
-				.await
-				.unwrap();
-			let resp: Response = resp_value.dyn_into().unwrap();
-			let data = JsFuture::from(resp.array_buffer().unwrap())
-				.await
-				.unwrap();
-			let bytes = Uint8Array::new(&data).to_vec();
-			Ok(bytes)
+			let resp = fetch(&path, &request(&path)?, &[]).await?;
+			read_body(&resp).await
+		})
+	}
+
+	fn read_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Reader, AssetIoError>> {
+		Box::pin(async move {
+			let path = self.root_path.join(path);
+			// the first chunk is fetched right away, so a missing file fails to open
+			let chunk = fetch_range(path.clone(), 0..READ_CHUNK_SIZE).await?;
+			Ok(Box::new(WasmFileReader {
+				path,
+				position: 0,
+				chunk,
+				pending: None,
+			}) as Reader)
+		})
+	}
+
+	fn load_path_range<'a>(
+		&'a self,
+		path: &'a Path,
+		range: Range<u64>,
+	) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+		Box::pin(async move {
+			if range.is_empty() {
+				return Ok(Vec::new());
+			}
+			let fetched = fetch_range(self.root_path.join(path), range.clone()).await?;
+			let bytes_end = fetched.start + fetched.bytes.len() as u64;
+			let end = range.end.min(bytes_end);
+			let start = range.start.min(end);
+			Ok(fetched.bytes[(start - fetched.start) as usize..(end - fetched.start) as usize].to_vec())
 		})
 	}
 
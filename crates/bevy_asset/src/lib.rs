@@ -25,6 +25,8 @@ mod info;
 mod io;
 mod loader;
 mod path;
+mod processor;
+mod saver;
 
 /// The `bevy_asset` prelude.
 pub mod prelude {
@@ -41,6 +43,8 @@ pub use info::*;
 pub use io::*;
 pub use loader::*;
 pub use path::*;
+pub use processor::*;
+pub use saver::*;
 
 use bevy_app::{prelude::Plugin, App};
 use bevy_ecs::schedule::{StageLabel, SystemStage};
@@ -98,14 +102,101 @@ pub fn create_platform_default_asset_io(app: &mut App) -> Box<dyn AssetIo> {
 	Box::new(source)
 }
 
+/// Creates the `AssetIo` of the processed asset folder, or `None` if assets are loaded without
+/// processing.
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+fn create_processed_asset_io(app: &mut App) -> Option<FileAssetIo> {
+	let processor_settings = app
+		.world
+		.get_resource_or_insert_with(AssetProcessorSettings::default)
+		.clone();
+	if processor_settings.mode == AssetProcessingMode::Unprocessed {
+		return None;
+	}
+	let watch_for_changes = app
+		.world
+		.get_resource_or_insert_with(AssetServerSettings::default)
+		.watch_for_changes;
+	Some(FileAssetIo::new(
+		&processor_settings.processed_asset_folder,
+		watch_for_changes,
+	))
+}
+
+/// Adds the [`AssetSourceProcessor`] writing into `destination` and its systems, if the
+/// [`AssetProcessorSettings`] ask for assets to be processed.
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+fn add_asset_source_processor(app: &mut App, destination: std::path::PathBuf) {
+	let mode = app
+		.world
+		.resource::<AssetProcessorSettings>()
+		.mode;
+	if !matches!(
+		mode,
+		AssetProcessingMode::ProcessOnStartup | AssetProcessingMode::ProcessAndExit
+	) {
+		return;
+	}
+	let settings = app.world.resource::<AssetServerSettings>();
+	let source_io = FileAssetIo::new(&settings.asset_folder, settings.watch_for_changes);
+	if settings.watch_for_changes {
+		source_io
+			.watch_path_for_changes(std::path::Path::new(""))
+			.unwrap();
+	}
+
+	let asset_server = app.world.resource::<AssetServer>().clone();
+	app.insert_resource(AssetProcessingTask::new(
+		&asset_server,
+		mode == AssetProcessingMode::ProcessAndExit,
+	));
+	app.insert_resource(AssetSourceProcessor::new(
+		asset_server,
+		Box::new(source_io),
+		destination,
+	));
+	app.add_startup_system_to_stage(bevy_app::StartupStage::PreStartup, process_assets_system);
+	app.add_system_to_stage(AssetStage::LoadAssets, poll_asset_processing_system);
+	#[cfg(feature = "filesystem_watcher")]
+	app.add_system_to_stage(AssetStage::LoadAssets, process_changed_assets_system);
+}
+
 impl Plugin for AssetPlugin {
 	fn build(&self, app: &mut App) {
+		#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+		if !app.world.contains_resource::<AssetServer>() {
+			if let Some(processed_io) = create_processed_asset_io(app) {
+				let destination = processed_io.root_path().clone();
+				app.insert_resource(AssetServer::with_boxed_io(Box::new(processed_io)));
+				add_asset_source_processor(app, destination);
+			}
+		}
+
 		if !app.world.contains_resource::<AssetServer>() {
 			let source = create_platform_default_asset_io(app);
 			let asset_server = AssetServer::with_boxed_io(source);
 			app.insert_resource(asset_server);
 		}
 
+		let asset_server = app.world.resource::<AssetServer>().clone();
+		if !asset_server
+			.asset_sources()
+			.contains_source(AssetSources::EMBEDDED)
+		{
+			let embedded_io = MemoryAssetIo::new();
+			let settings = app
+				.world
+				.get_resource_or_insert_with(AssetServerSettings::default);
+			if settings.watch_for_changes {
+				if let Err(err) = embedded_io.watch_for_changes() {
+					bevy_log::warn!("{}", err);
+				}
+			}
+			asset_server
+				.asset_sources()
+				.add_source(AssetSources::EMBEDDED, embedded_io);
+		}
+
 		app.add_stage_before(
 			bevy_app::CoreStage::PreUpdate,
 			AssetStage::LoadAssets,
@@ -117,6 +208,16 @@ impl Plugin for AssetPlugin {
 			SystemStage::parallel(),
 		);
 		app.register_type::<HandleId>();
+		app.add_event::<AssetDependenciesLoaded>();
+		app.add_system_to_stage(
+			AssetStage::AssetEvents,
+			asset_server::dependencies_loaded_system,
+		);
+		app.add_event::<AssetLoadFailed>();
+		app.add_system_to_stage(
+			AssetStage::AssetEvents,
+			asset_server::asset_load_failures_system,
+		);
 		app.add_system_to_stage(
 			bevy_app::CoreStage::PreUpdate,
 			asset_server::free_unused_assets_system,
@@ -1,6 +1,6 @@
 use crate::{
-	path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
-	RefChangeChannel, SourceMeta,
+	path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, AssetServerError, Assets, Handle,
+	HandleId, ProcessMeta, Reader, RefChangeChannel, SourceMeta,
 };
 use anyhow::Error;
 use anyhow::Result;
@@ -8,14 +8,29 @@ use bevy_ecs::system::{Res, ResMut};
 use bevy_reflect::{TypeUuid, TypeUuidDynamic};
 use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender};
-use downcast_rs::{impl_downcast, Downcast};
-use std::path::Path;
+use downcast_rs::{impl_downcast, Downcast, DowncastSync};
+use futures_lite::AsyncReadExt;
+use serde::{de::DeserializeOwned, de::IgnoredAny, Serialize};
+use std::{
+	any::{type_name, TypeId},
+	ops::Range,
+	path::Path,
+	sync::Arc,
+};
 
 /// A loader for an asset source.
 ///
 /// Types implementing this trait are used by the asset server to load assets into their respective
 /// asset storages.
 pub trait AssetLoader: Send + Sync + 'static {
+	/// The settings an asset source is loaded with, available from
+	/// [`LoadContext::settings`].
+	///
+	/// They are given to [`AssetServer::load_with_settings`], or read from the `loader_settings`
+	/// of the `.meta` file of the source (see [`ProcessMeta`]). Missing settings use their default
+	/// values. Loaders without settings use `()`.
+	type Settings: Serialize + DeserializeOwned + Default + Send + Sync + 'static;
+
 	/// Processes the asset in an asynchronous closure.
 	fn load<'a>(
 		&'a self,
@@ -23,10 +38,117 @@ pub trait AssetLoader: Send + Sync + 'static {
 		load_context: &'a mut LoadContext,
 	) -> BoxedFuture<'a, Result<(), Error>>;
 
+	/// Processes the asset from a [`Reader`] streaming its source, in an asynchronous closure.
+	///
+	/// This is how the asset server loads assets. By default the whole source is read and given
+	/// to [`load`](AssetLoader::load). Loaders which don't need all of the source at once, for
+	/// example to only read the headers of a sound or the smallest mip levels of a texture, can
+	/// override this method and seek through the reader instead, or read ranges of the source
+	/// later with [`AssetIo::load_path_range`].
+	fn load_reader<'a>(
+		&'a self,
+		reader: &'a mut Reader,
+		load_context: &'a mut LoadContext,
+	) -> BoxedFuture<'a, Result<(), Error>> {
+		Box::pin(async move {
+			let mut bytes = Vec::new();
+			reader.read_to_end(&mut bytes).await?;
+			self.load(&bytes, load_context).await
+		})
+	}
+
 	/// Returns a list of extensions supported by this asset loader, without the preceding dot.
 	fn extensions(&self) -> &[&str];
 }
 
+/// The settings of an [`AssetLoader`], see [`AssetLoader::Settings`].
+pub trait LoaderSettings: DowncastSync + erased_serde::Serialize {}
+impl_downcast!(sync LoaderSettings);
+
+impl<T> LoaderSettings for T where T: Serialize + Send + Sync + 'static {}
+
+impl Serialize for dyn LoaderSettings {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		erased_serde::serialize(self, serializer)
+	}
+}
+
+/// An [`AssetLoader`] with its settings type erased.
+pub(crate) trait ErasedAssetLoader: Send + Sync + 'static {
+	fn load<'a>(
+		&'a self,
+		bytes: &'a [u8],
+		load_context: &'a mut LoadContext,
+	) -> BoxedFuture<'a, Result<(), Error>>;
+
+	fn load_reader<'a>(
+		&'a self,
+		reader: &'a mut Reader,
+		load_context: &'a mut LoadContext,
+	) -> BoxedFuture<'a, Result<(), Error>>;
+
+	fn extensions(&self) -> &[&str];
+
+	/// Reads the loader settings of a `.meta` file, or the default settings without one.
+	fn read_settings(&self, meta: Option<&str>) -> Result<Arc<dyn LoaderSettings>, ron::Error>;
+
+	/// Returns `true` if `settings` are the settings of this loader.
+	fn is_settings(&self, settings: &dyn LoaderSettings) -> bool;
+
+	/// Returns `false` if the settings of this loader are `()`.
+	fn has_settings(&self) -> bool;
+
+	fn settings_type_name(&self) -> &'static str;
+
+	fn type_name(&self) -> &'static str;
+}
+
+impl<L: AssetLoader> ErasedAssetLoader for L {
+	fn load<'a>(
+		&'a self,
+		bytes: &'a [u8],
+		load_context: &'a mut LoadContext,
+	) -> BoxedFuture<'a, Result<(), Error>> {
+		AssetLoader::load(self, bytes, load_context)
+	}
+
+	fn load_reader<'a>(
+		&'a self,
+		reader: &'a mut Reader,
+		load_context: &'a mut LoadContext,
+	) -> BoxedFuture<'a, Result<(), Error>> {
+		AssetLoader::load_reader(self, reader, load_context)
+	}
+
+	fn extensions(&self) -> &[&str] {
+		AssetLoader::extensions(self)
+	}
+
+	fn read_settings(&self, meta: Option<&str>) -> Result<Arc<dyn LoaderSettings>, ron::Error> {
+		let settings = match meta {
+			Some(meta) => ron::from_str::<ProcessMeta<IgnoredAny, L::Settings>>(meta)?.loader_settings,
+			None => L::Settings::default(),
+		};
+		Ok(Arc::new(settings))
+	}
+
+	fn is_settings(&self, settings: &dyn LoaderSettings) -> bool {
+		settings.is::<L::Settings>()
+	}
+
+	fn has_settings(&self) -> bool {
+		TypeId::of::<L::Settings>() != TypeId::of::<()>()
+	}
+
+	fn settings_type_name(&self) -> &'static str {
+		type_name::<L::Settings>()
+	}
+
+	fn type_name(&self) -> &'static str {
+		type_name::<L>()
+	}
+}
+
 /// An essential piece of data of an application.
 ///
 /// Assets are the building blocks of games. They can be anything, from images and sounds to scenes
@@ -111,6 +233,7 @@ pub struct LoadContext<'a> {
 	pub(crate) labeled_assets: HashMap<Option<String>, BoxedLoadedAsset>,
 	pub(crate) path: &'a Path,
 	pub(crate) version: usize,
+	pub(crate) settings: Arc<dyn LoaderSettings>,
 }
 
 impl<'a> LoadContext<'a> {
@@ -119,6 +242,7 @@ impl<'a> LoadContext<'a> {
 		ref_change_channel: &'a RefChangeChannel,
 		asset_io: &'a dyn AssetIo,
 		version: usize,
+		settings: Arc<dyn LoaderSettings>,
 	) -> Self {
 		Self {
 			ref_change_channel,
@@ -126,6 +250,7 @@ impl<'a> LoadContext<'a> {
 			labeled_assets: Default::default(),
 			version,
 			path,
+			settings,
 		}
 	}
 
@@ -134,6 +259,17 @@ impl<'a> LoadContext<'a> {
 		self.path
 	}
 
+	/// Gets the settings the asset source is loaded with.
+	///
+	/// Fails with [`AssetServerError::IncorrectSettingsType`] if `S` is not the
+	/// [`AssetLoader::Settings`] of the loader of the source.
+	pub fn settings<S: LoaderSettings>(&self) -> Result<&S, AssetServerError> {
+		self
+			.settings
+			.downcast_ref::<S>()
+			.ok_or_else(|| AssetServerError::IncorrectSettingsType(type_name::<S>()))
+	}
+
 	/// Returns `true` if the load context contains an asset with the specified label.
 	pub fn has_labeled_asset(&self, label: &str) -> bool {
 		self
@@ -166,6 +302,19 @@ impl<'a> LoadContext<'a> {
 		self.asset_io.load_path(path.as_ref()).await
 	}
 
+	/// Reads the bytes in `range` of the file at the specified path through the [`AssetIo`]
+	/// associated with this context, see [`AssetIo::load_path_range`].
+	pub async fn read_asset_range<P: AsRef<Path>>(
+		&self,
+		path: P,
+		range: Range<u64>,
+	) -> Result<Vec<u8>, AssetIoError> {
+		self
+			.asset_io
+			.load_path_range(path.as_ref(), range)
+			.await
+	}
+
 	/// Generates metadata for the assets managed by this load context.
 	pub fn get_asset_metas(&self) -> SourceMeta {
 		SourceMeta {
@@ -213,6 +362,15 @@ pub enum AssetLifecycleEvent<T> {
 	Create(AssetResult<T>),
 	/// An asset was freed.
 	Free(HandleId),
+	/// An asset was found to be a duplicate of another asset, see [`Assets::get_alias`].
+	Alias {
+		/// The id of the duplicate asset.
+		id: HandleId,
+		/// The id of the asset it is a duplicate of.
+		original: HandleId,
+		/// The version of the source of the duplicate asset.
+		version: usize,
+	},
 }
 
 /// A trait for sending lifecycle notifications from assets in the asset server.
@@ -224,6 +382,14 @@ pub trait AssetLifecycle: Downcast + Send + Sync + 'static {
 }
 impl_downcast!(AssetLifecycle);
 
+/// The [`AssetLifecycle`] of the asset types registered in the asset server, which can also
+/// alias duplicate assets.
+pub(crate) trait ServerAssetLifecycle: AssetLifecycle {
+	/// Notifies the asset server that an asset is a duplicate of another asset.
+	fn alias_asset(&self, id: HandleId, original: HandleId, version: usize);
+}
+impl_downcast!(ServerAssetLifecycle);
+
 impl<T: AssetDynamic> AssetLifecycle for AssetLifecycleChannel<T> {
 	fn create_asset(&self, id: HandleId, asset: Box<dyn AssetDynamic>, version: usize) {
 		if let Ok(asset) = asset.downcast::<T>() {
@@ -251,6 +417,19 @@ impl<T: AssetDynamic> AssetLifecycle for AssetLifecycleChannel<T> {
 	}
 }
 
+impl<T: AssetDynamic> ServerAssetLifecycle for AssetLifecycleChannel<T> {
+	fn alias_asset(&self, id: HandleId, original: HandleId, version: usize) {
+		self
+			.sender
+			.send(AssetLifecycleEvent::Alias {
+				id,
+				original,
+				version,
+			})
+			.unwrap();
+	}
+}
+
 impl<T> Default for AssetLifecycleChannel<T> {
 	fn default() -> Self {
 		let (sender, receiver) = crossbeam_channel::unbounded();
@@ -263,5 +442,6 @@ pub fn update_asset_storage_system<T: Asset + AssetDynamic>(
 	asset_server: Res<AssetServer>,
 	assets: ResMut<Assets<T>>,
 ) {
+	asset_server.save_assets(&assets);
 	asset_server.update_asset_storage(assets);
 }
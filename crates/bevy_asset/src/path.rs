@@ -51,6 +51,13 @@ impl<'a> AssetPath<'a> {
 		&self.path
 	}
 
+	/// Gets the name of the asset source of the path, given by its `source://` scheme, or `None`
+	/// for the default source. See [`AssetSources`](crate::AssetSources).
+	#[inline]
+	pub fn source(&self) -> Option<&str> {
+		split_source(&self.path).0
+	}
+
 	/// Converts the borrowed path data to owned.
 	#[inline]
 	pub fn to_owned(&self) -> AssetPath<'static> {
@@ -114,6 +121,10 @@ impl<'a> From<Option<&'a str>> for LabelId {
 }
 
 impl AssetPathId {
+	pub(crate) fn new(source_path_id: SourcePathId, label_id: LabelId) -> Self {
+		AssetPathId(source_path_id, label_id)
+	}
+
 	/// Gets the id of the source path.
 	pub fn source_path_id(&self) -> SourcePathId {
 		self.0
@@ -125,6 +136,28 @@ impl AssetPathId {
 	}
 }
 
+/// Splits the `source://` scheme of `path` from the path inside of the source.
+///
+/// The `source:/` form is accepted too: joining a relative path to the parent of a file at the
+/// root of a source, like `mods://scene.gltf`, collapses the separators of the scheme. Single
+/// letter schemes aren't accepted in this form, as they are Windows drives.
+pub(crate) fn split_source(path: &Path) -> (Option<&str>, &Path) {
+	if let Some((source, rest)) = path
+		.to_str()
+		.and_then(|path| path.split_once(":/"))
+	{
+		let (rest, collapsed) = match rest.strip_prefix('/') {
+			Some(rest) => (rest, false),
+			None => (rest, true),
+		};
+		let is_drive = collapsed && source.len() == 1;
+		if !source.is_empty() && !source.contains('/') && !is_drive {
+			return (Some(source), Path::new(rest));
+		}
+	}
+	(None, path)
+}
+
 /// this hasher provides consistent results across runs
 pub(crate) fn get_hasher() -> AHasher {
 	AHasher::new_with_keys(42, 23)
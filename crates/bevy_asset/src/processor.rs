@@ -0,0 +1,803 @@
+use crate::{AssetIoError, AssetServerError, LoaderSettings, ProcessMeta, ProcessedInfo};
+use anyhow::Error;
+use bevy_utils::BoxedFuture;
+use serde::{
+	de::{DeserializeOwned, IgnoredAny},
+	Serialize,
+};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Processes asset sources ahead of time, so the work is not done every time they are loaded.
+///
+/// A processor is picked for an asset source by its `.meta` file, see [`ProcessMeta`]. The
+/// processed bytes are written to the processed asset folder under the same path as the source,
+/// and are loaded by the [`AssetLoader`](crate::AssetLoader) of the source's extension.
+///
+/// Processors are registered with [`AddAsset::add_asset_processor`](crate::AddAsset) and run
+/// according to the [`AssetProcessingMode`].
+pub trait AssetProcessor: Send + Sync + 'static {
+	/// The settings of the processor, read from the `.meta` file of the source.
+	type Settings: Serialize + DeserializeOwned + Default + Send + Sync + 'static;
+
+	/// Processes the bytes of the source at `path` into the bytes of the processed asset.
+	fn process<'a>(
+		&'a self,
+		bytes: &'a [u8],
+		settings: &'a Self::Settings,
+		path: &'a Path,
+	) -> BoxedFuture<'a, Result<Vec<u8>, Error>>;
+}
+
+/// An [`AssetProcessor`] with its settings type erased, which passes them around as RON.
+pub(crate) trait ErasedAssetProcessor: Send + Sync + 'static {
+	fn read_settings(&self, meta: Option<&str>) -> Result<String, ron::Error>;
+
+	fn process<'a>(
+		&'a self,
+		bytes: &'a [u8],
+		settings: &'a str,
+		path: &'a Path,
+	) -> BoxedFuture<'a, Result<Vec<u8>, Error>>;
+
+	fn write_meta(
+		&self,
+		settings: &str,
+		loader_settings: &dyn LoaderSettings,
+		processed: ProcessedInfo,
+	) -> Result<String, ron::Error>;
+}
+
+impl<P: AssetProcessor> ErasedAssetProcessor for P {
+	fn read_settings(&self, meta: Option<&str>) -> Result<String, ron::Error> {
+		let settings = match meta {
+			Some(meta) => ron::from_str::<ProcessMeta<P::Settings, IgnoredAny>>(meta)?.settings,
+			None => P::Settings::default(),
+		};
+		ron::to_string(&settings)
+	}
+
+	fn process<'a>(
+		&'a self,
+		bytes: &'a [u8],
+		settings: &'a str,
+		path: &'a Path,
+	) -> BoxedFuture<'a, Result<Vec<u8>, Error>> {
+		Box::pin(async move {
+			let settings: P::Settings = ron::from_str(settings)?;
+			AssetProcessor::process(self, bytes, &settings, path).await
+		})
+	}
+
+	fn write_meta(
+		&self,
+		settings: &str,
+		loader_settings: &dyn LoaderSettings,
+		processed: ProcessedInfo,
+	) -> Result<String, ron::Error> {
+		let meta = ProcessMeta::<P::Settings, _> {
+			processor: Some(std::any::type_name::<P>().to_string()),
+			settings: ron::from_str(settings)?,
+			loader_settings,
+			processed: Some(processed),
+		};
+		ron::ser::to_string_pretty(&meta, Default::default())
+	}
+}
+
+/// Errors that occur while processing asset sources.
+#[derive(Error, Debug)]
+pub enum AssetProcessError {
+	/// Encountered an error while reading an asset source or its `.meta` file.
+	#[error("encountered an error while reading an asset source: {0}")]
+	AssetIoError(#[from] AssetIoError),
+
+	/// The `.meta` file of an asset source could not be parsed.
+	#[error("invalid meta file for asset source {path:?}: {error}")]
+	InvalidMeta {
+		/// The path of the asset source.
+		path: PathBuf,
+		/// The parsing error.
+		error: ron::Error,
+	},
+
+	/// The processor named in the `.meta` file of an asset source was not registered.
+	#[error("no `AssetProcessor` named {processor} found for asset source {path:?}")]
+	MissingProcessor {
+		/// The path of the asset source.
+		path: PathBuf,
+		/// The processor named in the `.meta` file.
+		processor: String,
+	},
+
+	/// The processor returned an error.
+	#[error("encountered an error while processing asset source {path:?}: {error}")]
+	ProcessorError {
+		/// The path of the asset source.
+		path: PathBuf,
+		/// The error returned by the processor.
+		error: Error,
+	},
+
+	/// The processed asset could not be loaded to find its dependencies.
+	#[error("encountered an error while loading processed asset {path:?}: {error}")]
+	LoaderError {
+		/// The path of the asset source.
+		path: PathBuf,
+		/// The loading error.
+		error: AssetServerError,
+	},
+
+	/// The processed asset could not be written.
+	#[error("encountered an error while writing processed asset {path:?}: {error}")]
+	WriteError {
+		/// The path the processed asset was written to.
+		path: PathBuf,
+		/// The I/O error.
+		error: std::io::Error,
+	},
+}
+
+/// How the [`AssetPlugin`](crate::AssetPlugin) uses the [`AssetProcessor`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetProcessingMode {
+	/// Load the asset sources as they are, ignoring the processors of their `.meta` files.
+	Unprocessed,
+	/// Load the assets from the processed asset folder, without processing anything. This is meant
+	/// for release builds shipping the processed asset folder instead of the sources.
+	Processed,
+	/// Process the asset sources that changed at startup, then load the processed assets.
+	///
+	/// When [`AssetServerSettings::watch_for_changes`](crate::AssetServerSettings) is set, a
+	/// source is processed again when it or one of its dependencies changes.
+	ProcessOnStartup,
+	/// Process all the asset sources, then exit the app. This is meant to prepare the processed
+	/// asset folder of release builds, for example from a headless app.
+	ProcessAndExit,
+}
+
+/// Settings of asset processing, read by the [`AssetPlugin`](crate::AssetPlugin).
+#[derive(Debug, Clone)]
+pub struct AssetProcessorSettings {
+	/// How assets are processed and loaded.
+	pub mode: AssetProcessingMode,
+	/// The folder where processed assets are written to and loaded from, relative to the same
+	/// base path as [`AssetServerSettings::asset_folder`](crate::AssetServerSettings).
+	pub processed_asset_folder: String,
+}
+
+impl Default for AssetProcessorSettings {
+	fn default() -> Self {
+		Self {
+			mode: AssetProcessingMode::Unprocessed,
+			processed_asset_folder: "imported_assets".to_string(),
+		}
+	}
+}
+
+/// Returns the path of the `.meta` file of the asset source at `path`.
+pub fn meta_path(path: &Path) -> PathBuf {
+	let mut meta_path = path.as_os_str().to_owned();
+	meta_path.push(".meta");
+	PathBuf::from(meta_path)
+}
+
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+pub use source_processor::*;
+
+#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+mod source_processor {
+	use super::*;
+	use crate::{path::get_hasher, AssetIo, AssetServer};
+	use bevy_app::AppExit;
+	use bevy_ecs::{
+		event::EventWriter,
+		system::{Res, ResMut},
+	};
+	use bevy_log::{error, info};
+	use bevy_tasks::{IoTaskPool, Task};
+	use bevy_utils::{HashMap, HashSet};
+	use futures_lite::future;
+	use parking_lot::{Mutex, RwLock};
+	use std::{ffi::OsStr, fs, hash::Hasher, sync::Arc};
+
+	/// The folder of the processed asset folder caching the outputs of the processors.
+	pub const PROCESSED_ARTIFACTS_FOLDER: &str = ".artifacts";
+
+	/// Processes the asset sources of an [`AssetIo`] into a processed asset folder.
+	///
+	/// A source is only processed again when the source, its `.meta` file or one of its
+	/// dependencies changed since it was last processed. Sources without an [`AssetProcessor`]
+	/// are copied as is, so the processed asset folder contains every asset.
+	///
+	/// The outputs of the processors are cached in the [`PROCESSED_ARTIFACTS_FOLDER`] of the
+	/// destination, keyed by the hash of the source, its processor and their settings: a source
+	/// processed again with settings it was already processed with reuses the cached output.
+	///
+	/// This resource is added by the [`AssetPlugin`](crate::AssetPlugin) when the
+	/// [`AssetProcessingMode`] processes assets.
+	#[derive(Clone)]
+	pub struct AssetSourceProcessor {
+		server: AssetServer,
+		source_io: Arc<dyn AssetIo>,
+		destination: PathBuf,
+		dependencies: Arc<RwLock<HashMap<PathBuf, Vec<PathBuf>>>>,
+	}
+
+	/// The full hashes of the sources processed during a single run, `None` while processing.
+	#[derive(Default)]
+	struct ProcessRun {
+		hashes: Mutex<HashMap<PathBuf, Option<u64>>>,
+	}
+
+	impl AssetSourceProcessor {
+		/// Creates a processor of the sources of `source_io`, writing to the `destination` folder.
+		///
+		/// The loaders and processors of `server` are used.
+		pub fn new(
+			server: AssetServer,
+			source_io: Box<dyn AssetIo>,
+			destination: impl Into<PathBuf>,
+		) -> Self {
+			Self {
+				server,
+				source_io: source_io.into(),
+				destination: destination.into(),
+				dependencies: Default::default(),
+			}
+		}
+
+		/// The [`AssetIo`] of the asset sources.
+		pub fn source_io(&self) -> &dyn AssetIo {
+			&*self.source_io
+		}
+
+		/// The folder processed assets are written to.
+		pub fn destination(&self) -> &Path {
+			&self.destination
+		}
+
+		/// Processes all the asset sources that changed, returning the errors that occurred once
+		/// done.
+		pub fn process_all(&self) -> BoxedFuture<'static, Vec<AssetProcessError>> {
+			let processor = self.clone();
+			Box::pin(async move {
+				let mut paths = Vec::new();
+				if let Err(err) = processor.collect_sources(Path::new(""), &mut paths) {
+					return vec![err];
+				}
+				processor.process_paths(paths).await
+			})
+		}
+
+		/// Processes the asset sources at `paths` if they changed, along with the sources
+		/// depending on them, returning the errors that occurred once done.
+		pub fn process_changed(
+			&self,
+			paths: impl IntoIterator<Item = PathBuf>,
+		) -> BoxedFuture<'static, Vec<AssetProcessError>> {
+			let mut changed: HashSet<PathBuf> = paths.into_iter().collect();
+			let dependencies = self.dependencies.read();
+			loop {
+				let dependents: Vec<PathBuf> = dependencies
+					.iter()
+					.filter(|(path, dependencies)| {
+						!changed.contains(*path)
+							&& dependencies
+								.iter()
+								.any(|dependency| changed.contains(dependency))
+					})
+					.map(|(path, _)| path.clone())
+					.collect();
+				if dependents.is_empty() {
+					break;
+				}
+				changed.extend(dependents);
+			}
+			drop(dependencies);
+
+			let mut paths: Vec<PathBuf> = changed.into_iter().collect();
+			paths.sort();
+			let processor = self.clone();
+			Box::pin(async move { processor.process_paths(paths).await })
+		}
+
+		async fn process_paths(&self, paths: Vec<PathBuf>) -> Vec<AssetProcessError> {
+			let run = ProcessRun::default();
+			let mut errors = Vec::new();
+			for path in paths {
+				if let Err(err) = self.process_path(&path, &run).await {
+					errors.push(err);
+				}
+			}
+			errors
+		}
+
+		fn collect_sources(
+			&self,
+			path: &Path,
+			paths: &mut Vec<PathBuf>,
+		) -> Result<(), AssetProcessError> {
+			let mut children: Vec<PathBuf> = self.source_io.read_directory(path)?.collect();
+			children.sort();
+			for child in children {
+				if self.source_io.is_dir(&child) {
+					self.collect_sources(&child, paths)?;
+				} else if child.extension() != Some(OsStr::new("meta")) {
+					paths.push(child);
+				}
+			}
+			Ok(())
+		}
+
+		fn process_path<'a>(
+			&'a self,
+			path: &'a Path,
+			run: &'a ProcessRun,
+		) -> BoxedFuture<'a, Result<u64, AssetProcessError>> {
+			Box::pin(async move {
+				match run.hashes.lock().get(path) {
+					Some(Some(full_hash)) => return Ok(*full_hash),
+					// a dependency cycle, the hash of the source being processed isn't known yet
+					Some(None) => return Ok(0),
+					None => {},
+				}
+				run.hashes.lock().insert(path.to_owned(), None);
+				let result = self.process_source(path, run).await;
+				run
+					.hashes
+					.lock()
+					.insert(path.to_owned(), result.as_ref().ok().copied());
+				result
+			})
+		}
+
+		async fn process_source(
+			&self,
+			path: &Path,
+			run: &ProcessRun,
+		) -> Result<u64, AssetProcessError> {
+			let bytes = self.source_io.load_path(path).await?;
+			let meta_path = meta_path(path);
+			let meta = match self.source_io.load_path(&meta_path).await {
+				Ok(meta) => Some(String::from_utf8_lossy(&meta).into_owned()),
+				Err(AssetIoError::NotFound(_)) => None,
+				Err(err) => return Err(err.into()),
+			};
+			let invalid_meta = |error| AssetProcessError::InvalidMeta {
+				path: path.to_owned(),
+				error,
+			};
+			let processor_name = match &meta {
+				Some(meta) => {
+					ron::from_str::<ProcessMeta<IgnoredAny, IgnoredAny>>(meta)
+						.map_err(invalid_meta)?
+						.processor
+				},
+				None => None,
+			};
+			let processor = match &processor_name {
+				Some(name) => Some(self.server.get_processor(name).ok_or_else(|| {
+					AssetProcessError::MissingProcessor {
+						path: path.to_owned(),
+						processor: name.clone(),
+					}
+				})?),
+				None => None,
+			};
+			let settings = match &processor {
+				Some(processor) => processor
+					.read_settings(meta.as_deref())
+					.map_err(invalid_meta)?,
+				None => String::new(),
+			};
+			let loader = self.server.get_path_asset_loader(path).ok();
+			let loader_settings: Arc<dyn LoaderSettings> = match &loader {
+				Some(loader) => loader
+					.read_settings(meta.as_deref())
+					.map_err(invalid_meta)?,
+				None => Arc::new(()),
+			};
+			let loader_settings_ron = ron::to_string(&*loader_settings).map_err(invalid_meta)?;
+
+			let mut hasher = get_hasher();
+			hasher.write(&bytes);
+			hasher.write(
+				processor_name
+					.as_deref()
+					.unwrap_or_default()
+					.as_bytes(),
+			);
+			hasher.write(settings.as_bytes());
+			hasher.write(loader_settings_ron.as_bytes());
+			let hash = hasher.finish();
+
+			let output_path = self.destination.join(path);
+			let output_meta_path = self.destination.join(&meta_path);
+			let previous = fs::read_to_string(&output_meta_path)
+				.ok()
+				.and_then(|meta| ron::from_str::<ProcessMeta<IgnoredAny, IgnoredAny>>(&meta).ok())
+				.and_then(|meta| meta.processed);
+			if let Some(previous) = previous {
+				if previous.hash == hash && output_path.is_file() {
+					let full_hash = self.full_hash(path, &previous, run).await?;
+					if full_hash == previous.full_hash {
+						return Ok(full_hash);
+					}
+				}
+			}
+
+			let processed = match &processor {
+				Some(processor) => {
+					let artifact_path = self.artifact_path(hash);
+					match fs::read(&artifact_path) {
+						Ok(processed) => processed,
+						Err(_) => {
+							let processed = processor
+								.process(&bytes, &settings, path)
+								.await
+								.map_err(|error| AssetProcessError::ProcessorError {
+									path: path.to_owned(),
+									error,
+								})?;
+							write(&artifact_path, &processed)?;
+							processed
+						},
+					}
+				},
+				None => bytes,
+			};
+			let source_meta = match &loader {
+				Some(loader) => Some(
+					self
+						.server
+						.load_source_meta(
+							&**loader,
+							loader_settings.clone(),
+							path,
+							&processed,
+							&*self.source_io,
+						)
+						.await
+						.map_err(|error| AssetProcessError::LoaderError {
+							path: path.to_owned(),
+							error,
+						})?,
+				),
+				None => None,
+			};
+			let mut info = ProcessedInfo {
+				hash,
+				full_hash: 0,
+				meta: source_meta,
+			};
+			info.full_hash = self.full_hash(path, &info, run).await?;
+			let full_hash = info.full_hash;
+
+			let output_meta = match &processor {
+				Some(processor) => processor.write_meta(&settings, &*loader_settings, info),
+				None => {
+					let meta = ProcessMeta {
+						processor: None,
+						settings: (),
+						loader_settings: &*loader_settings,
+						processed: Some(info),
+					};
+					ron::ser::to_string_pretty(&meta, Default::default())
+				},
+			}
+			.map_err(invalid_meta)?;
+			write(&output_path, &processed)?;
+			write(&output_meta_path, output_meta.as_bytes())?;
+			info!("processed asset {:?}", path);
+			Ok(full_hash)
+		}
+
+		/// Combines the hash of a processed source with the full hashes of its dependencies,
+		/// processing them if needed.
+		async fn full_hash(
+			&self,
+			path: &Path,
+			info: &ProcessedInfo,
+			run: &ProcessRun,
+		) -> Result<u64, AssetProcessError> {
+			let dependencies = info.dependencies();
+			let mut hasher = get_hasher();
+			hasher.write_u64(info.hash);
+			for dependency in &dependencies {
+				hasher.write_u64(self.process_path(dependency, run).await?);
+			}
+			self
+				.dependencies
+				.write()
+				.insert(path.to_owned(), dependencies);
+			Ok(hasher.finish())
+		}
+
+		/// The path of the cached output of a processor, for a source of the given hash.
+		fn artifact_path(&self, hash: u64) -> PathBuf {
+			self
+				.destination
+				.join(PROCESSED_ARTIFACTS_FOLDER)
+				.join(format!("{:016x}", hash))
+		}
+	}
+
+	fn write(path: &Path, bytes: &[u8]) -> Result<(), AssetProcessError> {
+		let write_error = |error| AssetProcessError::WriteError {
+			path: path.to_owned(),
+			error,
+		};
+		if let Some(parent) = path.parent() {
+			fs::create_dir_all(parent).map_err(write_error)?;
+		}
+		fs::write(path, bytes).map_err(write_error)
+	}
+
+	/// The processing of the asset sources running in the background, started by the systems of
+	/// the [`AssetPlugin`](crate::AssetPlugin).
+	pub struct AssetProcessingTask {
+		task: Option<Task<Vec<AssetProcessError>>>,
+		/// The sources changed while processing, processed once the running task is done.
+		changed: Vec<PathBuf>,
+		/// Closed once the sources are processed at startup, so the asset server loads them.
+		startup_processing: Option<async_channel::Sender<()>>,
+		exit_when_done: bool,
+	}
+
+	impl AssetProcessingTask {
+		/// Creates the task resource, making the loads of `asset_server` wait until the sources
+		/// are processed at startup. The app exits once they are if `exit_when_done` is set.
+		pub(crate) fn new(asset_server: &AssetServer, exit_when_done: bool) -> Self {
+			let (sender, receiver) = async_channel::bounded(1);
+			asset_server.wait_for_processing(receiver);
+			Self {
+				task: None,
+				changed: Vec::new(),
+				startup_processing: Some(sender),
+				exit_when_done,
+			}
+		}
+
+		/// Returns `true` while sources are being processed.
+		pub fn is_processing(&self) -> bool {
+			self.task.is_some() || self.startup_processing.is_some()
+		}
+	}
+
+	/// Starts processing all the asset sources that changed in the background, added at startup
+	/// by the [`AssetPlugin`](crate::AssetPlugin).
+	pub fn process_assets_system(
+		processor: Res<AssetSourceProcessor>,
+		mut processing: ResMut<AssetProcessingTask>,
+	) {
+		let process_all = processor.process_all();
+		// the loads wait until the sender is dropped with the task
+		let startup_processing = processing.startup_processing.take();
+		processing.task = Some(IoTaskPool::get().spawn(async move {
+			let errors = process_all.await;
+			drop(startup_processing);
+			errors
+		}));
+	}
+
+	/// Reports the errors of the background processing once it is done, then processes the
+	/// sources changed meanwhile. In [`AssetProcessingMode::ProcessAndExit`], exits the app once
+	/// all the sources are processed.
+	pub fn poll_asset_processing_system(
+		processor: Res<AssetSourceProcessor>,
+		mut processing: ResMut<AssetProcessingTask>,
+		mut app_exit_events: EventWriter<AppExit>,
+	) {
+		let errors = match &mut processing.task {
+			Some(task) => match future::block_on(future::poll_once(task)) {
+				Some(errors) => errors,
+				None => return,
+			},
+			None => return,
+		};
+		processing.task = None;
+		for err in errors {
+			error!("{}", err);
+		}
+		if processing.exit_when_done {
+			info!("All the assets were processed, exiting.");
+			app_exit_events.send(AppExit);
+		} else if !processing.changed.is_empty() {
+			let changed = std::mem::take(&mut processing.changed);
+			processing.task = Some(IoTaskPool::get().spawn(processor.process_changed(changed)));
+		}
+	}
+
+	/// Processes the asset sources modified on disk, and the ones depending on them, in the
+	/// background.
+	#[cfg(feature = "filesystem_watcher")]
+	pub fn process_changed_assets_system(
+		processor: Res<AssetSourceProcessor>,
+		mut processing: ResMut<AssetProcessingTask>,
+	) {
+		processing.changed.extend(
+			processor
+				.source_io
+				.changed_paths()
+				.into_iter()
+				.map(|path| match path.extension() {
+					Some(extension) if extension == "meta" => path.with_extension(""),
+					_ => path,
+				}),
+		);
+		if processing.changed.is_empty() || processing.is_processing() {
+			return;
+		}
+		let changed = std::mem::take(&mut processing.changed);
+		processing.task = Some(IoTaskPool::get().spawn(processor.process_changed(changed)));
+	}
+}
+
+#[cfg(all(test, not(target_arch = "wasm32"), not(target_os = "android")))]
+mod tests {
+	use super::*;
+	use crate::{AssetServer, FileAssetIo, LoadContext, LoadedAsset};
+	use bevy_reflect::TypeUuid;
+	use futures_lite::future;
+	use serde::Deserialize;
+	use std::sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	};
+
+	#[derive(Debug, TypeUuid)]
+	#[uuid = "6ec6c7d8-1d62-4cb4-8a3b-4a2db2f5d6b0"]
+	struct Text;
+
+	/// Loads `.txt` files, where each line starting with `dep:` is a dependency.
+	struct TextLoader;
+
+	impl crate::AssetLoader for TextLoader {
+		type Settings = ();
+
+		fn load<'a>(
+			&'a self,
+			bytes: &'a [u8],
+			load_context: &'a mut LoadContext,
+		) -> BoxedFuture<'a, Result<(), Error>> {
+			Box::pin(async move {
+				let mut asset = LoadedAsset::from(Text);
+				for line in std::str::from_utf8(bytes)?.lines() {
+					if let Some(dependency) = line.strip_prefix("dep:") {
+						asset.add_dependency(dependency.into());
+					}
+				}
+				load_context.set_default_asset(asset);
+				Ok(())
+			})
+		}
+
+		fn extensions(&self) -> &[&str] {
+			&["txt"]
+		}
+	}
+
+	#[derive(Default, Serialize, Deserialize)]
+	struct PrefixSettings {
+		prefix: String,
+	}
+
+	/// Prefixes text, counting how many times it ran.
+	#[derive(Default)]
+	struct Prefix(Arc<AtomicUsize>);
+
+	impl AssetProcessor for Prefix {
+		type Settings = PrefixSettings;
+
+		fn process<'a>(
+			&'a self,
+			bytes: &'a [u8],
+			settings: &'a Self::Settings,
+			_path: &'a Path,
+		) -> BoxedFuture<'a, Result<Vec<u8>, Error>> {
+			Box::pin(async move {
+				self.0.fetch_add(1, Ordering::Relaxed);
+				let mut text = settings.prefix.clone();
+				text.push_str(std::str::from_utf8(bytes)?);
+				Ok(text.into_bytes())
+			})
+		}
+	}
+
+	#[test]
+	fn process_with_cache_and_dependencies() {
+		let source = tempfile::tempdir().unwrap();
+		let destination = tempfile::tempdir().unwrap();
+		let write_source = |path: &str, contents: &str| {
+			std::fs::write(source.path().join(path), contents).unwrap();
+		};
+		write_source("a.txt", "a\ndep:b.txt");
+		write_source(
+			"a.txt.meta",
+			&format!(
+				"(processor: Some(\"{}\"), settings: (prefix: \"!\"))",
+				std::any::type_name::<Prefix>()
+			),
+		);
+		write_source("b.txt", "b");
+		write_source("c.bin", "c");
+
+		let server = AssetServer::from(FileAssetIo::new(destination.path(), false));
+		server.add_loader(TextLoader);
+		let prefix_processor = Prefix::default();
+		let processed_count = prefix_processor.0.clone();
+		server.add_processor(prefix_processor);
+		let processor = AssetSourceProcessor::new(
+			server.clone(),
+			Box::new(FileAssetIo::new(source.path(), false)),
+			destination.path(),
+		);
+
+		assert!(future::block_on(processor.process_all()).is_empty());
+		let read = |path: &str| std::fs::read_to_string(destination.path().join(path)).unwrap();
+		assert_eq!(read("a.txt"), "!a\ndep:b.txt");
+		assert_eq!(read("b.txt"), "b");
+		assert_eq!(read("c.bin"), "c");
+		assert_eq!(processed_count.load(Ordering::Relaxed), 1);
+
+		// nothing changed
+		assert!(future::block_on(processor.process_all()).is_empty());
+		assert_eq!(processed_count.load(Ordering::Relaxed), 1);
+
+		// a dependency changed
+		write_source("b.txt", "b2");
+		assert!(future::block_on(processor.process_changed(vec![PathBuf::from("b.txt")])).is_empty());
+		assert_eq!(read("b.txt"), "b2");
+		// the output of `a.txt` only depends on its source and settings, so it is cached
+		assert_eq!(processed_count.load(Ordering::Relaxed), 1);
+
+		// the settings changed
+		write_source(
+			"a.txt.meta",
+			&format!(
+				"(processor: Some(\"{}\"), settings: (prefix: \"?\"))",
+				std::any::type_name::<Prefix>()
+			),
+		);
+		assert!(future::block_on(processor.process_all()).is_empty());
+		assert_eq!(read("a.txt"), "?a\ndep:b.txt");
+		assert_eq!(processed_count.load(Ordering::Relaxed), 2);
+
+		// the output cached for the previous settings is reused
+		write_source(
+			"a.txt.meta",
+			&format!(
+				"(processor: Some(\"{}\"), settings: (prefix: \"!\"))",
+				std::any::type_name::<Prefix>()
+			),
+		);
+		assert!(future::block_on(processor.process_all()).is_empty());
+		assert_eq!(read("a.txt"), "!a\ndep:b.txt");
+		assert_eq!(processed_count.load(Ordering::Relaxed), 2);
+	}
+
+	#[test]
+	fn missing_processor() {
+		let source = tempfile::tempdir().unwrap();
+		let destination = tempfile::tempdir().unwrap();
+		std::fs::write(source.path().join("a.txt"), "a").unwrap();
+		std::fs::write(
+			source.path().join("a.txt.meta"),
+			"(processor: Some(\"Missing\"))",
+		)
+		.unwrap();
+
+		let server = AssetServer::from(FileAssetIo::new(destination.path(), false));
+		let processor = AssetSourceProcessor::new(
+			server,
+			Box::new(FileAssetIo::new(source.path(), false)),
+			destination.path(),
+		);
+		let errors = future::block_on(processor.process_all());
+		assert!(matches!(
+			errors.as_slice(),
+			[AssetProcessError::MissingProcessor { .. }]
+		));
+	}
+}
@@ -0,0 +1,46 @@
+use crate::{Asset, AssetDynamic};
+use anyhow::Error;
+use bevy_reflect::TypeUuid;
+use bevy_utils::Uuid;
+
+/// A saver writing an asset back to an asset source, the counterpart of an
+/// [`AssetLoader`](crate::AssetLoader).
+///
+/// Savers are used by [`AssetServer::save`](crate::AssetServer::save), which picks the saver of
+/// the asset type by the extension of the path the asset is saved to.
+pub trait AssetSaver: Send + Sync + 'static {
+	/// The type of the assets saved.
+	type Asset: Asset;
+
+	/// Serializes the asset into the bytes of an asset source.
+	fn save(&self, asset: &Self::Asset) -> Result<Vec<u8>, Error>;
+
+	/// Returns a list of extensions supported by this asset saver, without the preceding dot.
+	fn extensions(&self) -> &[&str];
+}
+
+/// An [`AssetSaver`] with its asset type erased.
+pub(crate) trait ErasedAssetSaver: Send + Sync + 'static {
+	fn save(&self, asset: &dyn AssetDynamic) -> Result<Vec<u8>, Error>;
+
+	fn extensions(&self) -> &[&str];
+
+	fn asset_type_uuid(&self) -> Uuid;
+}
+
+impl<S: AssetSaver> ErasedAssetSaver for S {
+	fn save(&self, asset: &dyn AssetDynamic) -> Result<Vec<u8>, Error> {
+		let asset = asset
+			.downcast_ref::<S::Asset>()
+			.expect("savers are only given assets of their type");
+		AssetSaver::save(self, asset)
+	}
+
+	fn extensions(&self) -> &[&str] {
+		AssetSaver::extensions(self)
+	}
+
+	fn asset_type_uuid(&self) -> Uuid {
+		S::Asset::TYPE_UUID
+	}
+}
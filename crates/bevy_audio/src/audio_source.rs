@@ -30,6 +30,8 @@ impl AsRef<[u8]> for AudioSource {
 pub struct AudioLoader;
 
 impl AssetLoader for AudioLoader {
+	type Settings = ();
+
 	fn load(&self, bytes: &[u8], load_context: &mut LoadContext) -> BoxedFuture<Result<()>> {
 		load_context.set_default_asset(LoadedAsset::from(AudioSource {
 			bytes: bytes.into(),
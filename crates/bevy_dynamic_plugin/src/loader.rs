@@ -1,34 +1,390 @@
 use libloading::{Library, Symbol};
+use std::{
+	any::TypeId,
+	ffi::{CStr, OsStr},
+	path::{Path, PathBuf},
+};
+use thiserror::Error;
 
-use bevy_app::{App, CreatePlugin, Plugin};
+use bevy_app::{App, CreatePlugin, Plugin, PluginBuildFingerprint, PluginCrateName, PluginError};
+use bevy_ecs::component::{ComponentInfo, ForgetComponentsError};
+use bevy_reflect::TypeRegistryArc;
+use bevy_utils::HashSet;
+
+/// An error loading a dynamic plugin.
+#[derive(Debug, Error)]
+pub enum DynamicPluginLoadError {
+	#[error("cannot load library for dynamic plugin: {0}")]
+	Library(#[source] libloading::Error),
+	#[error("dynamic plugin library doesn't export `{symbol}`, consider deriving `DynamicPlugin` for the plugin: {source}")]
+	MissingSymbol {
+		symbol: &'static str,
+		#[source]
+		source: libloading::Error,
+	},
+	#[error("dynamic plugin was built for `{found}` but the app for `{expected}`")]
+	IncompatibleBuild { expected: String, found: String },
+	#[error("cannot add dynamic plugin: {0}")]
+	Plugin(#[from] PluginError),
+	#[error(transparent)]
+	Unload(#[from] DynamicPluginUnloadError),
+}
+
+/// An error unloading a dynamic plugin, which stays loaded and is returned with the error.
+#[derive(Debug, Error)]
+#[error("cannot unload dynamic plugin `{}`: {source}", .plugin.crate_name)]
+pub struct DynamicPluginUnloadError {
+	pub plugin: Box<LoadedPlugin>,
+	#[source]
+	pub source: ForgetComponentsError,
+}
 
 /// Dynamically links a plugin at the given path. The plugin must export a function with the
-/// [`CreatePlugin`] signature named `_bevy_create_plugin`.
+/// [`CreatePlugin`] signature named `_bevy_create_plugin`, and a function with the
+/// [`PluginBuildFingerprint`] signature named `_bevy_plugin_build_fingerprint`.
+///
+/// The fingerprint of the plugin is checked against `build_fingerprint` before creating it, which
+/// is the `bevy::BUILD_FINGERPRINT` of the app.
 ///
 /// # Safety
 ///
-/// The specified plugin must be linked against the exact same libbevy.so as this program.
-/// In addition the `_bevy_create_plugin` symbol must not be manually created, but instead created
-/// by deriving `DynamicPlugin` on a unit struct implementing [`Plugin`].
-pub unsafe fn dynamically_load_plugin(path: &str) -> (Library, Box<dyn Plugin>) {
-	let lib = Library::new(path).unwrap();
-	let func: Symbol<CreatePlugin> = lib.get(b"_bevy_create_plugin").unwrap();
-	let plugin = Box::from_raw(func());
-	(lib, plugin)
+/// The specified plugin must be linked against the exact same libbevy.so as this program, which
+/// the fingerprint can't entirely guarantee, for example if Bevy was patched. In addition the
+/// exported symbols must not be manually created, but instead created by deriving `DynamicPlugin`
+/// on a unit struct implementing [`Plugin`].
+pub unsafe fn dynamically_load_plugin<P: AsRef<OsStr>>(
+	path: P,
+	build_fingerprint: &str,
+) -> Result<(Library, Box<dyn Plugin>), DynamicPluginLoadError> {
+	let lib = Library::new(path).map_err(DynamicPluginLoadError::Library)?;
+	check_build_fingerprint(&lib, build_fingerprint)?;
+	let plugin = {
+		let func: Symbol<CreatePlugin> = get_symbol(&lib, "_bevy_create_plugin")?;
+		Box::from_raw(func())
+	};
+	Ok((lib, plugin))
+}
+
+unsafe fn get_symbol<'lib, T>(
+	lib: &'lib Library,
+	symbol: &'static str,
+) -> Result<Symbol<'lib, T>, DynamicPluginLoadError> {
+	lib
+		.get(symbol.as_bytes())
+		.map_err(|source| DynamicPluginLoadError::MissingSymbol { symbol, source })
+}
+
+unsafe fn check_build_fingerprint(
+	lib: &Library,
+	build_fingerprint: &str,
+) -> Result<(), DynamicPluginLoadError> {
+	let fingerprint: Symbol<PluginBuildFingerprint> =
+		get_symbol(lib, "_bevy_plugin_build_fingerprint")?;
+	let found = CStr::from_ptr(fingerprint()).to_string_lossy();
+	let expected = build_fingerprint.trim_end_matches('\0');
+	if found != expected {
+		return Err(DynamicPluginLoadError::IncompatibleBuild {
+			expected: expected.to_string(),
+			found: found.into_owned(),
+		});
+	}
+	Ok(())
+}
+
+unsafe fn crate_name(lib: &Library) -> Result<String, DynamicPluginLoadError> {
+	let crate_name: Symbol<PluginCrateName> = get_symbol(lib, "_bevy_plugin_crate_name")?;
+	Ok(
+		CStr::from_ptr(crate_name())
+			.to_string_lossy()
+			.into_owned(),
+	)
+}
+
+/// A dynamic plugin added to an [`App`] by [`DynamicPluginExt::load_plugin`], needed to
+/// [unload](DynamicPluginExt::unload_plugin) it.
+///
+/// Dropping it keeps the library of the plugin loaded.
+#[derive(Debug)]
+pub struct LoadedPlugin {
+	library: Option<Library>,
+	path: PathBuf,
+	build_fingerprint: String,
+	crate_name: String,
+	/// The component, resource and reflected types registered while the plugin was added.
+	type_ids: HashSet<TypeId>,
+}
+
+impl LoadedPlugin {
+	/// The path the plugin was loaded from.
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	/// The name of the crate of the plugin.
+	pub fn crate_name(&self) -> &str {
+		&self.crate_name
+	}
+
+	/// Returns `true` if the type `type_id` was registered while the plugin was added, or if its
+	/// name mentions an item of the crate of the plugin, for the types registered later.
+	fn owns_type(&self, type_id: Option<TypeId>, name: &str) -> bool {
+		matches!(type_id, Some(type_id) if self.type_ids.contains(&type_id)) || self.owns(name)
+	}
+
+	/// Returns `true` if the type or system name `name` mentions an item of the crate of the
+	/// plugin, such as `my_plugin::Score` or `bevy_ecs::event::Events<my_plugin::Scored>`.
+	fn owns(&self, name: &str) -> bool {
+		let prefix = format!("{}::", self.crate_name);
+		name.match_indices(&prefix).any(|(index, _)| {
+			!matches!(
+				name[..index].chars().next_back(),
+				Some(c) if c.is_alphanumeric() || c == '_'
+			)
+		})
+	}
+}
+
+impl Drop for LoadedPlugin {
+	fn drop(&mut self) {
+		// the code of the plugin may still be in use
+		if let Some(library) = self.library.take() {
+			std::mem::forget(library);
+		}
+	}
 }
 
 pub trait DynamicPluginExt {
+	/// Loads the dynamic plugin at `path` and adds it to the app, checking that it was built with
+	/// the `build_fingerprint` of the app, usually `bevy::BUILD_FINGERPRINT`.
+	///
 	/// # Safety
 	///
 	/// Same as [`dynamically_load_plugin`].
-	unsafe fn load_plugin(&mut self, path: &str) -> &mut Self;
+	unsafe fn load_plugin(
+		&mut self,
+		path: impl AsRef<Path>,
+		build_fingerprint: &str,
+	) -> Result<LoadedPlugin, DynamicPluginLoadError>;
+
+	/// Removes a dynamic plugin from the app, with the systems whose names mention its crate and
+	/// the reflected types and resources it owns, then unloads its library. Meant for development,
+	/// to [reload](Self::reload_plugin) a rebuilt plugin.
+	///
+	/// The plugin owns the types registered while it was added, and the types of its crate
+	/// registered afterwards, for example by its systems. Its component and resource types are
+	/// [forgotten](App::forget_components) by the world of the app and of its sub apps, so that a
+	/// rebuilt plugin registers them again. Nothing is unloaded if an entity still has one of the
+	/// components: it must be despawned or the component removed beforehand.
+	///
+	/// # Safety
+	///
+	/// Nothing from the plugin may remain in use once its library is unloaded: the plugin must not
+	/// have added run criteria, observers of other plugins or values from its crate to resources
+	/// of other crates.
+	unsafe fn unload_plugin(&mut self, plugin: LoadedPlugin) -> Result<(), DynamicPluginUnloadError>;
+
+	/// Unloads a dynamic plugin and loads it again from the same path, for example once it was
+	/// rebuilt. If the plugin can't be unloaded, it is returned with the error.
+	///
+	/// # Safety
+	///
+	/// Same as [`unload_plugin`](Self::unload_plugin) and [`load_plugin`](Self::load_plugin).
+	unsafe fn reload_plugin(
+		&mut self,
+		plugin: LoadedPlugin,
+	) -> Result<LoadedPlugin, DynamicPluginLoadError>;
 }
 
 impl DynamicPluginExt for App {
-	unsafe fn load_plugin(&mut self, path: &str) -> &mut Self {
-		let (lib, plugin) = dynamically_load_plugin(path);
-		std::mem::forget(lib); // Ensure that the library is not automatically unloaded
-		plugin.build(self);
-		self
+	unsafe fn load_plugin(
+		&mut self,
+		path: impl AsRef<Path>,
+		build_fingerprint: &str,
+	) -> Result<LoadedPlugin, DynamicPluginLoadError> {
+		let path = path.as_ref();
+		let (lib, plugin) = dynamically_load_plugin(path, build_fingerprint)?;
+		let crate_name = crate_name(&lib)?;
+		let registered_before = registered_type_ids(self);
+		self.add_boxed_plugin(plugin)?;
+		let mut type_ids = registered_type_ids(self);
+		type_ids.retain(|type_id| !registered_before.contains(type_id));
+		Ok(LoadedPlugin {
+			library: Some(lib),
+			path: path.to_owned(),
+			build_fingerprint: build_fingerprint.to_string(),
+			crate_name,
+			type_ids,
+		})
+	}
+
+	unsafe fn unload_plugin(
+		&mut self,
+		mut plugin: LoadedPlugin,
+	) -> Result<(), DynamicPluginUnloadError> {
+		// Everything using the code of the plugin is dropped before unloading its library.
+		if let Err(source) =
+			self.forget_components(|info: &ComponentInfo| plugin.owns_type(info.type_id(), info.name()))
+		{
+			return Err(DynamicPluginUnloadError {
+				plugin: Box::new(plugin),
+				source,
+			});
+		}
+		drop(self.remove_plugins(|added_plugin| plugin.owns(added_plugin.name())));
+		self.remove_systems(|name| plugin.owns(name));
+		if let Some(type_registry) = self
+			.world
+			.get_resource::<TypeRegistryArc>()
+			.cloned()
+		{
+			let mut type_registry = type_registry.write();
+			let type_ids: Vec<TypeId> = type_registry
+				.iter()
+				.filter(|registration| {
+					plugin.owns_type(Some(registration.type_id()), registration.type_name())
+				})
+				.map(|registration| registration.type_id())
+				.collect();
+			for type_id in type_ids {
+				type_registry.unregister(type_id);
+			}
+		}
+		drop(plugin.library.take());
+		Ok(())
+	}
+
+	unsafe fn reload_plugin(
+		&mut self,
+		plugin: LoadedPlugin,
+	) -> Result<LoadedPlugin, DynamicPluginLoadError> {
+		let path = plugin.path.clone();
+		let build_fingerprint = plugin.build_fingerprint.clone();
+		self.unload_plugin(plugin)?;
+		self.load_plugin(path, &build_fingerprint)
+	}
+}
+
+/// Returns the component, resource and reflected types registered in the app.
+fn registered_type_ids(app: &mut App) -> HashSet<TypeId> {
+	let mut type_ids = app.component_type_ids();
+	if let Some(type_registry) = app.world.get_resource::<TypeRegistryArc>() {
+		type_ids.extend(
+			type_registry
+				.read()
+				.iter()
+				.map(|registration| registration.type_id()),
+		);
+	}
+	type_ids
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy_ecs::prelude::*;
+	use bevy_reflect::Reflect;
+
+	#[derive(Component, Reflect, Default)]
+	struct Score(u32);
+
+	struct Scores(Vec<u32>);
+
+	fn record_scores(mut scores: ResMut<Scores>, query: Query<&Score>) {
+		query.for_each(|score| scores.0.push(score.0));
+	}
+
+	struct ScorePlugin;
+	impl Plugin for ScorePlugin {
+		fn build(&self, app: &mut App) {
+			app.insert_resource(Scores(Vec::new()));
+			app.register_type::<Score>();
+			app.add_system(record_scores);
+		}
+	}
+
+	/// Adds [`ScorePlugin`] as if it was dynamically loaded from a library of this crate.
+	fn load_score_plugin(app: &mut App) -> LoadedPlugin {
+		let registered_before = registered_type_ids(app);
+		app.add_plugin(ScorePlugin);
+		let mut type_ids = registered_type_ids(app);
+		type_ids.retain(|type_id| !registered_before.contains(type_id));
+		LoadedPlugin {
+			library: None,
+			path: PathBuf::from("missing/libscore_plugin.so"),
+			build_fingerprint: String::new(),
+			crate_name: env!("CARGO_CRATE_NAME").to_string(),
+			type_ids,
+		}
+	}
+
+	#[test]
+	fn owns() {
+		let plugin = LoadedPlugin {
+			library: None,
+			path: PathBuf::new(),
+			build_fingerprint: String::new(),
+			crate_name: "my_plugin".to_string(),
+			type_ids: [TypeId::of::<Score>()].into_iter().collect(),
+		};
+		assert!(plugin.owns("my_plugin::Score"));
+		assert!(plugin.owns("bevy_ecs::event::Events<my_plugin::Scored>"));
+		assert!(!plugin.owns("not_my_plugin::Score"));
+		assert!(!plugin.owns("bevy_ecs::event::Events<bevy_app::AppExit>"));
+		// the types registered while the plugin was added are owned whatever their name
+		assert!(plugin.owns_type(Some(TypeId::of::<Score>()), "renamed::Score"));
+		assert!(!plugin.owns_type(Some(TypeId::of::<Scores>()), "renamed::Scores"));
+	}
+
+	#[test]
+	fn unload_plugin() {
+		let mut app = App::new();
+		let plugin = load_score_plugin(&mut app);
+		let entity = app.world.spawn().insert(Score(1)).id();
+		app.update();
+
+		// SAFETY: the plugin has no library
+		let error = unsafe { app.unload_plugin(plugin) }.unwrap_err();
+		assert_eq!(
+			error.source,
+			ForgetComponentsError::InUse(std::any::type_name::<Score>().to_string())
+		);
+		assert!(app.is_plugin_added::<ScorePlugin>());
+		app.update();
+		assert_eq!(app.world.resource::<Scores>().0, [1, 1]);
+
+		app.world.despawn(entity);
+		// SAFETY: the plugin has no library
+		unsafe { app.unload_plugin(*error.plugin) }.unwrap();
+		assert!(!app.is_plugin_added::<ScorePlugin>());
+		assert!(!app.world.contains_resource::<Scores>());
+		assert_eq!(app.world.components().component_id::<Score>(), None);
+		assert!(app
+			.world
+			.resource::<TypeRegistryArc>()
+			.read()
+			.get(TypeId::of::<Score>())
+			.is_none());
+		// `record_scores` would panic without `Scores`
+		app.update();
+	}
+
+	#[test]
+	fn reload_plugin() {
+		let mut app = App::new();
+		let plugin = load_score_plugin(&mut app);
+		let entity = app.world.spawn().insert(Score(1)).id();
+
+		// SAFETY: the plugin has no library and its path doesn't exist
+		let plugin = match unsafe { app.reload_plugin(plugin) } {
+			Err(DynamicPluginLoadError::Unload(error)) => *error.plugin,
+			result => panic!("unexpected reload result {:?}", result.map(|_| ())),
+		};
+		assert!(app.is_plugin_added::<ScorePlugin>());
+
+		app.world.despawn(entity);
+		// SAFETY: the plugin has no library and its path doesn't exist
+		let result = unsafe { app.reload_plugin(plugin) };
+		assert!(matches!(result, Err(DynamicPluginLoadError::Library(_))));
+		assert!(!app.is_plugin_added::<ScorePlugin>());
 	}
 }
@@ -13,13 +13,25 @@ pub fn gilrs_event_startup_system(gilrs: NonSend<Gilrs>, mut events: EventWriter
 	}
 }
 
-pub fn gilrs_event_system(mut gilrs: NonSendMut<Gilrs>, mut events: EventWriter<GamepadEventRaw>) {
+pub fn gilrs_event_system(
+	mut gilrs: NonSendMut<Gilrs>,
+	mut events: EventWriter<GamepadEventRaw>,
+	#[cfg(feature = "bevy_ci_testing")] replayer: Option<
+		bevy_ecs::system::Res<bevy_app::InputReplayer>,
+	>,
+) {
 	while let Some(gilrs_event) = gilrs
 		.next_event()
 		.filter_ev(&axis_dpad_to_button, &mut gilrs)
 	{
 		gilrs.update(&gilrs_event);
 
+		// the recorded gamepad events are replayed instead, before the frame starts
+		#[cfg(feature = "bevy_ci_testing")]
+		if replayer.is_some() {
+			continue;
+		}
+
 		match gilrs_event.event {
 			EventType::Connected => {
 				events.send(GamepadEventRaw::new(
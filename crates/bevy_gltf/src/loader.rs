@@ -1,6 +1,7 @@
 use anyhow::Result;
 use bevy_asset::{
-	AssetIoError, AssetLoader, AssetPath, BoxedFuture, Handle, LoadContext, LoadedAsset,
+	AssetIoError, AssetLoader, AssetPath, AssetServerError, BoxedFuture, Handle, LoadContext,
+	LoadedAsset,
 };
 use bevy_core::Name;
 use bevy_core_pipeline::prelude::Camera3d;
@@ -37,6 +38,7 @@ use gltf::{
 	texture::{MagFilter, MinFilter},
 	Material, Node, Primitive,
 };
+use serde::{Deserialize, Serialize};
 use std::{collections::VecDeque, path::Path};
 use thiserror::Error;
 
@@ -65,6 +67,24 @@ pub enum GltfError {
 	MissingAnimationSampler(usize),
 	#[error("failed to generate tangents: {0}")]
 	GenerateTangentsError(#[from] bevy_render::mesh::GenerateTangentsError),
+	#[error("failed to get the loader settings: {0}")]
+	LoaderSettings(#[from] AssetServerError),
+}
+
+/// The settings a glTF file is loaded with by the [`GltfLoader`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GltfLoaderSettings {
+	/// Whether the animations of the file are loaded.
+	pub load_animations: bool,
+}
+
+impl Default for GltfLoaderSettings {
+	fn default() -> Self {
+		Self {
+			load_animations: true,
+		}
+	}
 }
 
 /// Loads glTF files with all of their data as their corresponding bevy representations.
@@ -73,6 +93,8 @@ pub struct GltfLoader {
 }
 
 impl AssetLoader for GltfLoader {
+	type Settings = GltfLoaderSettings;
+
 	fn load<'a>(
 		&'a self,
 		bytes: &'a [u8],
@@ -148,7 +170,10 @@ async fn load_gltf<'a, 'b>(
 		let mut animations = vec![];
 		let mut named_animations = HashMap::default();
 		let mut animation_roots = HashSet::default();
-		for animation in gltf.animations() {
+		let load_animations = load_context
+			.settings::<GltfLoaderSettings>()?
+			.load_animations;
+		for animation in gltf.animations().filter(|_| load_animations) {
 			let mut animation_clip = bevy_animation::AnimationClip::default();
 			for channel in animation.channels() {
 				match channel.sampler().interpolation() {
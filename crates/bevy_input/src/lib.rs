@@ -82,6 +82,16 @@ impl Plugin for InputPlugin {
 			CoreStage::PreUpdate,
 			touch_screen_input_system.label(InputSystem),
 		);
+
+		#[cfg(feature = "bevy_ci_testing")]
+		{
+			app.add_recorded_event::<KeyboardInput>();
+			app.add_recorded_event::<MouseButtonInput>();
+			app.add_recorded_event::<MouseMotion>();
+			app.add_recorded_event::<MouseWheel>();
+			app.add_recorded_event::<GamepadEventRaw>();
+			app.add_recorded_event::<TouchInput>();
+		}
 	}
 }
 
@@ -0,0 +1,30 @@
+use std::{env, process::Command};
+
+fn main() {
+	// The build fingerprint of dynamic plugins includes the compiler version.
+	let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+	let rustc_version = Command::new(rustc)
+		.arg("--version")
+		.output()
+		.ok()
+		.and_then(|output| String::from_utf8(output.stdout).ok())
+		.map(|version| version.trim().to_string())
+		.unwrap_or_else(|| "unknown rustc".to_string());
+	println!("cargo:rustc-env=BEVY_RUSTC_VERSION={}", rustc_version);
+
+	// The features of `bevy` are forwarded to this crate.
+	let mut features: Vec<String> = env::vars()
+		.filter_map(|(key, _)| {
+			key
+				.strip_prefix("CARGO_FEATURE_")
+				.map(str::to_lowercase)
+		})
+		.collect();
+	features.sort();
+	println!("cargo:rustc-env=BEVY_FEATURES={}", features.join(","));
+	println!(
+		"cargo:rustc-env=BEVY_TARGET={}",
+		env::var("TARGET").unwrap_or_default()
+	);
+	println!("cargo:rerun-if-changed=build.rs");
+}
@@ -5,6 +5,7 @@ use bevy_app::{PluginGroup, PluginGroupBuilder};
 /// * [`CorePlugin`](bevy_core::CorePlugin)
 /// * [`TimePlugin`](bevy_time::TimePlugin)
 /// * [`TransformPlugin`](bevy_transform::TransformPlugin)
+/// * [`TransformInterpolationPlugin`](bevy_transform::TransformInterpolationPlugin)
 /// * [`HierarchyPlugin`](bevy_hierarchy::HierarchyPlugin)
 /// * [`DiagnosticsPlugin`](bevy_diagnostic::DiagnosticsPlugin)
 /// * [`InputPlugin`](bevy_input::InputPlugin)
@@ -30,6 +31,7 @@ impl PluginGroup for DefaultPlugins {
 		group.init::<bevy_core::CorePlugin>();
 		group.init::<bevy_time::TimePlugin>();
 		group.init::<bevy_transform::TransformPlugin>();
+		group.init::<bevy_transform::TransformInterpolationPlugin>();
 		group.init::<bevy_hierarchy::HierarchyPlugin>();
 		group.init::<bevy_diagnostic::DiagnosticsPlugin>();
 		group.init::<bevy_input::InputPlugin>();
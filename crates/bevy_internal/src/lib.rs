@@ -7,6 +7,23 @@ pub mod prelude;
 mod default_plugins;
 pub use default_plugins::*;
 
+/// Identifies the build of Bevy, with its version, the version of the compiler, the target and
+/// the enabled features, as a NUL-terminated string.
+///
+/// Dynamically loaded plugins must have the same fingerprint as the app loading them, see
+/// `bevy::dynamic_plugin::dynamically_load_plugin`.
+pub const BUILD_FINGERPRINT: &str = concat!(
+	"bevy ",
+	env!("CARGO_PKG_VERSION"),
+	"; ",
+	env!("BEVY_RUSTC_VERSION"),
+	"; target ",
+	env!("BEVY_TARGET"),
+	"; features ",
+	env!("BEVY_FEATURES"),
+	"\0"
+);
+
 pub mod app {
 	//! Build bevy apps, create plugins, and read events.
 	pub use bevy_app::*;
@@ -132,6 +132,15 @@ impl Plugin for LogPlugin {
 		#[cfg(feature = "trace")]
 		let subscriber = subscriber.with(tracing_error::ErrorLayer::default());
 
+		// the errors are counted even if the CI testing configuration is applied after this plugin
+		#[cfg(feature = "bevy_ci_testing")]
+		let subscriber = subscriber.with(CiTestingErrorLayer(
+			app
+				.world
+				.get_resource_or_insert_with(bevy_app::CiTestingErrorCount::default)
+				.clone(),
+		));
+
 		#[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
 		{
 			#[cfg(feature = "tracing-chrome")]
@@ -196,3 +205,38 @@ impl Plugin for LogPlugin {
 		}
 	}
 }
+
+/// Counts the errors logged during a CI testing run, see
+/// [`CiTestingConfig::fail_on_error`](bevy_app::CiTestingConfig::fail_on_error).
+#[cfg(feature = "bevy_ci_testing")]
+struct CiTestingErrorLayer(bevy_app::CiTestingErrorCount);
+
+#[cfg(feature = "bevy_ci_testing")]
+impl<S: bevy_utils::tracing::Subscriber> tracing_subscriber::Layer<S> for CiTestingErrorLayer {
+	fn on_event(
+		&self,
+		event: &bevy_utils::tracing::Event<'_>,
+		_ctx: tracing_subscriber::layer::Context<'_, S>,
+	) {
+		if *event.metadata().level() == Level::ERROR {
+			self.0.increment();
+		}
+	}
+}
+
+#[cfg(all(test, feature = "bevy_ci_testing"))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn ci_testing_error_layer() {
+		let error_count = bevy_app::CiTestingErrorCount::default();
+		let subscriber = Registry::default().with(CiTestingErrorLayer(error_count.clone()));
+		bevy_utils::tracing::subscriber::with_default(subscriber, || {
+			warn!("not counted");
+			error!("counted");
+			error!("counted");
+		});
+		assert_eq!(error_count.get(), 2);
+	}
+}
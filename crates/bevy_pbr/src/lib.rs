@@ -37,8 +37,8 @@ pub mod draw_3d_graph {
 	}
 }
 
-use bevy_app::prelude::*;
-use bevy_asset::{load_internal_asset, Assets, Handle, HandleUntyped};
+use bevy_app::{prelude::*, PluginId};
+use bevy_asset::{load_internal_asset, AssetPlugin, Assets, Handle, HandleUntyped};
 use bevy_ecs::prelude::*;
 use bevy_reflect::TypeUuid;
 use bevy_render::{
@@ -49,7 +49,7 @@ use bevy_render::{
 	render_phase::{sort_phase_system, AddRenderCommand, DrawFunctions},
 	render_resource::{Shader, SpecializedMeshPipelines},
 	view::VisibilitySystems,
-	RenderApp, RenderStage,
+	RenderApp, RenderPlugin, RenderStage,
 };
 use bevy_transform::TransformSystem;
 
@@ -77,6 +77,13 @@ pub const SHADOW_SHADER_HANDLE: HandleUntyped =
 pub struct PbrPlugin;
 
 impl Plugin for PbrPlugin {
+	fn dependencies(&self) -> Vec<PluginId> {
+		vec![
+			PluginId::of::<AssetPlugin>(),
+			PluginId::of::<RenderPlugin>(),
+		]
+	}
+
 	fn build(&self, app: &mut App) {
 		load_internal_asset!(
 			app,
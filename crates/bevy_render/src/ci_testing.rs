@@ -0,0 +1,222 @@
+use crate::{
+	renderer::{RenderDevice, RenderQueue},
+	Extract,
+};
+use bevy_app::{CiTestingConfig, CiTestingFrameCapture};
+use bevy_ecs::prelude::*;
+use bevy_log::{error, info};
+use std::num::NonZeroU32;
+use wgpu::{AdapterInfo, Backend, TextureFormat, TextureUsages};
+
+/// The paths of the captures of the current frame requested by the CI testing configuration.
+pub(crate) struct FrameCaptures {
+	paths: Vec<String>,
+	backend: Backend,
+	/// Captures are configured and the window surfaces of the backend can be copied from.
+	enabled: bool,
+}
+
+impl FrameCaptures {
+	pub(crate) fn new(world: &World) -> Self {
+		let configured = matches!(
+			world.get_resource::<CiTestingConfig>(),
+			Some(config) if !config.capture_frames.is_empty()
+		);
+		let backend = world
+			.get_resource::<AdapterInfo>()
+			.map_or(Backend::Empty, |info| info.backend);
+		FrameCaptures {
+			paths: Vec::new(),
+			backend,
+			enabled: configured && surfaces_support_copy(backend),
+		}
+	}
+
+	/// The usages of the window surfaces: frames are captured by copying from the surface, which
+	/// needs the [`COPY_SRC`](TextureUsages::COPY_SRC) usage, only requested when captures are
+	/// enabled.
+	pub(crate) fn surface_usages(&self) -> TextureUsages {
+		if self.enabled {
+			TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC
+		} else {
+			TextureUsages::RENDER_ATTACHMENT
+		}
+	}
+}
+
+/// Returns `true` if the window surfaces of `backend` support the
+/// [`COPY_SRC`](TextureUsages::COPY_SRC) usage, as reported by the surface capabilities of
+/// wgpu-hal, which wgpu doesn't expose: always for DX12, for the common Vulkan drivers, and never
+/// for Metal and GL.
+fn surfaces_support_copy(backend: Backend) -> bool {
+	matches!(backend, Backend::Vulkan | Backend::Dx12)
+}
+
+pub(crate) fn extract_frame_captures(
+	mut frame_captures: ResMut<FrameCaptures>,
+	mut captures: Extract<EventReader<CiTestingFrameCapture>>,
+) {
+	for capture in captures.iter() {
+		if frame_captures.enabled {
+			frame_captures.paths.push(capture.path.clone());
+		} else {
+			error!(
+				"Cannot capture frame to {}: the window surfaces of the {:?} backend can't be copied",
+				capture.path, frame_captures.backend
+			);
+		}
+	}
+}
+
+/// The pending frame captures, taken from the render world before presenting the frame.
+pub(crate) struct PendingFrameCaptures {
+	paths: Vec<String>,
+	render_device: RenderDevice,
+	render_queue: RenderQueue,
+}
+
+impl PendingFrameCaptures {
+	pub(crate) fn take(world: &mut World) -> Option<Self> {
+		let paths = std::mem::take(&mut world.get_resource_mut::<FrameCaptures>()?.paths);
+		if paths.is_empty() {
+			return None;
+		}
+		Some(PendingFrameCaptures {
+			paths,
+			render_device: world.resource::<RenderDevice>().clone(),
+			render_queue: world.resource::<RenderQueue>().clone(),
+		})
+	}
+
+	/// Copies `texture` into a buffer, waits for the copy and saves it as an image at every path.
+	///
+	/// The texture must have the [`COPY_SRC`](wgpu::TextureUsages::COPY_SRC) usage.
+	pub(crate) fn capture(
+		&self,
+		texture: &wgpu::Texture,
+		format: TextureFormat,
+		width: u32,
+		height: u32,
+	) {
+		let row_bytes = width as usize * 4;
+		let padded_row_bytes = RenderDevice::align_copy_bytes_per_row(row_bytes);
+		let buffer = self
+			.render_device
+			.create_buffer(&wgpu::BufferDescriptor {
+				label: Some("frame_capture_buffer"),
+				size: (padded_row_bytes * height as usize) as u64,
+				usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+				mapped_at_creation: false,
+			});
+		let mut encoder = self
+			.render_device
+			.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+				label: Some("frame_capture_encoder"),
+			});
+		encoder.copy_texture_to_buffer(
+			texture.as_image_copy(),
+			wgpu::ImageCopyBuffer {
+				buffer: &buffer,
+				layout: wgpu::ImageDataLayout {
+					offset: 0,
+					bytes_per_row: NonZeroU32::new(padded_row_bytes as u32),
+					rows_per_image: None,
+				},
+			},
+			wgpu::Extent3d {
+				width,
+				height,
+				depth_or_array_layers: 1,
+			},
+		);
+		self.render_queue.submit([encoder.finish()]);
+
+		let buffer_slice = buffer.slice(..);
+		self
+			.render_device
+			.map_buffer(&buffer_slice, wgpu::MapMode::Read, |result| {
+				if let Err(err) = result {
+					error!("Cannot map frame capture buffer: {}", err);
+				}
+			});
+		self.render_device.poll(wgpu::Maintain::Wait);
+
+		let mut pixels = Vec::with_capacity(row_bytes * height as usize);
+		for row in buffer_slice
+			.get_mapped_range()
+			.chunks(padded_row_bytes)
+		{
+			pixels.extend_from_slice(&row[..row_bytes]);
+		}
+		buffer.unmap();
+		if matches!(
+			format,
+			TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb
+		) {
+			for pixel in pixels.chunks_exact_mut(4) {
+				pixel.swap(0, 2);
+			}
+		}
+
+		let image = match image::RgbaImage::from_raw(width, height, pixels) {
+			Some(image) => image,
+			None => {
+				error!("Cannot create frame capture image");
+				return;
+			},
+		};
+		for path in &self.paths {
+			match image.save(path) {
+				Ok(()) => info!("Captured frame to {}", path),
+				Err(err) => error!("Cannot save frame capture to {}: {}", path, err),
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn surface_usages(capture_frames: Vec<u32>, backend: Backend) -> TextureUsages {
+		let mut world = World::new();
+		world.insert_resource(CiTestingConfig {
+			exit_after: None,
+			record: None,
+			replay: None,
+			events: Vec::new(),
+			capture_frames,
+			capture_prefix: None,
+			fail_on_error: true,
+			fail_on_panic: false,
+		});
+		world.insert_resource(AdapterInfo {
+			name: String::new(),
+			vendor: 0,
+			device: 0,
+			device_type: wgpu::DeviceType::Other,
+			backend,
+		});
+		FrameCaptures::new(&world).surface_usages()
+	}
+
+	#[test]
+	fn copy_surfaces_only_for_supported_captures() {
+		assert_eq!(
+			surface_usages(vec![10], Backend::Vulkan),
+			TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC
+		);
+		assert_eq!(
+			surface_usages(Vec::new(), Backend::Vulkan),
+			TextureUsages::RENDER_ATTACHMENT
+		);
+		assert_eq!(
+			surface_usages(vec![10], Backend::Gl),
+			TextureUsages::RENDER_ATTACHMENT
+		);
+		assert_eq!(
+			surface_usages(vec![10], Backend::Metal),
+			TextureUsages::RENDER_ATTACHMENT
+		);
+	}
+}
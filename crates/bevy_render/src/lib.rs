@@ -1,11 +1,14 @@
 extern crate core;
 
 pub mod camera;
+#[cfg(feature = "bevy_ci_testing")]
+mod ci_testing;
 pub mod color;
 pub mod extract_component;
 mod extract_param;
 pub mod extract_resource;
 pub mod mesh;
+pub mod pipelined_rendering;
 pub mod primitives;
 pub mod rangefinder;
 pub mod render_asset;
@@ -203,101 +206,108 @@ impl Plugin for RenderPlugin {
 			app.insert_resource(receiver);
 			render_app.insert_resource(sender);
 
-			app.add_sub_app(RenderApp, render_app, move |app_world, render_app| {
-				#[cfg(feature = "trace")]
-				let _render_span = bevy_log::info_span!("renderer subapp").entered();
-				{
+			app.add_sub_app_with_extract(
+				RenderApp,
+				render_app,
+				|app_world, render_app| {
 					#[cfg(feature = "trace")]
-					let _stage_span = bevy_log::info_span!("stage", name = "reserve_and_flush").entered();
-
-					// reserve all existing app entities for use in render_app
-					// they can only be spawned using `get_or_spawn()`
-					let meta_len = app_world.entities().meta_len();
-					render_app
-						.world
-						.entities()
-						.reserve_entities(meta_len as u32);
-
-					// flushing as "invalid" ensures that app world entities aren't added as "empty archetype" entities by default
-					// these entities cannot be accessed without spawning directly onto them
-					// this _only_ works as expected because clear_entities() is called at the end of every frame.
-					unsafe { render_app.world.entities_mut() }.flush_as_invalid();
-				}
-
-				{
+					let _render_span = bevy_log::info_span!("renderer subapp extract").entered();
+					{
+						#[cfg(feature = "trace")]
+						let _stage_span = bevy_log::info_span!("stage", name = "reserve_and_flush").entered();
+
+						// reserve all existing app entities for use in render_app
+						// they can only be spawned using `get_or_spawn()`
+						let meta_len = app_world.entities().meta_len();
+						render_app
+							.world
+							.entities()
+							.reserve_entities(meta_len as u32);
+
+						// flushing as "invalid" ensures that app world entities aren't added as "empty archetype" entities by default
+						// these entities cannot be accessed without spawning directly onto them
+						// this _only_ works as expected because clear_entities() is called at the end of every frame.
+						unsafe { render_app.world.entities_mut() }.flush_as_invalid();
+					}
+
+					{
+						#[cfg(feature = "trace")]
+						let _stage_span = bevy_log::info_span!("stage", name = "extract").entered();
+
+						// extract
+						extract(app_world, render_app);
+					}
+				},
+				|render_app| {
 					#[cfg(feature = "trace")]
-					let _stage_span = bevy_log::info_span!("stage", name = "extract").entered();
-
-					// extract
-					extract(app_world, render_app);
-				}
-
-				{
-					#[cfg(feature = "trace")]
-					let _stage_span = bevy_log::info_span!("stage", name = "prepare").entered();
-
-					// prepare
-					let prepare = render_app
-						.schedule
-						.get_stage_mut::<SystemStage>(&RenderStage::Prepare)
-						.unwrap();
-					prepare.run(&mut render_app.world);
-				}
-
-				{
-					#[cfg(feature = "trace")]
-					let _stage_span = bevy_log::info_span!("stage", name = "queue").entered();
-
-					// queue
-					let queue = render_app
-						.schedule
-						.get_stage_mut::<SystemStage>(&RenderStage::Queue)
-						.unwrap();
-					queue.run(&mut render_app.world);
-				}
-
-				{
-					#[cfg(feature = "trace")]
-					let _stage_span = bevy_log::info_span!("stage", name = "sort").entered();
-
-					// phase sort
-					let phase_sort = render_app
-						.schedule
-						.get_stage_mut::<SystemStage>(&RenderStage::PhaseSort)
-						.unwrap();
-					phase_sort.run(&mut render_app.world);
-				}
-
-				{
-					#[cfg(feature = "trace")]
-					let _stage_span = bevy_log::info_span!("stage", name = "render").entered();
-
-					// render
-					let render = render_app
-						.schedule
-						.get_stage_mut::<SystemStage>(&RenderStage::Render)
-						.unwrap();
-					render.run(&mut render_app.world);
-				}
-
-				{
-					#[cfg(feature = "trace")]
-					let _stage_span = bevy_log::info_span!("stage", name = "cleanup").entered();
-
-					// cleanup
-					let cleanup = render_app
-						.schedule
-						.get_stage_mut::<SystemStage>(&RenderStage::Cleanup)
-						.unwrap();
-					cleanup.run(&mut render_app.world);
-				}
-				{
-					#[cfg(feature = "trace")]
-					let _stage_span = bevy_log::info_span!("stage", name = "clear_entities").entered();
-
-					render_app.world.clear_entities();
-				}
-			});
+					let _render_span = bevy_log::info_span!("renderer subapp").entered();
+					{
+						#[cfg(feature = "trace")]
+						let _stage_span = bevy_log::info_span!("stage", name = "prepare").entered();
+
+						// prepare
+						let prepare = render_app
+							.schedule
+							.get_stage_mut::<SystemStage>(&RenderStage::Prepare)
+							.unwrap();
+						prepare.run(&mut render_app.world);
+					}
+
+					{
+						#[cfg(feature = "trace")]
+						let _stage_span = bevy_log::info_span!("stage", name = "queue").entered();
+
+						// queue
+						let queue = render_app
+							.schedule
+							.get_stage_mut::<SystemStage>(&RenderStage::Queue)
+							.unwrap();
+						queue.run(&mut render_app.world);
+					}
+
+					{
+						#[cfg(feature = "trace")]
+						let _stage_span = bevy_log::info_span!("stage", name = "sort").entered();
+
+						// phase sort
+						let phase_sort = render_app
+							.schedule
+							.get_stage_mut::<SystemStage>(&RenderStage::PhaseSort)
+							.unwrap();
+						phase_sort.run(&mut render_app.world);
+					}
+
+					{
+						#[cfg(feature = "trace")]
+						let _stage_span = bevy_log::info_span!("stage", name = "render").entered();
+
+						// render
+						let render = render_app
+							.schedule
+							.get_stage_mut::<SystemStage>(&RenderStage::Render)
+							.unwrap();
+						render.run(&mut render_app.world);
+					}
+
+					{
+						#[cfg(feature = "trace")]
+						let _stage_span = bevy_log::info_span!("stage", name = "cleanup").entered();
+
+						// cleanup
+						let cleanup = render_app
+							.schedule
+							.get_stage_mut::<SystemStage>(&RenderStage::Cleanup)
+							.unwrap();
+						cleanup.run(&mut render_app.world);
+					}
+					{
+						#[cfg(feature = "trace")]
+						let _stage_span = bevy_log::info_span!("stage", name = "clear_entities").entered();
+
+						render_app.world.clear_entities();
+					}
+				},
+			);
 		}
 
 		app.add_plugin(WindowRenderPlugin);
@@ -340,7 +350,11 @@ fn extract(app_world: &mut World, render_app: &mut App) {
 	app_world.insert_resource(ScratchMainWorld(scratch_world));
 
 	// Note: We apply buffers (read, Commands) after the `MainWorld` has been removed from the render app's world
-	// so that in future, pipelining will be able to do this too without any code relying on it.
+	// so that no code relies on it, as the main world isn't available to the render world
+	// once it runs on its own thread.
 	// see <https://github.com/bevyengine/bevy/issues/5082>
 	extract.apply_buffers(running_world);
+
+	// still on the thread updating the app, even when rendering is pipelined
+	view::window::create_surfaces(running_world);
 }
@@ -0,0 +1,40 @@
+use crate::{RenderApp, RenderPlugin};
+use bevy_app::{App, Plugin, PluginId};
+
+/// Runs the [`RenderApp`] on a thread of its own, so that the app world simulates frame `N + 1`
+/// while the render world draws frame `N`.
+///
+/// The [`Extract`](crate::RenderStage::Extract) stage still runs on the main thread, at the end
+/// of each update, and is the only point of contact between the two worlds. The window surfaces
+/// are created and configured there too, as some OSes require it to happen on the main thread.
+/// Rendering a frame then overlaps with the next update of the app, which adds a frame of latency.
+///
+/// This plugin isn't part of the default plugins, add it after the [`RenderPlugin`]:
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_render::{pipelined_rendering::PipelinedRenderingPlugin, RenderPlugin};
+/// let mut app = App::new();
+/// app.add_plugin(RenderPlugin);
+/// app.add_plugin(PipelinedRenderingPlugin);
+/// ```
+///
+/// It has no effect if the renderer isn't initialized, or on platforms without threads.
+#[derive(Default)]
+pub struct PipelinedRenderingPlugin;
+
+impl Plugin for PipelinedRenderingPlugin {
+	fn build(&self, _app: &mut App) {}
+
+	/// Moves the render app to its thread once all the plugins are set up, as the render world
+	/// can't be given non-send resources afterwards.
+	fn cleanup(&self, app: &mut App) {
+		if app.get_sub_app(RenderApp).is_ok() {
+			app.set_sub_app_pipelined(RenderApp, true);
+		}
+	}
+
+	fn dependencies(&self) -> Vec<PluginId> {
+		vec![PluginId::of::<RenderPlugin>()]
+	}
+}
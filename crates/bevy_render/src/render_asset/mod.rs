@@ -48,9 +48,11 @@ pub enum PrepareAssetLabel {
 	PostAssetPrepare,
 }
 
-/// Temporarily stores the extracted and removed assets of the current frame.
+/// Temporarily stores the extracted, aliased and removed assets of the current frame.
 pub struct ExtractedAssets<A: RenderAsset> {
 	extracted: Vec<(Handle<A>, A::ExtractedAsset)>,
+	/// The handles aliasing other assets, with the handle of the asset they alias.
+	aliased: Vec<(Handle<A>, Handle<A>)>,
 	removed: Vec<Handle<A>>,
 }
 
@@ -58,6 +60,7 @@ impl<A: RenderAsset> Default for ExtractedAssets<A> {
 	fn default() -> Self {
 		Self {
 			extracted: Default::default(),
+			aliased: Default::default(),
 			removed: Default::default(),
 		}
 	}
@@ -65,7 +68,69 @@ impl<A: RenderAsset> Default for ExtractedAssets<A> {
 
 /// Stores all GPU representations ([`RenderAsset::PreparedAssets`](RenderAsset::PreparedAsset))
 /// of [`RenderAssets`](RenderAsset) as long as they exist.
-pub type RenderAssets<A> = HashMap<Handle<A>, <A as RenderAsset>::PreparedAsset>;
+///
+/// The handles aliasing another asset (see [`Assets::get_alias`]) share the GPU representation
+/// of that asset instead of preparing a copy of it.
+pub struct RenderAssets<A: RenderAsset> {
+	prepared: HashMap<Handle<A>, A::PreparedAsset>,
+	aliases: HashMap<Handle<A>, Handle<A>>,
+}
+
+impl<A: RenderAsset> Default for RenderAssets<A> {
+	fn default() -> Self {
+		Self {
+			prepared: Default::default(),
+			aliases: Default::default(),
+		}
+	}
+}
+
+impl<A: RenderAsset> RenderAssets<A> {
+	/// Gets the GPU representation of the asset of the given handle.
+	pub fn get(&self, handle: &Handle<A>) -> Option<&A::PreparedAsset> {
+		let handle = self.aliases.get(handle).unwrap_or(handle);
+		self.prepared.get(handle)
+	}
+
+	/// Gets mutable access to the GPU representation of the asset of the given handle.
+	pub fn get_mut(&mut self, handle: &Handle<A>) -> Option<&mut A::PreparedAsset> {
+		let handle = self.aliases.get(handle).unwrap_or(handle);
+		self.prepared.get_mut(handle)
+	}
+
+	/// Returns `true` if there is a GPU representation for the asset of the given handle.
+	pub fn contains_key(&self, handle: &Handle<A>) -> bool {
+		self.get(handle).is_some()
+	}
+
+	/// Inserts the GPU representation of the asset of the given handle, returning the previous
+	/// one. The handle doesn't alias another asset anymore.
+	pub fn insert(
+		&mut self,
+		handle: Handle<A>,
+		prepared_asset: A::PreparedAsset,
+	) -> Option<A::PreparedAsset> {
+		self.aliases.remove(&handle);
+		self.prepared.insert(handle, prepared_asset)
+	}
+
+	/// Makes the given handle share the GPU representation of the asset of `original`.
+	pub fn insert_alias(&mut self, handle: Handle<A>, original: Handle<A>) {
+		self.prepared.remove(&handle);
+		self.aliases.insert(handle, original);
+	}
+
+	/// Removes the GPU representation or the alias of the given handle.
+	pub fn remove(&mut self, handle: &Handle<A>) -> Option<A::PreparedAsset> {
+		self.aliases.remove(handle);
+		self.prepared.remove(handle)
+	}
+
+	/// Gets an iterator over the GPU representations, without the aliases.
+	pub fn iter(&self) -> impl Iterator<Item = (&Handle<A>, &A::PreparedAsset)> {
+		self.prepared.iter()
+	}
+}
 
 /// This system extracts all crated or modified assets of the corresponding [`RenderAsset`] type
 /// into the "render world".
@@ -89,13 +154,20 @@ pub(crate) fn extract_render_asset<A: RenderAsset>(
 	}
 
 	let mut extracted = Vec::new();
+	let mut aliased = Vec::new();
 	for handle in changed.drain() {
-		if let Some(asset) = assets.get(&handle) {
+		if let Some(original) = assets.get_alias(&handle) {
+			aliased.push((handle, original.clone_weak()));
+		} else if let Some(asset) = assets.get(&handle) {
 			extracted.push((handle, asset.extract_asset()));
 		}
 	}
 
-	commands.insert_resource(ExtractedAssets { extracted, removed });
+	commands.insert_resource(ExtractedAssets {
+		extracted,
+		aliased,
+		removed,
+	});
 }
 
 // TODO: consider storing inside system?
@@ -141,6 +213,10 @@ pub(crate) fn prepare_assets<R: RenderAsset>(
 		render_assets.remove(&removed);
 	}
 
+	for (handle, original) in std::mem::take(&mut extracted_assets.aliased) {
+		render_assets.insert_alias(handle, original);
+	}
+
 	for (handle, extracted_asset) in std::mem::take(&mut extracted_assets.extracted) {
 		match R::prepare_asset(extracted_asset, &mut param) {
 			Ok(prepared) => {
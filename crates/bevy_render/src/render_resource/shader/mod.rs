@@ -237,6 +237,8 @@ impl ShaderReflection {
 pub struct ShaderLoader;
 
 impl AssetLoader for ShaderLoader {
+	type Settings = ();
+
 	fn load<'a>(
 		&'a self,
 		bytes: &'a [u8],
@@ -61,10 +61,24 @@ pub fn render_system(world: &mut World) {
 				.remove::<ViewTarget>();
 		}
 
+		#[cfg(feature = "bevy_ci_testing")]
+		let frame_captures = crate::ci_testing::PendingFrameCaptures::take(world);
+
 		let mut windows = world.resource_mut::<ExtractedWindows>();
 		for window in windows.values_mut() {
 			if let Some(texture_view) = window.swap_chain_texture.take() {
 				if let Some(surface_texture) = texture_view.take_surface_texture() {
+					#[cfg(feature = "bevy_ci_testing")]
+					if let Some(frame_captures) = &frame_captures {
+						if window.id.is_primary() {
+							frame_captures.capture(
+								&surface_texture.texture,
+								crate::texture::BevyDefault::bevy_default(),
+								window.physical_width,
+								window.physical_height,
+							);
+						}
+					}
 					surface_texture.present();
 				}
 			}
@@ -9,6 +9,8 @@ use wgpu::{Extent3d, TextureDimension, TextureFormat};
 pub struct HdrTextureLoader;
 
 impl AssetLoader for HdrTextureLoader {
+	type Settings = ();
+
 	fn load<'a>(
 		&'a self,
 		bytes: &'a [u8],
@@ -282,6 +282,35 @@ impl Image {
 		value
 	}
 
+	/// Creates a magenta and black checkerboard, the fallback asset of the images failing to
+	/// load so they are easy to spot.
+	pub fn placeholder() -> Self {
+		const SIZE: u32 = 8;
+		let mut data = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+		for y in 0..SIZE {
+			for x in 0..SIZE {
+				let pixel = if (x + y) % 2 == 0 {
+					[255, 0, 255, 255]
+				} else {
+					[0, 0, 0, 255]
+				};
+				data.extend_from_slice(&pixel);
+			}
+		}
+		let mut image = Image::new(
+			Extent3d {
+				width: SIZE,
+				height: SIZE,
+				depth_or_array_layers: 1,
+			},
+			TextureDimension::D2,
+			data,
+			TextureFormat::Rgba8UnormSrgb,
+		);
+		image.sampler_descriptor = ImageSampler::nearest();
+		image
+	}
+
 	/// Returns the aspect ratio (height/width) of a 2D image.
 	pub fn aspect_2d(&self) -> f32 {
 		self.texture_descriptor.size.height as f32 / self.texture_descriptor.size.width as f32
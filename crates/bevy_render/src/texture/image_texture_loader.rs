@@ -2,11 +2,12 @@ use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_ecs::prelude::{FromWorld, World};
 use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
 	renderer::RenderDevice,
-	texture::{Image, ImageType, TextureError},
+	texture::{Image, ImageSampler, ImageType, TextureError},
 };
 
 use super::CompressedImageFormats;
@@ -17,6 +18,56 @@ pub struct ImageTextureLoader {
 	supported_compressed_formats: CompressedImageFormats,
 }
 
+/// The settings an image is loaded with by the [`ImageTextureLoader`].
+///
+/// For example, the `.meta` file of a normal map with nearest sampling contains:
+///
+/// ```ron
+/// (
+///     loader_settings: (is_srgb: false, sampler: Nearest),
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageLoaderSettings {
+	/// Whether the image is in the sRGB color space. Images holding linear data, such as normal
+	/// maps, should set it to `false`.
+	pub is_srgb: bool,
+	/// The sampler of the image.
+	pub sampler: ImageLoaderSampler,
+}
+
+impl Default for ImageLoaderSettings {
+	fn default() -> Self {
+		Self {
+			is_srgb: true,
+			sampler: ImageLoaderSampler::Default,
+		}
+	}
+}
+
+/// The sampler of an image loaded by the [`ImageTextureLoader`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageLoaderSampler {
+	/// The default sampler of the [`ImageSettings`](super::ImageSettings) resource.
+	#[default]
+	Default,
+	/// A sampler with `Linear` min and mag filters.
+	Linear,
+	/// A sampler with `Nearest` min and mag filters.
+	Nearest,
+}
+
+impl From<ImageLoaderSampler> for ImageSampler {
+	fn from(sampler: ImageLoaderSampler) -> Self {
+		match sampler {
+			ImageLoaderSampler::Default => ImageSampler::Default,
+			ImageLoaderSampler::Linear => ImageSampler::linear(),
+			ImageLoaderSampler::Nearest => ImageSampler::nearest(),
+		}
+	}
+}
+
 const FILE_EXTENSIONS: &[&str] = &[
 	#[cfg(feature = "basis-universal")]
 	"basis",
@@ -37,6 +88,8 @@ const FILE_EXTENSIONS: &[&str] = &[
 ];
 
 impl AssetLoader for ImageTextureLoader {
+	type Settings = ImageLoaderSettings;
+
 	fn load<'a>(
 		&'a self,
 		bytes: &'a [u8],
@@ -51,16 +104,18 @@ impl AssetLoader for ImageTextureLoader {
 				.to_str()
 				.unwrap();
 
-			let dyn_img = Image::from_buffer(
+			let settings = load_context.settings::<ImageLoaderSettings>()?;
+			let mut dyn_img = Image::from_buffer(
 				bytes,
 				ImageType::Extension(ext),
 				self.supported_compressed_formats,
-				true,
+				settings.is_srgb,
 			)
 			.map_err(|err| FileTextureError {
 				error: err,
 				path: format!("{}", load_context.path().display()),
 			})?;
+			dyn_img.sampler_descriptor = settings.sampler.into();
 
 			load_context.set_default_asset(LoadedAsset::from(dyn_img));
 			Ok(())
@@ -0,0 +1,36 @@
+use crate::texture::{image_texture_conversion::texture_to_image, Image};
+use anyhow::Result;
+use bevy_asset::AssetSaver;
+use std::io::Cursor;
+use thiserror::Error;
+
+/// Saves [`Image`]s as PNG files.
+///
+/// Only images with 8 bits per channel can be saved, like the images loaded from PNG files.
+#[derive(Clone, Default)]
+pub struct ImageTextureSaver;
+
+/// An error that occurs when saving an [`Image`].
+#[derive(Error, Debug)]
+pub enum ImageTextureSaverError {
+	/// The texture format of the image can't be saved.
+	#[error("unsupported texture format: {0:?}")]
+	UnsupportedFormat(wgpu::TextureFormat),
+}
+
+impl AssetSaver for ImageTextureSaver {
+	type Asset = Image;
+
+	fn save(&self, image: &Image) -> Result<Vec<u8>> {
+		let dynamic_image = texture_to_image(image).ok_or(
+			ImageTextureSaverError::UnsupportedFormat(image.texture_descriptor.format),
+		)?;
+		let mut bytes = Cursor::new(Vec::new());
+		dynamic_image.write_to(&mut bytes, image::ImageOutputFormat::Png)?;
+		Ok(bytes.into_inner())
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["png"]
+	}
+}
@@ -8,6 +8,8 @@ mod hdr_texture_loader;
 #[allow(clippy::module_inception)]
 mod image;
 mod image_texture_loader;
+#[cfg(feature = "png")]
+mod image_texture_saver;
 #[cfg(feature = "ktx2")]
 mod ktx2;
 mod texture_cache;
@@ -24,6 +26,8 @@ pub use hdr_texture_loader::*;
 
 pub use fallback_image::*;
 pub use image_texture_loader::*;
+#[cfg(feature = "png")]
+pub use image_texture_saver::*;
 pub use texture_cache::*;
 
 use crate::{
@@ -58,6 +62,11 @@ impl Plugin for ImagePlugin {
 			app.init_asset_loader::<HdrTextureLoader>();
 		}
 
+		#[cfg(feature = "png")]
+		{
+			app.init_asset_saver::<ImageTextureSaver>();
+		}
+
 		app.add_plugin(RenderAssetPlugin::<Image>::from(
 			PrepareAssetLabel::PreAssetPrepare,
 		));
@@ -66,6 +75,7 @@ impl Plugin for ImagePlugin {
 			.world
 			.resource_mut::<Assets<Image>>()
 			.set_untracked(DEFAULT_IMAGE_HANDLE, Image::default());
+		app.set_fallback_asset(Image::placeholder());
 
 		let default_sampler = app
 			.world
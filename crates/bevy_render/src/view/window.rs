@@ -25,11 +25,25 @@ pub enum WindowSystem {
 
 impl Plugin for WindowRenderPlugin {
 	fn build(&self, app: &mut App) {
+		#[cfg(feature = "bevy_ci_testing")]
+		let frame_captures = crate::ci_testing::FrameCaptures::new(&app.world);
 		if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
 			render_app.init_resource::<ExtractedWindows>();
 			render_app.init_resource::<WindowSurfaces>();
 			render_app.init_resource::<NonSendMarker>();
 			render_app.add_system_to_stage(RenderStage::Extract, extract_windows);
+			#[cfg(feature = "bevy_ci_testing")]
+			{
+				render_app
+					.world
+					.resource_mut::<WindowSurfaces>()
+					.usages = frame_captures.surface_usages();
+				render_app.insert_resource(frame_captures);
+				render_app.add_system_to_stage(
+					RenderStage::Extract,
+					crate::ci_testing::extract_frame_captures,
+				);
+			}
 			render_app.add_system_to_stage(
 				RenderStage::Prepare,
 				prepare_windows.label(WindowSystem::Prepare),
@@ -109,14 +123,73 @@ fn extract_windows(
 	}
 }
 
-#[derive(Default)]
 pub struct WindowSurfaces {
 	surfaces: HashMap<WindowId, wgpu::Surface>,
 	/// List of windows that we have already called the initial `configure_surface` for
 	configured_windows: HashSet<WindowId>,
+	/// The usages the surfaces are configured with
+	usages: wgpu::TextureUsages,
+}
+
+impl Default for WindowSurfaces {
+	fn default() -> Self {
+		Self {
+			surfaces: Default::default(),
+			configured_windows: Default::default(),
+			usages: wgpu::TextureUsages::RENDER_ATTACHMENT,
+		}
+	}
+}
+
+/// Creates the surfaces of the new windows and (re)configures the surfaces of the new, resized
+/// and outdated windows.
+///
+/// NOTE: On some OSes surfaces MUST be created and configured on the main thread. This runs at
+/// the end of the extraction, on the thread updating the app, instead of in a render stage that
+/// runs on the render thread when rendering is
+/// [pipelined](crate::pipelined_rendering::PipelinedRenderingPlugin).
+pub(crate) fn create_surfaces(world: &mut World) {
+	let world = world.cell();
+	let windows = world.resource::<ExtractedWindows>();
+	let mut window_surfaces = world.resource_mut::<WindowSurfaces>();
+	let render_device = world.resource::<RenderDevice>();
+	let render_instance = world.resource::<RenderInstance>();
+
+	let window_surfaces = window_surfaces.deref_mut();
+	for window in windows.windows.values() {
+		let surface = window_surfaces
+			.surfaces
+			.entry(window.id)
+			.or_insert_with(|| unsafe { render_instance.create_surface(&window.handle.get_handle()) });
+
+		// Do the initial surface configuration if it hasn't been configured yet
+		if window_surfaces
+			.configured_windows
+			.insert(window.id)
+			|| window.size_changed
+		{
+			let swap_chain_descriptor = wgpu::SurfaceConfiguration {
+				format: TextureFormat::bevy_default(),
+				width: window.physical_width,
+				height: window.physical_height,
+				usage: window_surfaces.usages,
+				present_mode: match window.present_mode {
+					PresentMode::Fifo => wgpu::PresentMode::Fifo,
+					PresentMode::Mailbox => wgpu::PresentMode::Mailbox,
+					PresentMode::Immediate => wgpu::PresentMode::Immediate,
+					PresentMode::AutoVsync => wgpu::PresentMode::AutoVsync,
+					PresentMode::AutoNoVsync => wgpu::PresentMode::AutoNoVsync,
+				},
+			};
+			render_device.configure_surface(surface, &swap_chain_descriptor);
+		}
+	}
 }
 
-/// Creates and (re)configures window surfaces, and obtains a swapchain texture for rendering.
+/// Obtains a swapchain texture for rendering from the surfaces created by [`create_surfaces`].
+///
+/// An outdated surface is reconfigured by the next extraction, and its window isn't rendered
+/// this frame.
 ///
 /// NOTE: `get_current_texture` in `prepare_windows` can take a long time if the GPU workload is
 /// the performance bottleneck. This can be seen in profiles as multiple prepare-stage systems all
@@ -143,49 +216,20 @@ pub fn prepare_windows(
 	_marker: NonSend<NonSendMarker>,
 	mut windows: ResMut<ExtractedWindows>,
 	mut window_surfaces: ResMut<WindowSurfaces>,
-	render_device: Res<RenderDevice>,
-	render_instance: Res<RenderInstance>,
 ) {
 	let window_surfaces = window_surfaces.deref_mut();
 	for window in windows.windows.values_mut() {
-		let surface = window_surfaces
-			.surfaces
-			.entry(window.id)
-			.or_insert_with(|| unsafe {
-				// NOTE: On some OSes this MUST be called from the main thread.
-				render_instance.create_surface(&window.handle.get_handle())
-			});
-
-		let swap_chain_descriptor = wgpu::SurfaceConfiguration {
-			format: TextureFormat::bevy_default(),
-			width: window.physical_width,
-			height: window.physical_height,
-			usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-			present_mode: match window.present_mode {
-				PresentMode::Fifo => wgpu::PresentMode::Fifo,
-				PresentMode::Mailbox => wgpu::PresentMode::Mailbox,
-				PresentMode::Immediate => wgpu::PresentMode::Immediate,
-				PresentMode::AutoVsync => wgpu::PresentMode::AutoVsync,
-				PresentMode::AutoNoVsync => wgpu::PresentMode::AutoNoVsync,
-			},
+		let surface = match window_surfaces.surfaces.get(&window.id) {
+			Some(surface) => surface,
+			None => continue,
 		};
-
-		// Do the initial surface configuration if it hasn't been configured yet
-		if window_surfaces
-			.configured_windows
-			.insert(window.id)
-			|| window.size_changed
-		{
-			render_device.configure_surface(surface, &swap_chain_descriptor);
-		}
-
 		let frame = match surface.get_current_texture() {
 			Ok(swap_chain_frame) => swap_chain_frame,
 			Err(wgpu::SurfaceError::Outdated) => {
-				render_device.configure_surface(surface, &swap_chain_descriptor);
-				surface
-					.get_current_texture()
-					.expect("Error reconfiguring surface")
+				window_surfaces
+					.configured_windows
+					.remove(&window.id);
+				continue;
 			},
 			err => err.expect("Failed to acquire next swap chain texture!"),
 		};
@@ -27,7 +27,8 @@ struct SceneTransform {
 /// A component bundle for a [`Scene`] root.
 ///
 /// The scene from `scene` will be spawn as a child of the entity with this component.
-/// Once it's spawned, the entity will have a [`SceneInstance`] component.
+/// Once it's spawned, the entity will have a [`SceneInstance`] component and a
+/// [`SceneInstanceReady`](crate::SceneInstanceReady) event is sent.
 #[derive(Default, Bundle)]
 pub struct SceneBundle {
 	/// Handle to the scene to spawn
@@ -41,7 +42,8 @@ pub struct SceneBundle {
 /// A component bundle for a [`DynamicScene`] root.
 ///
 /// The dynamic scene from `scene` will be spawn as a child of the entity with this component.
-/// Once it's spawned, the entity will have a [`SceneInstance`] component.
+/// Once it's spawned, the entity will have a [`SceneInstance`] component and a
+/// [`SceneInstanceReady`](crate::SceneInstanceReady) event is sent.
 #[derive(Default, Bundle)]
 pub struct DynamicSceneBundle {
 	/// Handle to the scene to spawn
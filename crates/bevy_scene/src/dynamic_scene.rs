@@ -1,14 +1,16 @@
-use crate::{serde::SceneSerializer, Scene, SceneSpawnError};
+use crate::{serde::SceneSerializer, DynamicSceneBuilder, Scene, SceneSpawnError};
 use anyhow::Result;
+use bevy_asset::Handle;
 use bevy_ecs::{
-	entity::EntityMap,
-	reflect::{ReflectComponent, ReflectMapEntities},
+	entity::{Entity, EntityMap, MapEntitiesError},
+	reflect::{ReflectComponent, ReflectMapEntities, ReflectMapEntitiesResource, ReflectResource},
 	world::World,
 };
-use bevy_reflect::{Reflect, TypeRegistryArc, TypeUuid};
+use bevy_reflect::{GetPath, Reflect, TypeRegistry, TypeRegistryArc, TypeUuid};
 use serde::Serialize;
 
-/// A collection of serializable dynamic entities, each with its own run-time defined set of components.
+/// A collection of serializable resources and dynamic entities, each with its own run-time defined
+/// set of components.
 /// To spawn a dynamic scene, you can use either:
 /// * [`SceneSpawner::spawn_dynamic`](crate::SceneSpawner::spawn_dynamic)
 /// * adding the [`DynamicSceneBundle`](crate::DynamicSceneBundle) to an entity
@@ -18,7 +20,12 @@ use serde::Serialize;
 #[derive(Default, TypeUuid)]
 #[uuid = "749479b1-fb8c-4ff8-a775-623aa76014f5"]
 pub struct DynamicScene {
+	/// A vector of boxed resources that implement the `Reflect` trait.
+	pub resources: Vec<Box<dyn Reflect>>,
 	pub entities: Vec<DynamicEntity>,
+	/// The instances of other scenes nested in this scene, spawned by the
+	/// [`SceneSpawner`](crate::SceneSpawner).
+	pub instances: Vec<DynamicSceneInstance>,
 }
 
 /// A reflection-powered serializable representation of an entity and its components.
@@ -30,55 +37,119 @@ pub struct DynamicEntity {
 	pub components: Vec<Box<dyn Reflect>>,
 }
 
+/// An instance of another dynamic scene nested in a [`DynamicScene`], like a prefab, with the
+/// properties of its entities overridden in this instance.
+///
+/// When the instantiated scene changes, the instance is updated and the overrides are applied
+/// again.
+pub struct DynamicSceneInstance {
+	/// The asset path of the instantiated scene, as written to scene files.
+	pub path: String,
+	/// The handle of the instantiated scene. The [`SceneLoader`](crate::SceneLoader) loads it
+	/// from `path`.
+	pub scene: Handle<DynamicScene>,
+	/// The entity of this scene the root entities of the instance are added as children to.
+	pub parent: Option<u32>,
+	/// The properties overridden in the entities of the instance, applied in order.
+	pub overrides: Vec<SceneOverride>,
+}
+
+/// A property of an entity of a nested scene overridden by a [`DynamicSceneInstance`].
+pub struct SceneOverride {
+	/// The identifier of the entity in the instantiated scene.
+	pub entity: u32,
+	/// The type name of the overridden component.
+	pub component: String,
+	/// The [path](GetPath) of the overridden field in the component, or an empty path to
+	/// override the whole component.
+	pub path: String,
+	/// The value of the overridden field or component.
+	pub value: Box<dyn Reflect>,
+}
+
+impl SceneOverride {
+	/// Applies the override to the entity it maps to in `entity_map`.
+	///
+	/// A whole component is inserted if the entity doesn't have it, while overriding a field
+	/// requires the entity to have the component.
+	pub fn apply(
+		&self,
+		world: &mut World,
+		entity_map: &EntityMap,
+		type_registry: &TypeRegistry,
+	) -> Result<(), SceneSpawnError> {
+		let invalid_override = |reason: String| SceneSpawnError::InvalidOverride {
+			entity: self.entity,
+			type_name: self.component.clone(),
+			path: self.path.clone(),
+			reason,
+		};
+		let entity = entity_map
+			.get(Entity::from_raw(self.entity))
+			.map_err(|_| invalid_override("the scene has no such entity".to_string()))?;
+		let registration = type_registry
+			.get_with_name(&self.component)
+			.ok_or(SceneSpawnError::UnregisteredType {
+				type_name: self.component.clone(),
+			})?;
+		let reflect_component =
+			registration
+				.data::<ReflectComponent>()
+				.ok_or(SceneSpawnError::UnregisteredComponent {
+					type_name: self.component.clone(),
+				})?;
+
+		if self.path.is_empty() {
+			reflect_component.apply_or_insert(world, entity, &*self.value);
+			return Ok(());
+		}
+		let mut component = reflect_component
+			.reflect_mut(world, entity)
+			.ok_or_else(|| invalid_override("the entity doesn't have the component".to_string()))?;
+		let field = component
+			.path_mut(&self.path)
+			.map_err(|err| invalid_override(err.to_string()))?;
+		// applying a value of another type panics
+		if field.type_name() != self.value.type_name() {
+			return Err(invalid_override(format!(
+				"the field is of type `{}`, not `{}`",
+				field.type_name(),
+				self.value.type_name()
+			)));
+		}
+		field.apply(&*self.value);
+		Ok(())
+	}
+}
+
 impl DynamicScene {
 	/// Create a new dynamic scene from a given scene.
 	pub fn from_scene(scene: &Scene, type_registry: &TypeRegistryArc) -> Self {
 		Self::from_world(&scene.world, type_registry)
 	}
 
-	/// Create a new dynamic scene from a given world.
+	/// Create a new dynamic scene from a given world, with all its entities and resources.
+	///
+	/// Use a [`DynamicSceneBuilder`] to choose the entities, components and resources.
 	pub fn from_world(world: &World, type_registry: &TypeRegistryArc) -> Self {
-		let mut scene = DynamicScene::default();
-		let type_registry = type_registry.read();
-
-		for archetype in world.archetypes().iter() {
-			let entities_offset = scene.entities.len();
-
-			// Create a new dynamic entity for each entity of the given archetype
-			// and insert it into the dynamic scene.
-			for entity in archetype.entities() {
-				scene.entities.push(DynamicEntity {
-					entity: entity.id(),
-					components: Vec::new(),
-				});
-			}
-
-			// Add each reflection-powered component to the entity it belongs to.
-			for component_id in archetype.components() {
-				let reflect_component = world
-					.components()
-					.get_info(component_id)
-					.and_then(|info| type_registry.get(info.type_id().unwrap()))
-					.and_then(|registration| registration.data::<ReflectComponent>());
-				if let Some(reflect_component) = reflect_component {
-					for (i, entity) in archetype.entities().iter().enumerate() {
-						if let Some(component) = reflect_component.reflect(world, *entity) {
-							scene.entities[entities_offset + i]
-								.components
-								.push(component.clone_value());
-						}
-					}
-				}
-			}
-		}
-
-		scene
+		let entities = world
+			.archetypes()
+			.iter()
+			.flat_map(|archetype| archetype.entities().iter().copied());
+		DynamicSceneBuilder::from_world_with_type_registry(world, type_registry.clone())
+			.extract_entities(entities)
+			.extract_resources()
+			.build()
 	}
 
-	/// Write the dynamic entities and their corresponding components to the given world.
+	/// Write the resources, the dynamic entities and their corresponding components to the given
+	/// world. Resources already in the world are overwritten.
 	///
 	/// This method will return a `SceneSpawnError` if either a type is not registered
-	/// or doesn't reflect the `Component` trait.
+	/// or doesn't reflect the `Component` or `Resource` trait.
+	///
+	/// The nested scene [`instances`](Self::instances) are only spawned by the
+	/// [`SceneSpawner`](crate::SceneSpawner).
 	pub fn write_to_world(
 		&self,
 		world: &mut World,
@@ -86,6 +157,35 @@ impl DynamicScene {
 	) -> Result<(), SceneSpawnError> {
 		let registry = world.resource::<TypeRegistryArc>().clone();
 		let type_registry = registry.read();
+		self.write_entities(world, entity_map, &type_registry)?;
+		self.map_entities(world, entity_map, &type_registry)
+	}
+
+	/// Writes the resources and the entities to the world, without mapping the entities they
+	/// refer to.
+	pub(crate) fn write_entities(
+		&self,
+		world: &mut World,
+		entity_map: &mut EntityMap,
+		type_registry: &TypeRegistry,
+	) -> Result<(), SceneSpawnError> {
+		for resource in &self.resources {
+			let registration = type_registry
+				.get_with_name(resource.type_name())
+				.ok_or(SceneSpawnError::UnregisteredType {
+					type_name: resource.type_name().to_string(),
+				})?;
+			let reflect_resource =
+				registration
+					.data::<ReflectResource>()
+					.ok_or(SceneSpawnError::UnregisteredResource {
+						type_name: resource.type_name().to_string(),
+					})?;
+
+			// If the world already has the given resource, just apply the (possibly) new value,
+			// otherwise insert the resource.
+			reflect_resource.apply_or_insert(world, &**resource);
+		}
 
 		for scene_entity in &self.entities {
 			// Fetch the entity with the given entity id from the `entity_map`
@@ -115,18 +215,47 @@ impl DynamicScene {
 			}
 		}
 
+		Ok(())
+	}
+
+	/// Maps the entities referred to by the components of the entities in `entity_map` and by the
+	/// resources of this scene.
+	///
+	/// Returns a [`SceneSpawnError::UnmappedEntity`] if they refer to an entity which isn't in
+	/// `entity_map`.
+	pub(crate) fn map_entities(
+		&self,
+		world: &mut World,
+		entity_map: &EntityMap,
+		type_registry: &TypeRegistry,
+	) -> Result<(), SceneSpawnError> {
+		let unmapped_entity =
+			|type_name: &str, MapEntitiesError::EntityNotFound(entity)| SceneSpawnError::UnmappedEntity {
+				type_name: type_name.to_string(),
+				entity,
+			};
 		for registration in type_registry.iter() {
 			if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
 				map_entities_reflect
 					.map_entities(world, entity_map)
-					.unwrap();
+					.map_err(|err| unmapped_entity(registration.type_name(), err))?;
 			}
 		}
 
+		// Only the resources written by this scene refer to its entities.
+		for resource in &self.resources {
+			let map_entities_reflect = type_registry
+				.get_with_name(resource.type_name())
+				.and_then(|registration| registration.data::<ReflectMapEntitiesResource>());
+			if let Some(map_entities_reflect) = map_entities_reflect {
+				map_entities_reflect
+					.map_entities(world, entity_map)
+					.map_err(|err| unmapped_entity(resource.type_name(), err))?;
+			}
+		}
 		Ok(())
 	}
 
-	// TODO: move to AssetSaver when it is implemented
 	/// Serialize this dynamic scene into rust object notation (ron).
 	pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
 		serialize_ron(SceneSerializer::new(self, registry))
@@ -144,3 +273,72 @@ where
 		.new_line("\n".to_string());
 	ron::ser::to_string_pretty(&serialize, pretty_config)
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::{DynamicEntity, DynamicScene, SceneSpawnError};
+	use bevy_ecs::{
+		entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+		reflect::{ReflectMapEntitiesResource, ReflectResource},
+		world::{FromWorld, World},
+	};
+	use bevy_reflect::{Reflect, TypeRegistryArc};
+
+	#[derive(Reflect)]
+	#[reflect(Resource, MapEntitiesResource)]
+	struct Target(Entity);
+
+	impl FromWorld for Target {
+		fn from_world(_world: &mut World) -> Self {
+			Target(Entity::from_raw(u32::MAX))
+		}
+	}
+
+	impl MapEntities for Target {
+		fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+			self.0 = entity_map.get(self.0)?;
+			Ok(())
+		}
+	}
+
+	fn world() -> World {
+		let registry = TypeRegistryArc::default();
+		registry.write().register::<Target>();
+		let mut world = World::new();
+		world.insert_resource(registry);
+		world
+	}
+
+	#[test]
+	fn map_resource_entities() {
+		let mut world = world();
+		let scene = DynamicScene {
+			resources: vec![Box::new(Target(Entity::from_raw(0)))],
+			entities: vec![DynamicEntity {
+				entity: 0,
+				components: Vec::new(),
+			}],
+			..Default::default()
+		};
+		let mut entity_map = EntityMap::default();
+		scene
+			.write_to_world(&mut world, &mut entity_map)
+			.unwrap();
+		let entity = entity_map.get(Entity::from_raw(0)).unwrap();
+		assert_eq!(world.resource::<Target>().0, entity);
+	}
+
+	#[test]
+	fn unmapped_resource_entity() {
+		let mut world = world();
+		let scene = DynamicScene {
+			resources: vec![Box::new(Target(Entity::from_raw(42)))],
+			..Default::default()
+		};
+		let result = scene.write_to_world(&mut world, &mut EntityMap::default());
+		assert!(matches!(
+			result,
+			Err(SceneSpawnError::UnmappedEntity { entity, .. }) if entity == Entity::from_raw(42)
+		));
+	}
+}
@@ -0,0 +1,455 @@
+use crate::{DynamicEntity, DynamicScene, SceneFilter};
+use bevy_ecs::{
+	component::{Component, ComponentTicks},
+	entity::Entity,
+	reflect::{ReflectComponent, ReflectResource},
+	system::Resource,
+	world::World,
+};
+use bevy_hierarchy::Children;
+use bevy_reflect::TypeRegistryArc;
+use std::collections::BTreeSet;
+
+/// A builder extracting a chosen set of entities, components and resources of a [`World`] into a
+/// [`DynamicScene`].
+///
+/// Entities are extracted explicitly, by query or by hierarchy, and resources only when asked
+/// for. The filters and [`changed_since`](Self::changed_since) apply when the scene is
+/// [built](Self::build), whatever the order of the calls.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::TypeRegistryArc;
+/// # use bevy_scene::DynamicSceneBuilder;
+/// # #[derive(Component)]
+/// # struct Player;
+/// # #[derive(Component)]
+/// # struct Cache;
+/// # let mut world = World::new();
+/// # world.init_resource::<TypeRegistryArc>();
+/// # world.spawn().insert(Player).insert(Cache);
+/// # world.spawn().insert(Cache);
+/// let players: Vec<Entity> = world
+///     .query_filtered::<Entity, With<Player>>()
+///     .iter(&world)
+///     .collect();
+/// let scene = DynamicSceneBuilder::from_world(&world)
+///     .deny::<Cache>()
+///     .extract_entities(players)
+///     .extract_resources()
+///     .build();
+/// assert_eq!(scene.entities.len(), 1);
+/// ```
+pub struct DynamicSceneBuilder<'w> {
+	world: &'w World,
+	type_registry: TypeRegistryArc,
+	entities: BTreeSet<Entity>,
+	extract_resources: bool,
+	component_filter: SceneFilter,
+	resource_filter: SceneFilter,
+	changed_since: Option<u32>,
+}
+
+impl<'w> DynamicSceneBuilder<'w> {
+	/// Creates a builder extracting from `world` with the [`TypeRegistryArc`] resource of the
+	/// world.
+	///
+	/// # Panics
+	///
+	/// Panics if the world has no [`TypeRegistryArc`] resource.
+	pub fn from_world(world: &'w World) -> Self {
+		let type_registry = world.resource::<TypeRegistryArc>().clone();
+		Self::from_world_with_type_registry(world, type_registry)
+	}
+
+	/// Creates a builder extracting from `world` with the given type registry.
+	pub fn from_world_with_type_registry(world: &'w World, type_registry: TypeRegistryArc) -> Self {
+		DynamicSceneBuilder {
+			world,
+			type_registry,
+			entities: BTreeSet::new(),
+			extract_resources: false,
+			component_filter: SceneFilter::default(),
+			resource_filter: SceneFilter::default(),
+			changed_since: None,
+		}
+	}
+
+	/// Replaces the filter of the extracted component types.
+	pub fn with_filter(mut self, filter: SceneFilter) -> Self {
+		self.component_filter = filter;
+		self
+	}
+
+	/// Replaces the filter of the extracted resource types.
+	pub fn with_resource_filter(mut self, filter: SceneFilter) -> Self {
+		self.resource_filter = filter;
+		self
+	}
+
+	/// Allows the component `T`, see [`SceneFilter::allow`].
+	pub fn allow<T: Component>(mut self) -> Self {
+		self.component_filter = self.component_filter.allow::<T>();
+		self
+	}
+
+	/// Denies the component `T`, see [`SceneFilter::deny`].
+	pub fn deny<T: Component>(mut self) -> Self {
+		self.component_filter = self.component_filter.deny::<T>();
+		self
+	}
+
+	/// Allows the resource `T`, see [`SceneFilter::allow`].
+	pub fn allow_resource<T: Resource>(mut self) -> Self {
+		self.resource_filter = self.resource_filter.allow::<T>();
+		self
+	}
+
+	/// Denies the resource `T`, see [`SceneFilter::deny`].
+	pub fn deny_resource<T: Resource>(mut self) -> Self {
+		self.resource_filter = self.resource_filter.deny::<T>();
+		self
+	}
+
+	/// Only extracts the components and resources changed since the change tick `tick`, such as a
+	/// [`World::read_change_tick`] recorded at the previous save.
+	///
+	/// Entities without changed components are still extracted, with no components.
+	pub fn changed_since(mut self, tick: u32) -> Self {
+		self.changed_since = Some(tick);
+		self
+	}
+
+	/// Extracts the given entity. Entities that don't exist are ignored.
+	pub fn extract_entity(self, entity: Entity) -> Self {
+		self.extract_entities(std::iter::once(entity))
+	}
+
+	/// Extracts the given entities, for example the entities of a query.
+	pub fn extract_entities(mut self, entities: impl IntoIterator<Item = Entity>) -> Self {
+		self.entities.extend(entities);
+		self
+	}
+
+	/// Extracts the entity `root` and all its descendants.
+	pub fn extract_hierarchy(mut self, root: Entity) -> Self {
+		let mut stack = vec![root];
+		while let Some(entity) = stack.pop() {
+			if !self.entities.insert(entity) {
+				continue;
+			}
+			if let Some(children) = self.world.get::<Children>(entity) {
+				stack.extend(children.iter().copied());
+			}
+		}
+		self
+	}
+
+	/// Extracts the resources of the world registered with `#[reflect(Resource)]`.
+	pub fn extract_resources(mut self) -> Self {
+		self.extract_resources = true;
+		self
+	}
+
+	/// Builds the [`DynamicScene`] of the extracted entities and resources.
+	pub fn build(self) -> DynamicScene {
+		let world = self.world;
+		let type_registry = self.type_registry.read();
+		let change_tick = world.read_change_tick();
+		let is_changed = |ticks: Option<&ComponentTicks>| match (self.changed_since, ticks) {
+			(None, _) => true,
+			(Some(tick), Some(ticks)) => ticks.is_changed(tick, change_tick),
+			(Some(_), None) => false,
+		};
+
+		let mut scene = DynamicScene::default();
+		for &entity in &self.entities {
+			let entity_ref = match world.get_entity(entity) {
+				Some(entity_ref) => entity_ref,
+				None => continue,
+			};
+
+			// Add each reflection-powered component passing the filters.
+			let mut components = Vec::new();
+			for component_id in entity_ref.archetype().components() {
+				let type_id = match world
+					.components()
+					.get_info(component_id)
+					.and_then(|info| info.type_id())
+				{
+					Some(type_id) => type_id,
+					None => continue,
+				};
+				if !self.component_filter.is_allowed_by_id(type_id)
+					|| !is_changed(entity_ref.get_change_ticks_by_id(component_id))
+				{
+					continue;
+				}
+				let component = type_registry
+					.get(type_id)
+					.and_then(|registration| registration.data::<ReflectComponent>())
+					.and_then(|reflect_component| reflect_component.reflect(world, entity));
+				if let Some(component) = component {
+					components.push(component.clone_value());
+				}
+			}
+
+			scene.entities.push(DynamicEntity {
+				entity: entity.id(),
+				components,
+			});
+		}
+
+		if self.extract_resources {
+			// Add each reflection-powered resource passing the filters.
+			for registration in type_registry.iter() {
+				let type_id = registration.type_id();
+				if !self.resource_filter.is_allowed_by_id(type_id) {
+					continue;
+				}
+				let ticks = world
+					.components()
+					.get_resource_id(type_id)
+					.and_then(|component_id| world.get_resource_change_ticks_by_id(component_id));
+				if !is_changed(ticks) {
+					continue;
+				}
+				let resource = registration
+					.data::<ReflectResource>()
+					.and_then(|reflect_resource| reflect_resource.reflect(world));
+				if let Some(resource) = resource {
+					scene.resources.push(resource.clone_value());
+				}
+			}
+			// Sort the resources by type name so they are serialized in a stable order.
+			scene
+				.resources
+				.sort_by(|a, b| a.type_name().cmp(b.type_name()));
+		}
+
+		scene
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{DynamicEntity, DynamicSceneBuilder, SceneFilter};
+	use bevy_ecs::{
+		component::Component,
+		entity::Entity,
+		reflect::{ReflectComponent, ReflectResource},
+		world::World,
+	};
+	use bevy_hierarchy::BuildWorldChildren;
+	use bevy_reflect::{Reflect, TypeRegistryArc};
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Health(u32);
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Armor(u32);
+
+	#[derive(Reflect, Default)]
+	#[reflect(Resource)]
+	struct Score(u32);
+
+	#[derive(Reflect, Default)]
+	#[reflect(Resource)]
+	struct Settings(u32);
+
+	fn world() -> World {
+		let registry = TypeRegistryArc::default();
+		{
+			let mut registry = registry.write();
+			registry.register::<Health>();
+			registry.register::<Armor>();
+			registry.register::<Score>();
+			registry.register::<Settings>();
+		}
+		let mut world = World::new();
+		world.insert_resource(registry);
+		world.insert_resource(Score(1));
+		world.insert_resource(Settings(2));
+		world
+	}
+
+	/// The type names of the components of an extracted entity.
+	fn component_names(entity: &DynamicEntity) -> Vec<&str> {
+		entity
+			.components
+			.iter()
+			.map(|component| component.type_name())
+			.collect()
+	}
+
+	#[test]
+	fn scene_filter() {
+		let filter = SceneFilter::default();
+		assert!(filter.is_allowed::<Health>());
+
+		let filter = filter.allow::<Health>();
+		assert_eq!(
+			filter,
+			SceneFilter::Allowlist(
+				[std::any::TypeId::of::<Health>()]
+					.into_iter()
+					.collect()
+			)
+		);
+		assert!(filter.is_allowed::<Health>());
+		assert!(!filter.is_allowed::<Armor>());
+		assert!(!filter.deny::<Health>().is_allowed::<Health>());
+
+		let filter = SceneFilter::default().deny::<Health>();
+		assert!(!filter.is_allowed::<Health>());
+		assert!(filter.is_allowed::<Armor>());
+		assert!(filter.allow::<Health>().is_allowed::<Health>());
+
+		assert!(SceneFilter::allow_all().is_allowed::<Health>());
+		assert!(!SceneFilter::deny_all().is_allowed::<Health>());
+	}
+
+	#[test]
+	fn extract_entities() {
+		let mut world = world();
+		let entity = world
+			.spawn()
+			.insert(Health(10))
+			.insert(Armor(5))
+			.id();
+		let other = world.spawn().insert(Health(20)).id();
+
+		let scene = DynamicSceneBuilder::from_world(&world)
+			.extract_entity(entity)
+			.extract_entity(Entity::from_raw(100))
+			.build();
+		assert_eq!(scene.entities.len(), 1);
+		assert_eq!(scene.entities[0].entity, entity.id());
+		assert_eq!(scene.entities[0].components.len(), 2);
+		assert!(scene.resources.is_empty());
+
+		let scene = DynamicSceneBuilder::from_world(&world)
+			.extract_entities([entity, other])
+			.deny::<Armor>()
+			.build();
+		assert_eq!(scene.entities.len(), 2);
+		for entity in &scene.entities {
+			assert_eq!(component_names(entity), [std::any::type_name::<Health>()]);
+		}
+
+		let scene = DynamicSceneBuilder::from_world(&world)
+			.allow::<Armor>()
+			.extract_entity(entity)
+			.build();
+		assert_eq!(
+			component_names(&scene.entities[0]),
+			[std::any::type_name::<Armor>()]
+		);
+	}
+
+	#[test]
+	fn changed_since() {
+		let mut world = world();
+		let changed = world
+			.spawn()
+			.insert(Health(10))
+			.insert(Armor(5))
+			.id();
+		let unchanged = world.spawn().insert(Health(20)).id();
+		let tick = world.read_change_tick();
+		world.increment_change_tick();
+		world.get_mut::<Armor>(changed).unwrap().0 = 6;
+		world.resource_mut::<Score>().0 = 2;
+
+		let scene = DynamicSceneBuilder::from_world(&world)
+			.changed_since(tick)
+			.extract_entities([changed, unchanged])
+			.extract_resources()
+			.build();
+		assert_eq!(scene.entities.len(), 2);
+		let components: Vec<_> = scene
+			.entities
+			.iter()
+			.map(|entity| (entity.entity, component_names(entity)))
+			.collect();
+		assert!(components.contains(&(changed.id(), vec![std::any::type_name::<Armor>()])));
+		// the unchanged entity is still extracted, without components
+		assert!(components.contains(&(unchanged.id(), Vec::new())));
+		let resources: Vec<_> = scene
+			.resources
+			.iter()
+			.map(|resource| resource.type_name())
+			.collect();
+		assert_eq!(resources, [std::any::type_name::<Score>()]);
+	}
+
+	#[test]
+	fn extract_hierarchy() {
+		let mut world = world();
+		let grandchild = world.spawn().insert(Health(3)).id();
+		let child = world
+			.spawn()
+			.insert(Health(2))
+			.push_children(&[grandchild])
+			.id();
+		let root = world
+			.spawn()
+			.insert(Health(1))
+			.push_children(&[child])
+			.id();
+		world.spawn().insert(Health(4));
+
+		let scene = DynamicSceneBuilder::from_world(&world)
+			.extract_hierarchy(root)
+			.build();
+		let mut entities: Vec<u32> = scene
+			.entities
+			.iter()
+			.map(|entity| entity.entity)
+			.collect();
+		entities.sort_unstable();
+		let mut expected = vec![root.id(), child.id(), grandchild.id()];
+		expected.sort_unstable();
+		assert_eq!(entities, expected);
+	}
+
+	#[test]
+	fn resource_filter() {
+		let world = world();
+
+		let scene = DynamicSceneBuilder::from_world(&world)
+			.extract_resources()
+			.build();
+		let resources: Vec<_> = scene
+			.resources
+			.iter()
+			.map(|resource| resource.type_name())
+			.collect();
+		// sorted by type name
+		assert_eq!(
+			resources,
+			[
+				std::any::type_name::<Score>(),
+				std::any::type_name::<Settings>()
+			]
+		);
+
+		let scene = DynamicSceneBuilder::from_world(&world)
+			.deny_resource::<Score>()
+			.extract_resources()
+			.build();
+		assert_eq!(scene.resources.len(), 1);
+		assert_eq!(
+			scene.resources[0].type_name(),
+			std::any::type_name::<Settings>()
+		);
+
+		let scene = DynamicSceneBuilder::from_world(&world)
+			.with_resource_filter(SceneFilter::deny_all())
+			.extract_resources()
+			.build();
+		assert!(scene.resources.is_empty());
+	}
+}
@@ -1,19 +1,28 @@
 mod bundle;
 mod dynamic_scene;
+mod dynamic_scene_builder;
 mod scene;
+mod scene_filter;
 mod scene_loader;
+mod scene_saver;
 mod scene_spawner;
 pub mod serde;
 
 pub use bundle::*;
 pub use dynamic_scene::*;
+pub use dynamic_scene_builder::*;
 pub use scene::*;
+pub use scene_filter::*;
 pub use scene_loader::*;
+pub use scene_saver::*;
 pub use scene_spawner::*;
 
 pub mod prelude {
 	#[doc(hidden)]
-	pub use crate::{DynamicScene, DynamicSceneBundle, Scene, SceneBundle, SceneSpawner};
+	pub use crate::{
+		DynamicScene, DynamicSceneBuilder, DynamicSceneBundle, Scene, SceneBundle, SceneFilter,
+		SceneSpawner,
+	};
 }
 
 use bevy_app::prelude::*;
@@ -28,7 +37,10 @@ impl Plugin for ScenePlugin {
 		app.add_asset::<DynamicScene>();
 		app.add_asset::<Scene>();
 		app.init_asset_loader::<SceneLoader>();
+		app.init_asset_saver::<DynamicSceneSaver>();
+		app.init_asset_saver::<SceneSaver>();
 		app.init_resource::<SceneSpawner>();
+		app.add_event::<SceneInstanceReady>();
 		app.add_system_to_stage(
 			CoreStage::PreUpdate,
 			scene_spawner_system.exclusive_system().at_end(),
@@ -0,0 +1,85 @@
+use bevy_utils::HashSet;
+use std::any::{Any, TypeId};
+
+/// A filter of the component or resource types extracted into a
+/// [`DynamicScene`](crate::DynamicScene) by a [`DynamicSceneBuilder`](crate::DynamicSceneBuilder).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SceneFilter {
+	/// Every type is allowed, until a type is allowed or denied explicitly.
+	#[default]
+	Unset,
+	/// Only the types in the list are allowed.
+	Allowlist(HashSet<TypeId>),
+	/// Every type but the types in the list is allowed.
+	Denylist(HashSet<TypeId>),
+}
+
+impl SceneFilter {
+	/// Creates a filter allowing every type.
+	pub fn allow_all() -> Self {
+		SceneFilter::Denylist(HashSet::default())
+	}
+
+	/// Creates a filter denying every type.
+	pub fn deny_all() -> Self {
+		SceneFilter::Allowlist(HashSet::default())
+	}
+
+	/// Allows the type `T`.
+	///
+	/// An unset filter becomes an allowlist of `T`, while a denylist no longer denies `T`.
+	pub fn allow<T: Any>(self) -> Self {
+		self.allow_by_id(TypeId::of::<T>())
+	}
+
+	/// Allows the type with the given [`TypeId`], see [`allow`](Self::allow).
+	pub fn allow_by_id(self, type_id: TypeId) -> Self {
+		match self {
+			SceneFilter::Unset => SceneFilter::Allowlist([type_id].into_iter().collect()),
+			SceneFilter::Allowlist(mut list) => {
+				list.insert(type_id);
+				SceneFilter::Allowlist(list)
+			},
+			SceneFilter::Denylist(mut list) => {
+				list.remove(&type_id);
+				SceneFilter::Denylist(list)
+			},
+		}
+	}
+
+	/// Denies the type `T`.
+	///
+	/// An unset filter becomes a denylist of `T`, while an allowlist no longer allows `T`.
+	pub fn deny<T: Any>(self) -> Self {
+		self.deny_by_id(TypeId::of::<T>())
+	}
+
+	/// Denies the type with the given [`TypeId`], see [`deny`](Self::deny).
+	pub fn deny_by_id(self, type_id: TypeId) -> Self {
+		match self {
+			SceneFilter::Unset => SceneFilter::Denylist([type_id].into_iter().collect()),
+			SceneFilter::Allowlist(mut list) => {
+				list.remove(&type_id);
+				SceneFilter::Allowlist(list)
+			},
+			SceneFilter::Denylist(mut list) => {
+				list.insert(type_id);
+				SceneFilter::Denylist(list)
+			},
+		}
+	}
+
+	/// Returns `true` if the type `T` passes the filter.
+	pub fn is_allowed<T: Any>(&self) -> bool {
+		self.is_allowed_by_id(TypeId::of::<T>())
+	}
+
+	/// Returns `true` if the type with the given [`TypeId`] passes the filter.
+	pub fn is_allowed_by_id(&self, type_id: TypeId) -> bool {
+		match self {
+			SceneFilter::Unset => true,
+			SceneFilter::Allowlist(list) => list.contains(&type_id),
+			SceneFilter::Denylist(list) => !list.contains(&type_id),
+		}
+	}
+}
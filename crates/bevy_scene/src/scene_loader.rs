@@ -1,6 +1,6 @@
 use crate::serde::SceneDeserializer;
 use anyhow::Result;
-use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_asset::{AssetLoader, AssetPath, LoadContext, LoadedAsset};
 use bevy_ecs::world::{FromWorld, World};
 use bevy_reflect::TypeRegistryArc;
 use bevy_utils::BoxedFuture;
@@ -21,6 +21,8 @@ impl FromWorld for SceneLoader {
 }
 
 impl AssetLoader for SceneLoader {
+	type Settings = ();
+
 	fn load<'a>(
 		&'a self,
 		bytes: &'a [u8],
@@ -31,8 +33,19 @@ impl AssetLoader for SceneLoader {
 			let scene_deserializer = SceneDeserializer {
 				type_registry: &*self.type_registry.read(),
 			};
-			let scene = scene_deserializer.deserialize(&mut deserializer)?;
-			load_context.set_default_asset(LoadedAsset::from(scene));
+			let mut scene = scene_deserializer.deserialize(&mut deserializer)?;
+			// the nested scenes are loaded with the scene
+			let mut dependencies = Vec::new();
+			for instance in &mut scene.instances {
+				let path = AssetPath::from(&instance.path);
+				instance.scene = load_context.get_handle(path.clone());
+				dependencies.push(path.to_owned());
+			}
+			let mut scene = LoadedAsset::from(scene);
+			for dependency in dependencies {
+				scene.add_dependency(dependency);
+			}
+			load_context.set_default_asset(scene);
 			Ok(())
 		})
 	}
@@ -0,0 +1,68 @@
+use crate::{DynamicScene, Scene};
+use anyhow::Result;
+use bevy_asset::AssetSaver;
+use bevy_ecs::world::{FromWorld, World};
+use bevy_reflect::TypeRegistryArc;
+
+/// Saves [`DynamicScene`]s as `.scn.ron` files, loaded by the [`SceneLoader`](crate::SceneLoader).
+#[derive(Debug)]
+pub struct DynamicSceneSaver {
+	type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for DynamicSceneSaver {
+	fn from_world(world: &mut World) -> Self {
+		let type_registry = world.resource::<TypeRegistryArc>();
+		DynamicSceneSaver {
+			type_registry: (*type_registry).clone(),
+		}
+	}
+}
+
+impl AssetSaver for DynamicSceneSaver {
+	type Asset = DynamicScene;
+
+	fn save(&self, scene: &DynamicScene) -> Result<Vec<u8>> {
+		Ok(
+			scene
+				.serialize_ron(&self.type_registry)?
+				.into_bytes(),
+		)
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["scn", "scn.ron"]
+	}
+}
+
+/// Saves [`Scene`]s as `.scn.ron` files, which are loaded back as [`DynamicScene`]s.
+#[derive(Debug)]
+pub struct SceneSaver {
+	type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for SceneSaver {
+	fn from_world(world: &mut World) -> Self {
+		let type_registry = world.resource::<TypeRegistryArc>();
+		SceneSaver {
+			type_registry: (*type_registry).clone(),
+		}
+	}
+}
+
+impl AssetSaver for SceneSaver {
+	type Asset = Scene;
+
+	fn save(&self, scene: &Scene) -> Result<Vec<u8>> {
+		let scene = DynamicScene::from_scene(scene, &self.type_registry);
+		Ok(
+			scene
+				.serialize_ron(&self.type_registry)?
+				.into_bytes(),
+		)
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["scn", "scn.ron"]
+	}
+}
@@ -1,21 +1,46 @@
-use crate::{DynamicScene, Scene};
-use bevy_asset::{AssetEvent, Assets, Handle};
+use crate::{DynamicScene, Scene, SceneInstance, SceneOverride};
+use bevy_asset::{AssetEvent, AssetServer, Assets, Handle, HandleId, HandleUntyped, LoadState};
 use bevy_ecs::{
+	component::Component,
 	entity::{Entity, EntityMap},
 	event::{Events, ManualEventReader},
 	reflect::{ReflectComponent, ReflectMapEntities},
 	system::Command,
 	world::{Mut, World},
 };
-use bevy_hierarchy::{AddChild, Parent};
-use bevy_reflect::TypeRegistryArc;
-use bevy_utils::{tracing::error, HashMap};
+use bevy_hierarchy::{despawn_with_children_recursive, AddChild, BuildWorldChildren, Parent};
+use bevy_reflect::{TypeRegistry, TypeRegistryArc};
+use bevy_utils::{
+	tracing::{error, warn},
+	HashMap, HashSet,
+};
 use thiserror::Error;
 use uuid::Uuid;
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 struct InstanceInfo {
 	entity_map: EntityMap,
+	/// The entity the instance was spawned as a child of, once it is added to its children.
+	parent: Option<Entity>,
+	/// The instances of the scenes nested in a dynamic scene, in the order of
+	/// [`DynamicScene::instances`].
+	nested_instances: Vec<InstanceInfo>,
+}
+
+impl InstanceInfo {
+	/// Gets the entities of the instance and of its nested instances.
+	fn entities(&self) -> Vec<Entity> {
+		let mut entities = Vec::new();
+		self.collect_entities(&mut entities);
+		entities
+	}
+
+	fn collect_entities(&self, entities: &mut Vec<Entity>) {
+		entities.extend(self.entity_map.values());
+		for nested_instance in &self.nested_instances {
+			nested_instance.collect_entities(entities);
+		}
+	}
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -27,6 +52,30 @@ impl InstanceId {
 	}
 }
 
+/// Event sent once when a scene instance is spawned, after its entities were added to the
+/// children of its parent.
+///
+/// Instances spawned by the [`scene_spawner_system`] send the event the same frame, instances
+/// spawned with the `*_sync` methods of the [`SceneSpawner`] the next time the system runs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SceneInstanceReady {
+	/// The entity the instance was spawned as a child of, such as the entity of a
+	/// [`SceneBundle`](crate::SceneBundle).
+	pub parent: Option<Entity>,
+	pub instance_id: InstanceId,
+}
+
+/// Links an entity spawned from a scene to its scene instance.
+///
+/// Every entity of a scene instance, including the entities of the scenes nested in it, has this
+/// component.
+#[derive(Component, Clone, Debug)]
+pub struct SceneInstanceLink {
+	pub instance_id: InstanceId,
+	/// A weak handle to the [`Scene`] or [`DynamicScene`] the instance was spawned from.
+	pub scene: HandleUntyped,
+}
+
 #[derive(Default)]
 pub struct SceneSpawner {
 	spawned_scenes: HashMap<Handle<Scene>, Vec<InstanceId>>,
@@ -38,26 +87,44 @@ pub struct SceneSpawner {
 	scenes_to_despawn: Vec<Handle<DynamicScene>>,
 	instances_to_despawn: Vec<InstanceId>,
 	scenes_with_parent: Vec<(InstanceId, Entity)>,
+	dynamic_scenes_to_update: Vec<Handle<DynamicScene>>,
+	instances_ready: Vec<InstanceId>,
 }
 
 #[derive(Error, Debug)]
 pub enum SceneSpawnError {
 	#[error("scene contains the unregistered component `{type_name}`. consider adding `#[reflect(Component)]` to your type")]
 	UnregisteredComponent { type_name: String },
+	#[error("scene contains the unregistered resource `{type_name}`. consider adding `#[reflect(Resource)]` to your type")]
+	UnregisteredResource { type_name: String },
 	#[error("scene contains the unregistered type `{type_name}`. consider registering the type using `app.register_type::<T>()`")]
 	UnregisteredType { type_name: String },
 	#[error("scene does not exist")]
 	NonExistentScene { handle: Handle<DynamicScene> },
 	#[error("scene does not exist")]
 	NonExistentRealScene { handle: Handle<Scene> },
+	#[error("scene is nested in itself")]
+	RecursiveScene { handle: Handle<DynamicScene> },
+	#[error("scene contains a `{type_name}` referring to the entity {entity:?}, which isn't part of the scene")]
+	UnmappedEntity { type_name: String, entity: Entity },
+	#[error(
+		"failed to override `{path}` of the component `{type_name}` of scene entity {entity}: {reason}"
+	)]
+	InvalidOverride {
+		entity: u32,
+		type_name: String,
+		path: String,
+		reason: String,
+	},
 }
 
 impl SceneSpawner {
-	pub fn spawn_dynamic(&mut self, scene_handle: Handle<DynamicScene>) {
+	pub fn spawn_dynamic(&mut self, scene_handle: Handle<DynamicScene>) -> InstanceId {
 		let instance_id = InstanceId::new();
 		self
 			.dynamic_scenes_to_spawn
 			.push((scene_handle, instance_id));
+		instance_id
 	}
 
 	pub fn spawn_dynamic_as_child(
@@ -98,6 +165,9 @@ impl SceneSpawner {
 		self.scenes_to_despawn.push(scene_handle);
 	}
 
+	/// Despawns a scene instance, or cancels its spawning if it isn't spawned yet.
+	///
+	/// See [`despawn_instance_sync`](Self::despawn_instance_sync).
 	pub fn despawn_instance(&mut self, instance_id: InstanceId) {
 		self.instances_to_despawn.push(instance_id);
 	}
@@ -118,10 +188,56 @@ impl SceneSpawner {
 		Ok(())
 	}
 
+	/// Despawns the entities of a scene instance with their descendants, removing them from the
+	/// children of their parents, or cancels the spawning of the instance if it isn't spawned yet.
+	///
+	/// The [`SceneInstance`] component of the entity the instance was spawned as a child of is
+	/// removed too.
 	pub fn despawn_instance_sync(&mut self, world: &mut World, instance_id: &InstanceId) {
+		self
+			.dynamic_scenes_to_spawn
+			.retain(|(_, id)| id != instance_id);
+		self
+			.scenes_to_spawn
+			.retain(|(_, id)| id != instance_id);
+		self
+			.scenes_with_parent
+			.retain(|(id, _)| id != instance_id);
+		self
+			.instances_ready
+			.retain(|id| id != instance_id);
+		for instance_ids in self
+			.spawned_scenes
+			.values_mut()
+			.chain(self.spawned_dynamic_scenes.values_mut())
+		{
+			instance_ids.retain(|id| id != instance_id);
+		}
+
 		if let Some(instance) = self.spawned_instances.remove(instance_id) {
-			for entity in instance.entity_map.values() {
-				let _ = world.despawn(entity);
+			let entities: HashSet<Entity> = instance.entities().into_iter().collect();
+			for &entity in &entities {
+				// Despawn the roots of the instance, whose parents are not in the instance, with
+				// their descendants.
+				let is_root = world
+					.get::<Parent>(entity)
+					.map(|parent| !entities.contains(parent))
+					.unwrap_or(true);
+				if is_root && world.get_entity(entity).is_some() {
+					despawn_with_children_recursive(world, entity);
+				}
+			}
+
+			if let Some(parent) = instance.parent {
+				let is_instance_parent = world
+					.get::<SceneInstance>(parent)
+					.map(|scene_instance| **scene_instance == *instance_id)
+					.unwrap_or(false);
+				if is_instance_parent {
+					world
+						.entity_mut(parent)
+						.remove::<SceneInstance>();
+				}
 			}
 		}
 	}
@@ -130,36 +246,200 @@ impl SceneSpawner {
 		&mut self,
 		world: &mut World,
 		scene_handle: &Handle<DynamicScene>,
-	) -> Result<(), SceneSpawnError> {
-		let mut entity_map = EntityMap::default();
-		Self::spawn_dynamic_internal(world, scene_handle, &mut entity_map)?;
+	) -> Result<InstanceId, SceneSpawnError> {
+		let mut instance_info = InstanceInfo::default();
+		Self::spawn_dynamic_internal(world, scene_handle, &mut instance_info)?;
 		let instance_id = InstanceId::new();
+		self.add_dynamic_instance(world, scene_handle, instance_id, instance_info);
+		Ok(instance_id)
+	}
+
+	/// Records a spawned dynamic scene instance, linking its entities to it.
+	fn add_dynamic_instance(
+		&mut self,
+		world: &mut World,
+		scene_handle: &Handle<DynamicScene>,
+		instance_id: InstanceId,
+		instance_info: InstanceInfo,
+	) {
+		insert_instance_links(
+			world,
+			instance_id,
+			&scene_handle.clone_weak_untyped(),
+			&instance_info,
+		);
 		self
 			.spawned_instances
-			.insert(instance_id, InstanceInfo { entity_map });
+			.insert(instance_id, instance_info);
 		let spawned = self
 			.spawned_dynamic_scenes
 			.entry(scene_handle.clone())
 			.or_insert_with(Vec::new);
 		spawned.push(instance_id);
-		Ok(())
+		self.instances_ready.push(instance_id);
 	}
 
 	fn spawn_dynamic_internal(
 		world: &mut World,
 		scene_handle: &Handle<DynamicScene>,
-		entity_map: &mut EntityMap,
+		instance_info: &mut InstanceInfo,
 	) -> Result<(), SceneSpawnError> {
 		world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
-			scenes
-				.get(scene_handle)
-				.ok_or(SceneSpawnError::NonExistentScene {
-					handle: scene_handle.clone_weak(),
-				})?
-				.write_to_world(world, entity_map)
+			// nothing is written until the nested scenes are loaded too
+			Self::check_dynamic_scene(&scenes, scene_handle, &mut Vec::new())?;
+			let registry = world.resource::<TypeRegistryArc>().clone();
+			let type_registry = registry.read();
+			Self::write_dynamic_scene(
+				world,
+				&scenes,
+				scene_handle,
+				instance_info,
+				&[],
+				&type_registry,
+			)
 		})
 	}
 
+	/// Checks that a dynamic scene and the scenes nested in it are loaded, and that no scene is
+	/// nested in itself.
+	fn check_dynamic_scene(
+		scenes: &Assets<DynamicScene>,
+		scene_handle: &Handle<DynamicScene>,
+		ancestors: &mut Vec<HandleId>,
+	) -> Result<(), SceneSpawnError> {
+		if ancestors.contains(&scene_handle.id) {
+			return Err(SceneSpawnError::RecursiveScene {
+				handle: scene_handle.clone_weak(),
+			});
+		}
+		let scene = scenes
+			.get(scene_handle)
+			.ok_or(SceneSpawnError::NonExistentScene {
+				handle: scene_handle.clone_weak(),
+			})?;
+		ancestors.push(scene_handle.id);
+		for instance in &scene.instances {
+			Self::check_dynamic_scene(scenes, &instance.scene, ancestors)?;
+		}
+		ancestors.pop();
+		Ok(())
+	}
+
+	/// Returns `true` if `scene_handle` is `nested_handle` or nests it, possibly indirectly.
+	fn dynamic_scene_nests(
+		scenes: &Assets<DynamicScene>,
+		scene_handle: &Handle<DynamicScene>,
+		nested_handle: &Handle<DynamicScene>,
+		visited: &mut Vec<HandleId>,
+	) -> bool {
+		if scene_handle == nested_handle {
+			return true;
+		}
+		if visited.contains(&scene_handle.id) {
+			return false;
+		}
+		visited.push(scene_handle.id);
+		scenes
+			.get(scene_handle)
+			.into_iter()
+			.flat_map(|scene| &scene.instances)
+			.any(|instance| Self::dynamic_scene_nests(scenes, &instance.scene, nested_handle, visited))
+	}
+
+	/// Writes a dynamic scene and its nested scene instances to the world, applying the
+	/// `overrides` of the scene instance nesting it.
+	fn write_dynamic_scene(
+		world: &mut World,
+		scenes: &Assets<DynamicScene>,
+		scene_handle: &Handle<DynamicScene>,
+		instance_info: &mut InstanceInfo,
+		overrides: &[SceneOverride],
+		type_registry: &TypeRegistry,
+	) -> Result<(), SceneSpawnError> {
+		let scene = scenes
+			.get(scene_handle)
+			.ok_or(SceneSpawnError::NonExistentScene {
+				handle: scene_handle.clone_weak(),
+			})?;
+
+		// Detach the nested instances from their parents while the entities of the scene are
+		// written and mapped: the `Children` of a parent only map to the entities of the scene.
+		for (instance, nested_instance) in scene
+			.instances
+			.iter()
+			.zip(&instance_info.nested_instances)
+		{
+			if let Some(parent) = instance_parent(instance.parent, &instance_info.entity_map) {
+				remove_root_entities_from_children(world, parent, nested_instance);
+			}
+		}
+
+		scene.write_entities(world, &mut instance_info.entity_map, type_registry)?;
+
+		// Despawn the instances removed from the scene since it was last written.
+		if instance_info.nested_instances.len() > scene.instances.len() {
+			for removed_instance in instance_info
+				.nested_instances
+				.drain(scene.instances.len()..)
+			{
+				// detach the roots of the instance from their parent in the scene
+				let entities = removed_instance.entities();
+				let parents: Vec<Entity> = removed_instance
+					.entity_map
+					.values()
+					.filter_map(|entity| {
+						world
+							.get::<Parent>(entity)
+							.map(|parent| **parent)
+					})
+					.filter(|parent| !entities.contains(parent))
+					.collect();
+				for parent in parents {
+					remove_root_entities_from_children(world, parent, &removed_instance);
+				}
+				for entity in entities {
+					let _ = world.despawn(entity);
+				}
+			}
+		}
+		instance_info
+			.nested_instances
+			.resize_with(scene.instances.len(), InstanceInfo::default);
+
+		let mut parented_instances = Vec::new();
+		for (instance, nested_instance) in scene
+			.instances
+			.iter()
+			.zip(&mut instance_info.nested_instances)
+		{
+			Self::write_dynamic_scene(
+				world,
+				scenes,
+				&instance.scene,
+				nested_instance,
+				&instance.overrides,
+				type_registry,
+			)?;
+			if let Some(parent) = instance_parent(instance.parent, &instance_info.entity_map) {
+				parented_instances.push((parent, nested_instance.entity_map.values().collect()));
+			}
+		}
+
+		// Overrides are applied before mapping the entities, as they are written with the
+		// identifiers of the scene entities.
+		for scene_override in overrides {
+			if let Err(err) = scene_override.apply(world, &instance_info.entity_map, type_registry) {
+				warn!("{}", err);
+			}
+		}
+		scene.map_entities(world, &instance_info.entity_map, type_registry)?;
+
+		for (parent, entities) in parented_instances {
+			add_root_entities_as_children(world, parent, entities);
+		}
+		Ok(())
+	}
+
 	pub fn spawn_sync(
 		&mut self,
 		world: &mut World,
@@ -174,9 +454,7 @@ impl SceneSpawner {
 		scene_handle: Handle<Scene>,
 		instance_id: InstanceId,
 	) -> Result<InstanceId, SceneSpawnError> {
-		let mut instance_info = InstanceInfo {
-			entity_map: EntityMap::default(),
-		};
+		let mut instance_info = InstanceInfo::default();
 		let type_registry = world.resource::<TypeRegistryArc>().clone();
 		let type_registry = type_registry.read();
 		world.resource_scope(|world, scenes: Mut<Assets<Scene>>| {
@@ -222,6 +500,12 @@ impl SceneSpawner {
 						.unwrap();
 				}
 			}
+			insert_instance_links(
+				world,
+				instance_id,
+				&scene_handle.clone_weak_untyped(),
+				&instance_info,
+			);
 			self
 				.spawned_instances
 				.insert(instance_id, instance_info);
@@ -230,20 +514,74 @@ impl SceneSpawner {
 				.entry(scene_handle)
 				.or_insert_with(Vec::new);
 			spawned.push(instance_id);
+			self.instances_ready.push(instance_id);
 			Ok(instance_id)
 		})
 	}
 
+	/// Updates the spawned instances of the given dynamic scenes, and the instances of these
+	/// scenes nested in other spawned dynamic scenes, keeping the overrides of the nested
+	/// instances.
+	///
+	/// Only the nested instances of the changed scenes are written again in the dynamic scenes
+	/// nesting them, so the changes made to their other entities are kept.
 	pub fn update_spawned_scenes(
 		&mut self,
 		world: &mut World,
 		scene_handles: &[Handle<DynamicScene>],
 	) -> Result<(), SceneSpawnError> {
-		for scene_handle in scene_handles {
-			if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) {
-				for instance_id in spawned_instances {
-					if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-						Self::spawn_dynamic_internal(world, scene_handle, &mut instance_info.entity_map)?;
+		let scenes = world.resource::<Assets<DynamicScene>>();
+		let updated_scenes: Vec<Handle<DynamicScene>> = self
+			.spawned_dynamic_scenes
+			.keys()
+			.filter(|spawned_handle| {
+				scene_handles.iter().any(|scene_handle| {
+					Self::dynamic_scene_nests(scenes, spawned_handle, scene_handle, &mut Vec::new())
+				})
+			})
+			.map(Handle::clone_weak)
+			.collect();
+
+		for scene_handle in updated_scenes {
+			let spawned_instances = &self.spawned_dynamic_scenes[&scene_handle];
+			for instance_id in spawned_instances {
+				if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
+					let result = if scene_handles.contains(&scene_handle) {
+						Self::spawn_dynamic_internal(world, &scene_handle, instance_info)
+					} else {
+						Self::update_nested_instances(world, &scene_handle, instance_info, scene_handles)
+					};
+					match result {
+						// link the entities added to the scene since it was spawned
+						Ok(_) => insert_instance_links(
+							world,
+							*instance_id,
+							&scene_handle.clone_weak_untyped(),
+							instance_info,
+						),
+						// a newly nested scene isn't loaded yet, the changed scenes nesting it are
+						// updated again once it is
+						Err(SceneSpawnError::NonExistentScene { handle })
+							if is_scene_loading(world, &handle) =>
+						{
+							let scenes = world.resource::<Assets<DynamicScene>>();
+							for changed_handle in scene_handles {
+								if Self::dynamic_scene_nests(scenes, &scene_handle, changed_handle, &mut Vec::new())
+									&& !self
+										.dynamic_scenes_to_update
+										.contains(changed_handle)
+								{
+									self
+										.dynamic_scenes_to_update
+										.push(changed_handle.clone_weak());
+								}
+							}
+							break;
+						},
+						Err(err) => {
+							error!("failed to update the instances of a dynamic scene: {}", err);
+							break;
+						},
 					}
 				}
 			}
@@ -251,6 +589,81 @@ impl SceneSpawner {
 		Ok(())
 	}
 
+	/// Writes the instances of the `changed_handles` scenes nested in an instance of
+	/// `scene_handle` again, leaving its other entities untouched.
+	fn update_nested_instances(
+		world: &mut World,
+		scene_handle: &Handle<DynamicScene>,
+		instance_info: &mut InstanceInfo,
+		changed_handles: &[Handle<DynamicScene>],
+	) -> Result<(), SceneSpawnError> {
+		world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
+			for changed_handle in changed_handles {
+				// nothing is written until the scenes nested in the changed scene are loaded too
+				Self::check_dynamic_scene(&scenes, changed_handle, &mut Vec::new())?;
+			}
+			let registry = world.resource::<TypeRegistryArc>().clone();
+			let type_registry = registry.read();
+			Self::write_nested_instances(
+				world,
+				&scenes,
+				scene_handle,
+				instance_info,
+				changed_handles,
+				&type_registry,
+			)
+		})
+	}
+
+	/// Writes the instances of the `changed_handles` scenes nested in an instance of
+	/// `scene_handle` again, recursively, with their overrides.
+	fn write_nested_instances(
+		world: &mut World,
+		scenes: &Assets<DynamicScene>,
+		scene_handle: &Handle<DynamicScene>,
+		instance_info: &mut InstanceInfo,
+		changed_handles: &[Handle<DynamicScene>],
+		type_registry: &TypeRegistry,
+	) -> Result<(), SceneSpawnError> {
+		let scene = match scenes.get(scene_handle) {
+			Some(scene) => scene,
+			None => return Ok(()),
+		};
+		for (instance, nested_instance) in scene
+			.instances
+			.iter()
+			.zip(&mut instance_info.nested_instances)
+		{
+			if !changed_handles.contains(&instance.scene) {
+				Self::write_nested_instances(
+					world,
+					scenes,
+					&instance.scene,
+					nested_instance,
+					changed_handles,
+					type_registry,
+				)?;
+				continue;
+			}
+			let parent = instance_parent(instance.parent, &instance_info.entity_map);
+			if let Some(parent) = parent {
+				remove_root_entities_from_children(world, parent, nested_instance);
+			}
+			Self::write_dynamic_scene(
+				world,
+				scenes,
+				&instance.scene,
+				nested_instance,
+				&instance.overrides,
+				type_registry,
+			)?;
+			if let Some(parent) = parent {
+				add_root_entities_as_children(world, parent, nested_instance.entity_map.values().collect());
+			}
+		}
+		Ok(())
+	}
+
 	pub fn despawn_queued_scenes(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
 		let scenes_to_despawn = std::mem::take(&mut self.scenes_to_despawn);
 
@@ -272,24 +685,32 @@ impl SceneSpawner {
 		let scenes_to_spawn = std::mem::take(&mut self.dynamic_scenes_to_spawn);
 
 		for (scene_handle, instance_id) in scenes_to_spawn {
-			let mut entity_map = EntityMap::default();
+			let mut instance_info = InstanceInfo::default();
 
-			match Self::spawn_dynamic_internal(world, &scene_handle, &mut entity_map) {
+			match Self::spawn_dynamic_internal(world, &scene_handle, &mut instance_info) {
 				Ok(_) => {
-					self
-						.spawned_instances
-						.insert(instance_id, InstanceInfo { entity_map });
-					let spawned = self
-						.spawned_dynamic_scenes
-						.entry(scene_handle.clone())
-						.or_insert_with(Vec::new);
-					spawned.push(instance_id);
+					self.add_dynamic_instance(world, &scene_handle, instance_id, instance_info);
 				},
-				Err(SceneSpawnError::NonExistentScene { .. }) => {
+				// the scene or a scene nested in it isn't loaded yet
+				Err(SceneSpawnError::NonExistentScene { handle }) if is_scene_loading(world, &handle) => {
 					self
 						.dynamic_scenes_to_spawn
 						.push((scene_handle, instance_id));
 				},
+				Err(SceneSpawnError::NonExistentScene { .. }) => {
+					error!(
+						"failed to spawn a dynamic scene: a scene it nests failed to load or doesn't exist"
+					);
+					self
+						.scenes_with_parent
+						.retain(|(id, _)| *id != instance_id);
+				},
+				Err(err @ SceneSpawnError::RecursiveScene { .. }) => {
+					error!("failed to spawn a dynamic scene: {}", err);
+					self
+						.scenes_with_parent
+						.retain(|(id, _)| *id != instance_id);
+				},
 				Err(err) => return Err(err),
 			}
 		}
@@ -313,26 +734,9 @@ impl SceneSpawner {
 		let scenes_with_parent = std::mem::take(&mut self.scenes_with_parent);
 
 		for (instance_id, parent) in scenes_with_parent {
-			if let Some(instance) = self.spawned_instances.get(&instance_id) {
-				for entity in instance.entity_map.values() {
-					// Add the `Parent` component to the scene root, and update the `Children` component of
-					// the scene parent
-					if !world
-						.get_entity(entity)
-						// This will filter only the scene root entity, as all other from the
-						// scene have a parent
-						.map(|entity| entity.contains::<Parent>())
-						// Default is true so that it won't run on an entity that wouldn't exist anymore
-						// this case shouldn't happen anyway
-						.unwrap_or(true)
-					{
-						AddChild {
-							parent,
-							child: entity,
-						}
-						.write(world);
-					}
-				}
+			if let Some(instance) = self.spawned_instances.get_mut(&instance_id) {
+				add_root_entities_as_children(world, parent, instance.entities());
+				instance.parent = Some(parent);
 			} else {
 				self
 					.scenes_with_parent
@@ -341,6 +745,23 @@ impl SceneSpawner {
 		}
 	}
 
+	/// Sends a [`SceneInstanceReady`] event for each scene instance spawned since the last call,
+	/// if the world has the events.
+	pub(crate) fn send_instance_ready_events(&mut self, world: &mut World) {
+		let instances_ready = std::mem::take(&mut self.instances_ready);
+		if let Some(mut events) = world.get_resource_mut::<Events<SceneInstanceReady>>() {
+			for instance_id in instances_ready {
+				events.send(SceneInstanceReady {
+					parent: self
+						.spawned_instances
+						.get(&instance_id)
+						.and_then(|instance| instance.parent),
+					instance_id,
+				});
+			}
+		}
+	}
+
 	/// Check that an scene instance spawned previously is ready to use
 	pub fn instance_is_ready(&self, instance_id: InstanceId) -> bool {
 		self
@@ -356,7 +777,79 @@ impl SceneSpawner {
 		self
 			.spawned_instances
 			.get(&instance_id)
-			.map(|instance| instance.entity_map.values())
+			.map(|instance| instance.entities().into_iter())
+	}
+}
+
+/// Returns `true` if the [`AssetServer`] is loading the scene of `handle`, in which case spawning
+/// the scenes nesting it waits for it.
+fn is_scene_loading(world: &World, handle: &Handle<DynamicScene>) -> bool {
+	matches!(
+		world.get_resource::<AssetServer>(),
+		Some(asset_server) if asset_server.get_load_state(handle) == LoadState::Loading
+	)
+}
+
+/// Gets the entity a nested scene instance is a child of, given the `entity_map` of the instance
+/// nesting it.
+fn instance_parent(parent: Option<u32>, entity_map: &EntityMap) -> Option<Entity> {
+	entity_map.get(Entity::from_raw(parent?)).ok()
+}
+
+/// Removes the root entities of a nested scene instance from the children of `parent`.
+fn remove_root_entities_from_children(world: &mut World, parent: Entity, instance: &InstanceInfo) {
+	let children: Vec<Entity> = instance
+		.entity_map
+		.values()
+		.filter(|entity| {
+			world
+				.get::<Parent>(*entity)
+				.map(|entity_parent| **entity_parent == parent)
+				.unwrap_or(false)
+		})
+		.collect();
+	if let Some(mut parent) = world.get_entity_mut(parent) {
+		parent.remove_children(&children);
+	}
+}
+
+/// Inserts a [`SceneInstanceLink`] to the entities of a scene instance.
+fn insert_instance_links(
+	world: &mut World,
+	instance_id: InstanceId,
+	scene: &HandleUntyped,
+	instance_info: &InstanceInfo,
+) {
+	for entity in instance_info.entities() {
+		if let Some(mut entity) = world.get_entity_mut(entity) {
+			entity.insert(SceneInstanceLink {
+				instance_id,
+				scene: scene.clone_weak(),
+			});
+		}
+	}
+}
+
+/// Adds the root entities of a scene instance as children of `parent`.
+fn add_root_entities_as_children(world: &mut World, parent: Entity, entities: Vec<Entity>) {
+	for entity in entities {
+		// Add the `Parent` component to the scene root, and update the `Children` component of
+		// the scene parent
+		if !world
+			.get_entity(entity)
+			// This will filter only the scene root entity, as all other from the
+			// scene have a parent
+			.map(|entity| entity.contains::<Parent>())
+			// Default is true so that it won't run on an entity that wouldn't exist anymore
+			// this case shouldn't happen anyway
+			.unwrap_or(true)
+		{
+			AddChild {
+				parent,
+				child: entity,
+			}
+			.write(world);
+		}
 	}
 }
 
@@ -364,19 +857,14 @@ pub fn scene_spawner_system(world: &mut World) {
 	world.resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
 		let scene_asset_events = world.resource::<Events<AssetEvent<DynamicScene>>>();
 
-		let mut updated_spawned_scenes = Vec::new();
 		let scene_spawner = &mut *scene_spawner;
+		let mut updated_spawned_scenes = std::mem::take(&mut scene_spawner.dynamic_scenes_to_update);
 		for event in scene_spawner
 			.scene_asset_event_reader
 			.iter(scene_asset_events)
 		{
 			if let AssetEvent::Modified { handle } = event {
-				if scene_spawner
-					.spawned_dynamic_scenes
-					.contains_key(handle)
-				{
-					updated_spawned_scenes.push(handle.clone_weak());
-				}
+				updated_spawned_scenes.push(handle.clone_weak());
 			}
 		}
 
@@ -391,5 +879,272 @@ pub fn scene_spawner_system(world: &mut World) {
 			.update_spawned_scenes(world, &updated_spawned_scenes)
 			.unwrap();
 		scene_spawner.set_scene_instance_parent_sync(world);
+		scene_spawner.send_instance_ready_events(world);
 	});
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		DynamicEntity, DynamicScene, DynamicSceneInstance, InstanceId, SceneOverride, SceneSpawner,
+	};
+	use bevy_app::App;
+	use bevy_asset::{AddAsset, AssetPlugin, AssetServer, Assets, Handle, HandleId, MemoryAssetIo};
+	use bevy_ecs::{component::Component, entity::Entity, reflect::ReflectComponent, world::World};
+	use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
+	use bevy_reflect::Reflect;
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Health {
+		value: u32,
+	}
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Armor {
+		value: u32,
+	}
+
+	fn world() -> World {
+		let mut app = App::new();
+		app.insert_resource(AssetServer::from(MemoryAssetIo::new()));
+		app.add_plugin(AssetPlugin);
+		app.add_asset::<DynamicScene>();
+		app.register_type::<Health>();
+		app.register_type::<Armor>();
+		app.register_type::<Parent>();
+		app.register_type::<Children>();
+		std::mem::take(&mut app.world)
+	}
+
+	/// A scene with an entity nesting `prefab` as its child, overriding its health.
+	fn level_scene(prefab: Handle<DynamicScene>) -> DynamicScene {
+		DynamicScene {
+			entities: vec![DynamicEntity {
+				entity: 0,
+				components: vec![Box::new(Health { value: 1 })],
+			}],
+			instances: vec![DynamicSceneInstance {
+				path: "prefab.scn.ron".to_string(),
+				scene: prefab,
+				parent: Some(0),
+				overrides: vec![SceneOverride {
+					entity: 0,
+					component: std::any::type_name::<Health>().to_string(),
+					path: "value".to_string(),
+					value: Box::new(20u32),
+				}],
+			}],
+			..Default::default()
+		}
+	}
+
+	fn prefab_scene(armor: u32) -> DynamicScene {
+		DynamicScene {
+			entities: vec![DynamicEntity {
+				entity: 0,
+				components: vec![
+					Box::new(Health { value: 10 }),
+					Box::new(Armor { value: armor }),
+				],
+			}],
+			..Default::default()
+		}
+	}
+
+	/// Gets the root entity of a spawned level and the entity of its nested prefab.
+	fn level_entities(
+		world: &World,
+		spawner: &SceneSpawner,
+		instance_id: InstanceId,
+	) -> (Entity, Entity) {
+		let entities: Vec<Entity> = spawner
+			.iter_instance_entities(instance_id)
+			.unwrap()
+			.collect();
+		assert_eq!(entities.len(), 2);
+		let (children, roots): (Vec<Entity>, Vec<Entity>) = entities
+			.into_iter()
+			.partition(|entity| world.get::<Parent>(*entity).is_some());
+		(roots[0], children[0])
+	}
+
+	#[test]
+	fn spawn_nested_scene() {
+		let mut world = world();
+		let mut scenes = world.resource_mut::<Assets<DynamicScene>>();
+		let prefab = scenes.add(prefab_scene(5));
+		let level = scenes.add(level_scene(prefab.clone_weak()));
+
+		let mut spawner = SceneSpawner::default();
+		let instance_id = spawner
+			.spawn_dynamic_sync(&mut world, &level)
+			.unwrap();
+		let (root, child) = level_entities(&world, &spawner, instance_id);
+
+		assert_eq!(**world.get::<Parent>(child).unwrap(), root);
+		assert!(world
+			.get::<Children>(root)
+			.unwrap()
+			.contains(&child));
+		assert_eq!(world.get::<Health>(root).unwrap().value, 1);
+		// the health of the prefab is overridden
+		assert_eq!(world.get::<Health>(child).unwrap().value, 20);
+		assert_eq!(world.get::<Armor>(child).unwrap().value, 5);
+	}
+
+	#[test]
+	fn propagate_prefab_changes() {
+		let mut world = world();
+		let mut scenes = world.resource_mut::<Assets<DynamicScene>>();
+		let prefab = scenes.add(prefab_scene(5));
+		let level = scenes.add(level_scene(prefab.clone_weak()));
+
+		let mut spawner = SceneSpawner::default();
+		let instance_id = spawner
+			.spawn_dynamic_sync(&mut world, &level)
+			.unwrap();
+		let (root, child) = level_entities(&world, &spawner, instance_id);
+		// changes made at runtime to the entities of the level
+		world.get_mut::<Health>(root).unwrap().value = 99;
+
+		*world
+			.resource_mut::<Assets<DynamicScene>>()
+			.get_mut(&prefab)
+			.unwrap() = prefab_scene(7);
+		spawner
+			.update_spawned_scenes(&mut world, &[prefab.clone_weak()])
+			.unwrap();
+
+		assert_eq!(level_entities(&world, &spawner, instance_id), (root, child));
+		assert_eq!(world.get::<Armor>(child).unwrap().value, 7);
+		// the override is kept
+		assert_eq!(world.get::<Health>(child).unwrap().value, 20);
+		// only the prefab instance is written again
+		assert_eq!(world.get::<Health>(root).unwrap().value, 99);
+		assert!(world
+			.get::<Children>(root)
+			.unwrap()
+			.contains(&child));
+	}
+
+	#[test]
+	fn missing_nested_scene() {
+		let mut world = world();
+		let missing = Handle::weak(HandleId::random::<DynamicScene>());
+		let level = world
+			.resource_mut::<Assets<DynamicScene>>()
+			.add(level_scene(missing));
+
+		let mut spawner = SceneSpawner::default();
+		let instance_id = spawner.spawn_dynamic(level);
+		spawner.spawn_queued_scenes(&mut world).unwrap();
+
+		// the nested scene isn't being loaded, so spawning the level isn't retried
+		assert!(!spawner.instance_is_ready(instance_id));
+		assert!(spawner.dynamic_scenes_to_spawn.is_empty());
+	}
+
+	#[test]
+	fn recursive_scene() {
+		let mut world = world();
+		let level = Handle::weak(HandleId::random::<DynamicScene>());
+		world
+			.resource_mut::<Assets<DynamicScene>>()
+			.set_untracked(level.clone_weak(), level_scene(level.clone_weak()));
+		let prefab = world
+			.resource_mut::<Assets<DynamicScene>>()
+			.add(prefab_scene(5));
+
+		let mut spawner = SceneSpawner::default();
+		let recursive_id = spawner.spawn_dynamic(level);
+		let instance_id = spawner.spawn_dynamic(prefab);
+		spawner.spawn_queued_scenes(&mut world).unwrap();
+
+		// the recursive scene is skipped, the rest of the queue is spawned
+		assert!(!spawner.instance_is_ready(recursive_id));
+		assert!(spawner.instance_is_ready(instance_id));
+		assert!(spawner.dynamic_scenes_to_spawn.is_empty());
+	}
+
+	#[test]
+	fn remove_nested_scene() {
+		let mut world = world();
+		let mut scenes = world.resource_mut::<Assets<DynamicScene>>();
+		let prefab = scenes.add(prefab_scene(5));
+		let level = scenes.add(level_scene(prefab.clone_weak()));
+
+		let mut spawner = SceneSpawner::default();
+		let instance_id = spawner
+			.spawn_dynamic_sync(&mut world, &level)
+			.unwrap();
+		let (root, child) = level_entities(&world, &spawner, instance_id);
+
+		world
+			.resource_mut::<Assets<DynamicScene>>()
+			.get_mut(&level)
+			.unwrap()
+			.instances
+			.clear();
+		spawner
+			.update_spawned_scenes(&mut world, &[level.clone_weak()])
+			.unwrap();
+
+		assert!(world.get_entity(child).is_none());
+		// the root of the removed instance is no longer a child of its parent
+		assert!(!matches!(
+			world.get::<Children>(root),
+			Some(children) if children.contains(&child)
+		));
+	}
+
+	#[test]
+	fn despawn_instance_sync() {
+		let mut world = world();
+		let mut scenes = world.resource_mut::<Assets<DynamicScene>>();
+		let prefab = scenes.add(prefab_scene(5));
+		let level = scenes.add(level_scene(prefab.clone_weak()));
+		let parent = world.spawn().id();
+
+		let mut spawner = SceneSpawner::default();
+		let instance_id = spawner.spawn_dynamic_as_child(level, parent);
+		spawner.spawn_queued_scenes(&mut world).unwrap();
+		let (root, child) = level_entities(&world, &spawner, instance_id);
+		spawner.set_scene_instance_parent_sync(&mut world);
+		assert_eq!(**world.get::<Parent>(root).unwrap(), parent);
+		// an entity added at runtime to the nested instance
+		let descendant = world.spawn().id();
+		world
+			.entity_mut(child)
+			.push_children(&[descendant]);
+
+		spawner.despawn_instance_sync(&mut world, &instance_id);
+
+		assert!(!spawner.instance_is_ready(instance_id));
+		for entity in [root, child, descendant] {
+			assert!(world.get_entity(entity).is_none());
+		}
+		assert!(!matches!(
+			world.get::<Children>(parent),
+			Some(children) if !children.is_empty()
+		));
+	}
+
+	#[test]
+	fn despawn_queued_instance_sync() {
+		let mut world = world();
+		let level = world
+			.resource_mut::<Assets<DynamicScene>>()
+			.add(prefab_scene(5));
+
+		let mut spawner = SceneSpawner::default();
+		let instance_id = spawner.spawn_dynamic(level);
+		spawner.despawn_instance_sync(&mut world, &instance_id);
+		spawner.spawn_queued_scenes(&mut world).unwrap();
+
+		// the spawning of the instance is cancelled
+		assert!(!spawner.instance_is_ready(instance_id));
+		assert_eq!(world.query::<&Armor>().iter(&world).count(), 0);
+	}
+}
@@ -1,14 +1,19 @@
-use crate::{DynamicEntity, DynamicScene};
+use crate::{DynamicEntity, DynamicScene, DynamicSceneInstance, SceneOverride};
 use anyhow::Result;
+use bevy_asset::{AssetPath, Handle, HandleId};
 use bevy_reflect::{
 	serde::{ReflectDeserializer, ReflectSerializer},
-	Reflect, TypeRegistry, TypeRegistryArc,
+	Array, DynamicArray, DynamicList, DynamicMap, DynamicStruct, DynamicTuple, DynamicTupleStruct,
+	Map, MigrationError, Reflect, ReflectRef, Struct, Tuple, TupleStruct, TypeRegistry,
+	TypeRegistryArc,
 };
+use bevy_utils::HashMap;
 use serde::{
 	de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
 	ser::{SerializeSeq, SerializeStruct},
 	Deserialize, Serialize,
 };
+use std::collections::BTreeMap;
 
 pub struct SceneSerializer<'a> {
 	pub scene: &'a DynamicScene,
@@ -19,6 +24,73 @@ impl<'a> SceneSerializer<'a> {
 	pub fn new(scene: &'a DynamicScene, registry: &'a TypeRegistryArc) -> Self {
 		SceneSerializer { scene, registry }
 	}
+
+	/// Returns the versions of the types of the resources, components and overrides of the scene,
+	/// and of the values of their fields, with a version other than 0, see
+	/// [`TypeRegistration::version`](bevy_reflect::TypeRegistration::version).
+	fn versions(&self) -> BTreeMap<&'a str, u32> {
+		let registry = self.registry.read();
+		let values = self
+			.scene
+			.resources
+			.iter()
+			.chain(
+				self
+					.scene
+					.entities
+					.iter()
+					.flat_map(|entity| &entity.components),
+			)
+			.chain(
+				self
+					.scene
+					.instances
+					.iter()
+					.flat_map(|instance| &instance.overrides)
+					.map(|scene_override| &scene_override.value),
+			);
+		let mut versions = BTreeMap::new();
+		for value in values {
+			collect_versions(&registry, &**value, &mut versions);
+		}
+		versions
+	}
+}
+
+/// Adds the versions of the type of a value and of the types of the values of its fields,
+/// recursively, if they are other than 0.
+fn collect_versions<'a>(
+	registry: &TypeRegistry,
+	value: &'a dyn Reflect,
+	versions: &mut BTreeMap<&'a str, u32>,
+) {
+	let type_name = value.type_name();
+	if let Some(registration) = registry.get_with_name(type_name) {
+		if registration.version() > 0 {
+			versions.insert(type_name, registration.version());
+		}
+	}
+	match value.reflect_ref() {
+		ReflectRef::Struct(value) => value
+			.iter_fields()
+			.for_each(|field| collect_versions(registry, field, versions)),
+		ReflectRef::TupleStruct(value) => value
+			.iter_fields()
+			.for_each(|field| collect_versions(registry, field, versions)),
+		ReflectRef::Tuple(value) => value
+			.iter_fields()
+			.for_each(|field| collect_versions(registry, field, versions)),
+		ReflectRef::List(value) => value
+			.iter()
+			.for_each(|item| collect_versions(registry, item, versions)),
+		ReflectRef::Array(value) => value
+			.iter()
+			.for_each(|item| collect_versions(registry, item, versions)),
+		ReflectRef::Map(value) => value
+			.iter()
+			.for_each(|(_, item)| collect_versions(registry, item, versions)),
+		ReflectRef::Value(_) => {},
+	}
 }
 
 impl<'a> Serialize for SceneSerializer<'a> {
@@ -26,8 +98,45 @@ impl<'a> Serialize for SceneSerializer<'a> {
 	where
 		S: serde::Serializer,
 	{
-		let mut state = serializer.serialize_seq(Some(self.scene.entities.len()))?;
-		for entity in &self.scene.entities {
+		let mut state = serializer.serialize_struct(SCENE_STRUCT, 4)?;
+		state.serialize_field(SCENE_FIELD_VERSIONS, &self.versions())?;
+		state.serialize_field(
+			SCENE_FIELD_RESOURCES,
+			&ComponentsSerializer {
+				components: &self.scene.resources,
+				registry: self.registry,
+			},
+		)?;
+		state.serialize_field(
+			SCENE_FIELD_ENTITIES,
+			&EntitiesSerializer {
+				entities: &self.scene.entities,
+				registry: self.registry,
+			},
+		)?;
+		state.serialize_field(
+			SCENE_FIELD_INSTANCES,
+			&InstancesSerializer {
+				instances: &self.scene.instances,
+				registry: self.registry,
+			},
+		)?;
+		state.end()
+	}
+}
+
+pub struct EntitiesSerializer<'a> {
+	pub entities: &'a [DynamicEntity],
+	pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for EntitiesSerializer<'a> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+		for entity in self.entities {
 			state.serialize_element(&EntitySerializer {
 				entity,
 				registry: self.registry,
@@ -81,6 +190,94 @@ impl<'a> Serialize for ComponentsSerializer<'a> {
 	}
 }
 
+pub struct InstancesSerializer<'a> {
+	pub instances: &'a [DynamicSceneInstance],
+	pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for InstancesSerializer<'a> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		let mut state = serializer.serialize_seq(Some(self.instances.len()))?;
+		for instance in self.instances {
+			state.serialize_element(&InstanceSerializer {
+				instance,
+				registry: self.registry,
+			})?;
+		}
+		state.end()
+	}
+}
+
+pub struct InstanceSerializer<'a> {
+	pub instance: &'a DynamicSceneInstance,
+	pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for InstanceSerializer<'a> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		let mut state = serializer.serialize_struct(INSTANCE_STRUCT, 3)?;
+		state.serialize_field(INSTANCE_FIELD_SCENE, &self.instance.path)?;
+		state.serialize_field(INSTANCE_FIELD_PARENT, &self.instance.parent)?;
+		state.serialize_field(
+			INSTANCE_FIELD_OVERRIDES,
+			&OverridesSerializer {
+				overrides: &self.instance.overrides,
+				registry: self.registry,
+			},
+		)?;
+		state.end()
+	}
+}
+
+pub struct OverridesSerializer<'a> {
+	pub overrides: &'a [SceneOverride],
+	pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for OverridesSerializer<'a> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		let mut state = serializer.serialize_seq(Some(self.overrides.len()))?;
+		for scene_override in self.overrides {
+			state.serialize_element(&OverrideSerializer {
+				scene_override,
+				registry: self.registry,
+			})?;
+		}
+		state.end()
+	}
+}
+
+pub struct OverrideSerializer<'a> {
+	pub scene_override: &'a SceneOverride,
+	pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for OverrideSerializer<'a> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		let mut state = serializer.serialize_struct(OVERRIDE_STRUCT, 4)?;
+		state.serialize_field(OVERRIDE_FIELD_ENTITY, &self.scene_override.entity)?;
+		state.serialize_field(OVERRIDE_FIELD_COMPONENT, &self.scene_override.component)?;
+		state.serialize_field(OVERRIDE_FIELD_PATH, &self.scene_override.path)?;
+		state.serialize_field(
+			OVERRIDE_FIELD_VALUE,
+			&ReflectSerializer::new(&*self.scene_override.value, &self.registry.read()),
+		)?;
+		state.end()
+	}
+}
+
 pub struct SceneDeserializer<'a> {
 	pub type_registry: &'a TypeRegistry,
 }
@@ -92,10 +289,260 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
 	where
 		D: serde::Deserializer<'de>,
 	{
-		Ok(DynamicScene {
-			entities: deserializer.deserialize_seq(SceneEntitySeqVisitor {
-				type_registry: self.type_registry,
-			})?,
+		// scenes written before resources were added are a list of entities
+		deserializer.deserialize_any(SceneVisitor {
+			type_registry: self.type_registry,
+		})
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneField {
+	Versions,
+	Resources,
+	Entities,
+	Instances,
+}
+
+pub const SCENE_STRUCT: &str = "Scene";
+pub const SCENE_FIELD_VERSIONS: &str = "versions";
+pub const SCENE_FIELD_RESOURCES: &str = "resources";
+pub const SCENE_FIELD_ENTITIES: &str = "entities";
+pub const SCENE_FIELD_INSTANCES: &str = "instances";
+
+struct SceneVisitor<'a> {
+	pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
+	type Value = DynamicScene;
+
+	fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+		formatter.write_str("scene")
+	}
+
+	/// Deserializes the scenes written before resources were added, which only have a list of
+	/// entities.
+	fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: SeqAccess<'de>,
+	{
+		let entities = SceneEntitySeqVisitor {
+			type_registry: self.type_registry,
+		}
+		.visit_seq(seq)?;
+		let mut scene = DynamicScene {
+			entities,
+			..Default::default()
+		};
+		migrate_scene(self.type_registry, &HashMap::default(), &mut scene).map_err(Error::custom)?;
+		Ok(scene)
+	}
+
+	fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+	where
+		A: MapAccess<'de>,
+	{
+		let mut versions = None;
+		let mut resources = None;
+		let mut entities = None;
+		let mut instances = None;
+		while let Some(key) = map.next_key()? {
+			match key {
+				SceneField::Versions => {
+					if versions.is_some() {
+						return Err(Error::duplicate_field(SCENE_FIELD_VERSIONS));
+					}
+					versions = Some(map.next_value::<HashMap<String, u32>>()?);
+				},
+				SceneField::Resources => {
+					if resources.is_some() {
+						return Err(Error::duplicate_field(SCENE_FIELD_RESOURCES));
+					}
+					// resources are serialized like the components of an entity
+					resources = Some(map.next_value_seed(ComponentVecDeserializer {
+						registry: self.type_registry,
+					})?);
+				},
+				SceneField::Entities => {
+					if entities.is_some() {
+						return Err(Error::duplicate_field(SCENE_FIELD_ENTITIES));
+					}
+					entities = Some(map.next_value_seed(SceneEntitiesDeserializer {
+						type_registry: self.type_registry,
+					})?);
+				},
+				SceneField::Instances => {
+					if instances.is_some() {
+						return Err(Error::duplicate_field(SCENE_FIELD_INSTANCES));
+					}
+					instances = Some(map.next_value_seed(InstanceVecDeserializer {
+						type_registry: self.type_registry,
+					})?);
+				},
+			}
+		}
+
+		let mut scene = DynamicScene {
+			// scenes without resources or instances may leave them out
+			resources: resources.unwrap_or_default(),
+			entities: entities.ok_or(Error::missing_field(SCENE_FIELD_ENTITIES))?,
+			instances: instances.unwrap_or_default(),
+		};
+
+		// scenes written before types were versioned leave the versions out
+		let versions = versions.unwrap_or_default();
+		migrate_scene(self.type_registry, &versions, &mut scene).map_err(Error::custom)?;
+
+		Ok(scene)
+	}
+}
+
+/// Migrates the resources, components and overrides of a deserialized scene, see
+/// [`migrate_value`].
+///
+/// Nothing is migrated if the scene and the registry only know version 0 of their types and no
+/// type was renamed.
+fn migrate_scene(
+	registry: &TypeRegistry,
+	versions: &HashMap<String, u32>,
+	scene: &mut DynamicScene,
+) -> Result<(), MigrationError> {
+	if versions.is_empty()
+		&& !registry.has_type_aliases()
+		&& registry
+			.iter()
+			.all(|registration| registration.version() == 0)
+	{
+		return Ok(());
+	}
+	let values = scene
+		.resources
+		.iter_mut()
+		.chain(
+			scene
+				.entities
+				.iter_mut()
+				.flat_map(|entity| &mut entity.components),
+		)
+		.chain(
+			scene
+				.instances
+				.iter_mut()
+				.flat_map(|instance| &mut instance.overrides)
+				.map(|scene_override| &mut scene_override.value),
+		);
+	for value in values {
+		migrate_value(registry, versions, value)?;
+	}
+	Ok(())
+}
+
+/// Migrates a deserialized resource, component or override value and the values of its fields,
+/// recursively, to the current versions of their types, given the versions the scene was written
+/// with. Dynamic values written with a type alias are named after the current name of their
+/// type.
+///
+/// The fields are migrated before the value itself, so the migrations of a type see its fields
+/// in their current version. The keys of maps aren't migrated. Enums are reflected as values, so
+/// their own migrations apply but the values they contain aren't migrated.
+fn migrate_value(
+	registry: &TypeRegistry,
+	versions: &HashMap<String, u32>,
+	value: &mut Box<dyn Reflect>,
+) -> Result<(), MigrationError> {
+	migrate_fields(registry, versions, value)?;
+	let registration = match registry.get_with_name(value.type_name()) {
+		Some(registration) => registration,
+		// unregistered types are reported when the scene is spawned
+		None => return Ok(()),
+	};
+	let version = versions
+		.get(value.type_name())
+		.copied()
+		.unwrap_or_default();
+	if version != registration.version() {
+		*value = registration.migrate(value.clone_value(), version)?;
+	}
+	if let Some(dynamic_struct) = value.downcast_mut::<DynamicStruct>() {
+		dynamic_struct.set_name(registration.type_name().to_string());
+	} else if let Some(tuple_struct) = value.downcast_mut::<DynamicTupleStruct>() {
+		tuple_struct.set_name(registration.type_name().to_string());
+	}
+	Ok(())
+}
+
+/// Migrates the values of the fields of a deserialized dynamic value, see [`migrate_value`].
+fn migrate_fields(
+	registry: &TypeRegistry,
+	versions: &HashMap<String, u32>,
+	value: &mut Box<dyn Reflect>,
+) -> Result<(), MigrationError> {
+	let migrate = |field: &dyn Reflect| {
+		let mut field = field.clone_value();
+		migrate_value(registry, versions, &mut field).map(|_| field)
+	};
+	if let Some(value) = value.downcast_mut::<DynamicStruct>() {
+		let mut migrated = DynamicStruct::default();
+		migrated.set_name(value.name().to_string());
+		for (index, field) in value.iter_fields().enumerate() {
+			migrated.insert_boxed(value.name_at(index).unwrap(), migrate(field)?);
+		}
+		*value = migrated;
+	} else if let Some(value) = value.downcast_mut::<DynamicTupleStruct>() {
+		let mut migrated = DynamicTupleStruct::default();
+		migrated.set_name(value.name().to_string());
+		for field in value.iter_fields() {
+			migrated.insert_boxed(migrate(field)?);
+		}
+		*value = migrated;
+	} else if let Some(value) = value.downcast_mut::<DynamicTuple>() {
+		let mut migrated = DynamicTuple::default();
+		migrated.set_name(value.name().to_string());
+		for field in value.iter_fields() {
+			migrated.insert_boxed(migrate(field)?);
+		}
+		*value = migrated;
+	} else if let Some(value) = value.downcast_mut::<DynamicList>() {
+		let mut migrated = DynamicList::default();
+		migrated.set_name(value.name().to_string());
+		for item in value.iter() {
+			migrated.push_box(migrate(item)?);
+		}
+		*value = migrated;
+	} else if let Some(value) = value.downcast_mut::<DynamicArray>() {
+		let items = value
+			.iter()
+			.map(migrate)
+			.collect::<Result<Vec<_>, _>>()?;
+		let mut migrated = DynamicArray::new(items.into_boxed_slice());
+		migrated.set_name(value.name().to_string());
+		*value = migrated;
+	} else if let Some(value) = value.downcast_mut::<DynamicMap>() {
+		let mut migrated = DynamicMap::default();
+		migrated.set_name(value.name().to_string());
+		for (key, item) in value.iter() {
+			migrated.insert_boxed(key.clone_value(), migrate(item)?);
+		}
+		*value = migrated;
+	}
+	Ok(())
+}
+
+pub struct SceneEntitiesDeserializer<'a> {
+	pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
+	type Value = Vec<DynamicEntity>;
+
+	fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		deserializer.deserialize_seq(SceneEntitySeqVisitor {
+			type_registry: self.type_registry,
 		})
 	}
 }
@@ -246,3 +693,468 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisitor<'a> {
 		Ok(dynamic_properties)
 	}
 }
+
+pub struct InstanceVecDeserializer<'a> {
+	pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for InstanceVecDeserializer<'a> {
+	type Value = Vec<DynamicSceneInstance>;
+
+	fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		deserializer.deserialize_seq(InstanceSeqVisitor {
+			type_registry: self.type_registry,
+		})
+	}
+}
+
+struct InstanceSeqVisitor<'a> {
+	pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for InstanceSeqVisitor<'a> {
+	type Value = Vec<DynamicSceneInstance>;
+
+	fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+		formatter.write_str("list of scene instances")
+	}
+
+	fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: SeqAccess<'de>,
+	{
+		let mut instances = Vec::new();
+		while let Some(instance) = seq.next_element_seed(InstanceDeserializer {
+			type_registry: self.type_registry,
+		})? {
+			instances.push(instance);
+		}
+
+		Ok(instances)
+	}
+}
+
+pub struct InstanceDeserializer<'a> {
+	pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for InstanceDeserializer<'a> {
+	type Value = DynamicSceneInstance;
+
+	fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		deserializer.deserialize_struct(
+			INSTANCE_STRUCT,
+			&[
+				INSTANCE_FIELD_SCENE,
+				INSTANCE_FIELD_PARENT,
+				INSTANCE_FIELD_OVERRIDES,
+			],
+			InstanceVisitor {
+				type_registry: self.type_registry,
+			},
+		)
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum InstanceField {
+	Scene,
+	Parent,
+	Overrides,
+}
+
+pub const INSTANCE_STRUCT: &str = "Instance";
+pub const INSTANCE_FIELD_SCENE: &str = "scene";
+pub const INSTANCE_FIELD_PARENT: &str = "parent";
+pub const INSTANCE_FIELD_OVERRIDES: &str = "overrides";
+
+struct InstanceVisitor<'a> {
+	pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for InstanceVisitor<'a> {
+	type Value = DynamicSceneInstance;
+
+	fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+		formatter.write_str("scene instance")
+	}
+
+	fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+	where
+		A: MapAccess<'de>,
+	{
+		let mut path = None;
+		let mut parent = None;
+		let mut overrides = None;
+		while let Some(key) = map.next_key()? {
+			match key {
+				InstanceField::Scene => {
+					if path.is_some() {
+						return Err(Error::duplicate_field(INSTANCE_FIELD_SCENE));
+					}
+					path = Some(map.next_value::<String>()?);
+				},
+				InstanceField::Parent => {
+					if parent.is_some() {
+						return Err(Error::duplicate_field(INSTANCE_FIELD_PARENT));
+					}
+					parent = Some(map.next_value::<Option<u32>>()?);
+				},
+				InstanceField::Overrides => {
+					if overrides.is_some() {
+						return Err(Error::duplicate_field(INSTANCE_FIELD_OVERRIDES));
+					}
+					overrides = Some(map.next_value_seed(OverrideVecDeserializer {
+						type_registry: self.type_registry,
+					})?);
+				},
+			}
+		}
+
+		let path = path.ok_or(Error::missing_field(INSTANCE_FIELD_SCENE))?;
+		// the scene loader makes the handle strong
+		let scene = Handle::weak(HandleId::from(AssetPath::from(&path)));
+		Ok(DynamicSceneInstance {
+			path,
+			scene,
+			parent: parent.flatten(),
+			overrides: overrides.unwrap_or_default(),
+		})
+	}
+}
+
+pub struct OverrideVecDeserializer<'a> {
+	pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for OverrideVecDeserializer<'a> {
+	type Value = Vec<SceneOverride>;
+
+	fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		deserializer.deserialize_seq(OverrideSeqVisitor {
+			type_registry: self.type_registry,
+		})
+	}
+}
+
+struct OverrideSeqVisitor<'a> {
+	pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for OverrideSeqVisitor<'a> {
+	type Value = Vec<SceneOverride>;
+
+	fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+		formatter.write_str("list of overrides")
+	}
+
+	fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+	where
+		A: SeqAccess<'de>,
+	{
+		let mut overrides = Vec::new();
+		while let Some(scene_override) = seq.next_element_seed(OverrideDeserializer {
+			type_registry: self.type_registry,
+		})? {
+			overrides.push(scene_override);
+		}
+
+		Ok(overrides)
+	}
+}
+
+pub struct OverrideDeserializer<'a> {
+	pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for OverrideDeserializer<'a> {
+	type Value = SceneOverride;
+
+	fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		deserializer.deserialize_struct(
+			OVERRIDE_STRUCT,
+			&[
+				OVERRIDE_FIELD_ENTITY,
+				OVERRIDE_FIELD_COMPONENT,
+				OVERRIDE_FIELD_PATH,
+				OVERRIDE_FIELD_VALUE,
+			],
+			OverrideVisitor {
+				type_registry: self.type_registry,
+			},
+		)
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum OverrideField {
+	Entity,
+	Component,
+	Path,
+	Value,
+}
+
+pub const OVERRIDE_STRUCT: &str = "Override";
+pub const OVERRIDE_FIELD_ENTITY: &str = "entity";
+pub const OVERRIDE_FIELD_COMPONENT: &str = "component";
+pub const OVERRIDE_FIELD_PATH: &str = "path";
+pub const OVERRIDE_FIELD_VALUE: &str = "value";
+
+struct OverrideVisitor<'a> {
+	pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for OverrideVisitor<'a> {
+	type Value = SceneOverride;
+
+	fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+		formatter.write_str("override")
+	}
+
+	fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+	where
+		A: MapAccess<'de>,
+	{
+		let mut entity = None;
+		let mut component = None;
+		let mut path = None;
+		let mut value = None;
+		while let Some(key) = map.next_key()? {
+			match key {
+				OverrideField::Entity => {
+					if entity.is_some() {
+						return Err(Error::duplicate_field(OVERRIDE_FIELD_ENTITY));
+					}
+					entity = Some(map.next_value::<u32>()?);
+				},
+				OverrideField::Component => {
+					if component.is_some() {
+						return Err(Error::duplicate_field(OVERRIDE_FIELD_COMPONENT));
+					}
+					component = Some(map.next_value::<String>()?);
+				},
+				OverrideField::Path => {
+					if path.is_some() {
+						return Err(Error::duplicate_field(OVERRIDE_FIELD_PATH));
+					}
+					path = Some(map.next_value::<String>()?);
+				},
+				OverrideField::Value => {
+					if value.is_some() {
+						return Err(Error::duplicate_field(OVERRIDE_FIELD_VALUE));
+					}
+					value = Some(map.next_value_seed(ReflectDeserializer::new(self.type_registry))?);
+				},
+			}
+		}
+
+		Ok(SceneOverride {
+			entity: entity.ok_or(Error::missing_field(OVERRIDE_FIELD_ENTITY))?,
+			component: component.ok_or(Error::missing_field(OVERRIDE_FIELD_COMPONENT))?,
+			// overrides of whole components may leave the path out
+			path: path.unwrap_or_default(),
+			value: value.ok_or(Error::missing_field(OVERRIDE_FIELD_VALUE))?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		dynamic_scene::serialize_ron,
+		serde::{EntitiesSerializer, SceneDeserializer},
+		DynamicScene,
+	};
+	use bevy_ecs::{
+		component::Component,
+		entity::EntityMap,
+		reflect::{ReflectComponent, ReflectResource},
+		world::World,
+	};
+	use bevy_reflect::{DynamicStruct, Struct};
+	use bevy_reflect::{Reflect, TypeRegistryArc};
+	use serde::de::DeserializeSeed;
+
+	#[derive(Component, Reflect, Default)]
+	#[reflect(Component)]
+	struct Health(u32);
+
+	#[derive(Reflect, Default)]
+	#[reflect(Resource)]
+	struct Score(u32);
+
+	fn registry() -> TypeRegistryArc {
+		let registry = TypeRegistryArc::default();
+		registry.write().register::<Health>();
+		registry.write().register::<Score>();
+		registry
+	}
+
+	fn deserialize(registry: &TypeRegistryArc, input: &str) -> DynamicScene {
+		let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+		SceneDeserializer {
+			type_registry: &registry.read(),
+		}
+		.deserialize(&mut deserializer)
+		.unwrap()
+	}
+
+	#[test]
+	fn resources_round_trip() {
+		let registry = registry();
+		let mut world = World::new();
+		world.insert_resource(Score(7));
+		world.spawn().insert(Health(3));
+		let scene = DynamicScene::from_world(&world, &registry);
+		let scene = deserialize(&registry, &scene.serialize_ron(&registry).unwrap());
+		assert_eq!(scene.resources.len(), 1);
+		assert_eq!(scene.entities.len(), 1);
+
+		let mut world = World::new();
+		world.insert_resource(registry);
+		world.insert_resource(Score(0));
+		scene
+			.write_to_world(&mut world, &mut EntityMap::default())
+			.unwrap();
+		assert_eq!(world.resource::<Score>().0, 7);
+		let mut query = world.query::<&Health>();
+		assert_eq!(query.single(&world).0, 3);
+	}
+
+	#[test]
+	fn legacy_entity_list() {
+		let registry = registry();
+		let mut world = World::new();
+		world.spawn().insert(Health(3));
+		let scene = DynamicScene::from_world(&world, &registry);
+		// scenes used to be written as the list of their entities
+		let input = serialize_ron(EntitiesSerializer {
+			entities: &scene.entities,
+			registry: &registry,
+		})
+		.unwrap();
+		assert!(input.starts_with('['));
+
+		let scene = deserialize(&registry, &input);
+		assert!(scene.resources.is_empty());
+		assert_eq!(scene.entities.len(), 1);
+		assert_eq!(scene.entities[0].components.len(), 1);
+		assert!(scene.instances.is_empty());
+	}
+
+	/// Types as they were written to older scenes.
+	mod v0 {
+		use bevy_ecs::{component::Component, reflect::ReflectComponent};
+		use bevy_reflect::Reflect;
+
+		#[derive(Reflect, Default)]
+		pub struct Health {
+			pub hp: u32,
+		}
+
+		#[derive(Component, Reflect, Default)]
+		#[reflect(Component)]
+		pub struct Unit {
+			pub health: Health,
+		}
+	}
+
+	/// The current versions of the types of [`v0`].
+	mod v1 {
+		use bevy_ecs::{component::Component, reflect::ReflectComponent};
+		use bevy_reflect::Reflect;
+
+		#[derive(Reflect, Default)]
+		pub struct Health {
+			pub value: u32,
+		}
+
+		#[derive(Component, Reflect, Default)]
+		#[reflect(Component)]
+		pub struct Unit {
+			pub health: Health,
+		}
+	}
+
+	fn v1_registry() -> TypeRegistryArc {
+		let registry = TypeRegistryArc::default();
+		let mut registry_mut = registry.write();
+		registry_mut.register::<v1::Unit>();
+		registry_mut.register::<v1::Health>();
+		registry_mut.register_type_alias::<v1::Unit>(std::any::type_name::<v0::Unit>());
+		registry_mut.register_type_alias::<v1::Health>(std::any::type_name::<v0::Health>());
+		// `hp` was renamed to `value`
+		registry_mut.add_type_migration::<v1::Health>(0, |health| {
+			let health = health.downcast_ref::<DynamicStruct>().unwrap();
+			let mut migrated = DynamicStruct::default();
+			migrated.insert_boxed("value", health.field("hp").unwrap().clone_value());
+			Box::new(migrated)
+		});
+		drop(registry_mut);
+		registry
+	}
+
+	fn spawn_unit(registry: TypeRegistryArc, scene: &DynamicScene) -> u32 {
+		let mut world = World::new();
+		world.insert_resource(registry);
+		scene
+			.write_to_world(&mut world, &mut EntityMap::default())
+			.unwrap();
+		let mut query = world.query::<&v1::Unit>();
+		query.single(&world).health.value
+	}
+
+	#[test]
+	fn migrate_nested_values_and_aliases() {
+		let v0_registry = TypeRegistryArc::default();
+		v0_registry.write().register::<v0::Unit>();
+		v0_registry.write().register::<v0::Health>();
+		let mut world = World::new();
+		world.spawn().insert(v0::Unit {
+			health: v0::Health { hp: 3 },
+		});
+		let input = DynamicScene::from_world(&world, &v0_registry)
+			.serialize_ron(&v0_registry)
+			.unwrap();
+
+		// the health nested in the unit is migrated, and both are named after their current types
+		let registry = v1_registry();
+		let scene = deserialize(&registry, &input);
+		assert_eq!(
+			scene.entities[0].components[0].type_name(),
+			std::any::type_name::<v1::Unit>()
+		);
+		assert_eq!(spawn_unit(registry, &scene), 3);
+	}
+
+	#[test]
+	fn write_versions_of_nested_values() {
+		let registry = v1_registry();
+		let mut world = World::new();
+		world.spawn().insert(v1::Unit {
+			health: v1::Health { value: 5 },
+		});
+		let input = DynamicScene::from_world(&world, &registry)
+			.serialize_ron(&registry)
+			.unwrap();
+		assert!(input.contains(&format!("\"{}\": 1", std::any::type_name::<v1::Health>())));
+
+		// values of the current version aren't migrated
+		let scene = deserialize(&registry, &input);
+		assert_eq!(spawn_unit(registry, &scene), 5);
+	}
+}
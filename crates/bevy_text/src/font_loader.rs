@@ -7,6 +7,8 @@ use bevy_utils::BoxedFuture;
 pub struct FontLoader;
 
 impl AssetLoader for FontLoader {
+	type Settings = ();
+
 	fn load<'a>(
 		&'a self,
 		bytes: &'a [u8],
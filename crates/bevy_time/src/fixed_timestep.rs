@@ -288,6 +288,38 @@ mod test {
 		assert_eq!(2., get_accumulator_deciseconds(&world));
 	}
 
+	#[test]
+	fn paused_time_does_not_step() {
+		let mut world = World::default();
+		let mut time = Time::default();
+		let instance = Instant::now();
+		time.update_with_instant(instance);
+		world.insert_resource(time);
+		world.insert_resource(FixedTimesteps::default());
+		world.insert_resource::<Count>(0);
+		let mut schedule = Schedule::default();
+
+		schedule.add_stage(
+			"update",
+			SystemStage::parallel()
+				.with_run_criteria(FixedTimestep::step(0.5).with_label(LABEL))
+				.with_system(fixed_update),
+		);
+
+		world.resource_mut::<Time>().pause();
+		advance_time(&mut world, instance, 1.2);
+		schedule.run(&mut world);
+		assert_eq!(0, *world.resource::<Count>());
+		assert_eq!(0., get_accumulator_deciseconds(&world));
+
+		// only the time elapsed since unpausing is accumulated
+		world.resource_mut::<Time>().unpause();
+		advance_time(&mut world, instance, 1.9);
+		schedule.run(&mut world);
+		assert_eq!(1, *world.resource::<Count>());
+		assert_eq!(2., get_accumulator_deciseconds(&world));
+	}
+
 	fn fixed_update(mut count: ResMut<Count>) {
 		*count += 1;
 	}
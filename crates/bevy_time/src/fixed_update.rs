@@ -0,0 +1,218 @@
+use crate::Time;
+use bevy_ecs::{
+	schedule::{ShouldRun, StageLabel},
+	system::{Res, ResMut},
+};
+use bevy_utils::Duration;
+
+/// The label of the [`Stage`](bevy_ecs::schedule::Stage) that runs once per elapsed [`FixedTime`]
+/// step, added by the [`TimePlugin`](crate::TimePlugin) right before
+/// [`CoreStage::Update`](bevy_app::CoreStage::Update).
+///
+/// Depending on how much virtual [`Time`] elapsed since the last frame, the stage runs zero,
+/// one or several times in a single frame. Systems in this stage should use
+/// [`FixedTime::step`] instead of [`Time::delta`] to advance the simulation.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_time::{FixedTime, FixedUpdateStage, TimePlugin};
+/// #
+/// #[derive(Component)]
+/// struct Velocity(f32);
+///
+/// #[derive(Component)]
+/// struct Position(f32);
+///
+/// fn integrate(fixed_time: Res<FixedTime>, mut query: Query<(&mut Position, &Velocity)>) {
+///     for (mut position, velocity) in &mut query {
+///         position.0 += velocity.0 * fixed_time.step_seconds();
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugin(TimePlugin);
+/// app.insert_resource(FixedTime::from_seconds(1.0 / 50.0));
+/// app.add_system_to_stage(FixedUpdateStage, integrate);
+/// ```
+#[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
+pub struct FixedUpdateStage;
+
+/// The fixed step of the [`FixedUpdateStage`] and the time accumulated towards the next step.
+///
+/// Insert this resource before adding the [`TimePlugin`](crate::TimePlugin) or change it at
+/// runtime to configure the step. It defaults to 60 steps per second.
+#[derive(Debug, Clone)]
+pub struct FixedTime {
+	step: Duration,
+	accumulator: Duration,
+	elapsed: Duration,
+	looping: bool,
+}
+
+impl Default for FixedTime {
+	fn default() -> Self {
+		Self::from_seconds(1.0 / 60.0)
+	}
+}
+
+impl FixedTime {
+	/// Creates a [`FixedTime`] that steps every `step`.
+	///
+	/// # Panics
+	///
+	/// Panics if `step` is zero.
+	pub fn new(step: Duration) -> Self {
+		assert!(!step.is_zero(), "the fixed step cannot be zero");
+		Self {
+			step,
+			accumulator: Duration::ZERO,
+			elapsed: Duration::ZERO,
+			looping: false,
+		}
+	}
+
+	/// Creates a [`FixedTime`] that steps every `step` seconds.
+	pub fn from_seconds(step: f64) -> Self {
+		Self::new(Duration::from_secs_f64(step))
+	}
+
+	/// Creates a [`FixedTime`] that steps `rate` times per second.
+	pub fn from_steps_per_second(rate: f64) -> Self {
+		Self::from_seconds(1.0 / rate)
+	}
+
+	/// The amount of time each step takes.
+	#[inline]
+	pub fn step(&self) -> Duration {
+		self.step
+	}
+
+	/// The amount of time each step takes as [`f32`] seconds.
+	#[inline]
+	pub fn step_seconds(&self) -> f32 {
+		self.step.as_secs_f32()
+	}
+
+	/// The amount of time each step takes as [`f64`] seconds.
+	#[inline]
+	pub fn step_seconds_f64(&self) -> f64 {
+		self.step.as_secs_f64()
+	}
+
+	/// Sets the amount of time each step takes, starting from the next step.
+	///
+	/// # Panics
+	///
+	/// Panics if `step` is zero.
+	pub fn set_step(&mut self, step: Duration) {
+		assert!(!step.is_zero(), "the fixed step cannot be zero");
+		self.step = step;
+	}
+
+	/// The amount of time left over from the last step.
+	#[inline]
+	pub fn accumulator(&self) -> Duration {
+		self.accumulator
+	}
+
+	/// The sum of all the steps that ran so far.
+	#[inline]
+	pub fn elapsed(&self) -> Duration {
+		self.elapsed
+	}
+
+	/// How far the current frame is between the last step and the next one, in the `0.0..1.0` range.
+	///
+	/// This is the factor to interpolate between the state before and after the last step when
+	/// rendering, outside of the [`FixedUpdateStage`].
+	#[inline]
+	pub fn alpha(&self) -> f32 {
+		self.alpha_f64() as f32
+	}
+
+	/// How far the current frame is between the last step and the next one as [`f64`].
+	#[inline]
+	pub fn alpha_f64(&self) -> f64 {
+		self.accumulator.as_secs_f64() / self.step.as_secs_f64()
+	}
+
+	fn update(&mut self, delta: Duration) -> ShouldRun {
+		if !self.looping {
+			self.accumulator += delta;
+		}
+
+		if self.accumulator >= self.step {
+			self.accumulator -= self.step;
+			self.elapsed += self.step;
+			self.looping = true;
+			ShouldRun::YesAndCheckAgain
+		} else {
+			self.looping = false;
+			ShouldRun::No
+		}
+	}
+}
+
+/// The run criteria of the [`FixedUpdateStage`], which accumulates the virtual [`Time`] delta
+/// into the [`FixedTime`] resource and runs the stage once per elapsed step.
+pub fn fixed_update_run_criteria(time: Res<Time>, mut fixed_time: ResMut<FixedTime>) -> ShouldRun {
+	fixed_time.update(time.delta())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy_ecs::prelude::*;
+	use bevy_utils::Instant;
+
+	type Count = usize;
+
+	#[test]
+	fn fixed_update_stage() {
+		let mut world = World::default();
+		let mut time = Time::default();
+		let instant = Instant::now();
+		time.update_with_instant(instant);
+		world.insert_resource(time);
+		world.insert_resource(FixedTime::from_seconds(0.5));
+		world.insert_resource::<Count>(0);
+		let mut schedule = Schedule::default();
+
+		schedule.add_stage(
+			FixedUpdateStage,
+			SystemStage::parallel()
+				.with_run_criteria(fixed_update_run_criteria)
+				.with_system(|mut count: ResMut<Count>| *count += 1),
+		);
+
+		schedule.run(&mut world);
+		assert_eq!(0, *world.resource::<Count>());
+
+		advance_time(&mut world, instant, 0.6);
+		schedule.run(&mut world);
+		assert_eq!(1, *world.resource::<Count>());
+		assert_eq!(
+			world.resource::<FixedTime>().accumulator(),
+			Duration::from_millis(100)
+		);
+		assert!((world.resource::<FixedTime>().alpha() - 0.2).abs() < 1e-6);
+
+		advance_time(&mut world, instant, 1.75);
+		schedule.run(&mut world);
+		assert_eq!(3, *world.resource::<Count>());
+		assert_eq!(
+			world.resource::<FixedTime>().elapsed(),
+			Duration::from_millis(1500)
+		);
+		assert!((world.resource::<FixedTime>().alpha() - 0.5).abs() < 1e-6);
+	}
+
+	fn advance_time(world: &mut World, instant: Instant, seconds: f64) {
+		world
+			.resource_mut::<Time>()
+			.update_with_instant(instant + Duration::from_secs_f64(seconds));
+	}
+}
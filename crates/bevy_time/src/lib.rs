@@ -1,10 +1,14 @@
 mod fixed_timestep;
+mod fixed_update;
+mod scheduled_commands;
 mod stopwatch;
 #[allow(clippy::module_inception)]
 mod time;
 mod timer;
 
 pub use fixed_timestep::*;
+pub use fixed_update::*;
+pub use scheduled_commands::*;
 pub use stopwatch::*;
 pub use time::*;
 pub use timer::*;
@@ -16,7 +20,7 @@ use crossbeam_channel::{Receiver, Sender};
 pub mod prelude {
 	//! The Bevy Time Prelude.
 	#[doc(hidden)]
-	pub use crate::{Time, Timer};
+	pub use crate::{FixedTime, FixedUpdateStage, ScheduleCommandsExt, Time, Timer};
 }
 
 use bevy_app::prelude::*;
@@ -35,6 +39,8 @@ impl Plugin for TimePlugin {
 	fn build(&self, app: &mut App) {
 		app.init_resource::<Time>();
 		app.init_resource::<FixedTimesteps>();
+		app.init_resource::<FixedTime>();
+		app.init_resource::<ScheduledCommands>();
 		app.register_type::<Timer>();
 		// time system is added as an "exclusive system" to ensure it runs before other systems
 		// in CoreStage::First
@@ -45,6 +51,19 @@ impl Plugin for TimePlugin {
 				.at_start()
 				.label(TimeSystem),
 		);
+		#[cfg(feature = "bevy_ci_testing")]
+		app.add_system_to_stage(CoreStage::First, record_time_system.after(TimeSystem));
+		app.add_system_to_stage(
+			CoreStage::First,
+			scheduled_commands_system
+				.exclusive_system()
+				.at_end(),
+		);
+		app.add_stage_before(
+			CoreStage::Update,
+			FixedUpdateStage,
+			SystemStage::parallel().with_run_criteria(fixed_update_run_criteria),
+		);
 	}
 }
 
@@ -62,12 +81,31 @@ pub fn create_time_channels() -> (TimeSender, TimeReceiver) {
 }
 
 /// The system used to update the [`Time`] used by app logic. If there is a render world the time is sent from
-/// there to this system through channels. Otherwise the time is updated in this system.
+/// there to this system through channels. Otherwise the time is updated in this system, from the
+/// [`MockClock`](bevy_app::MockClock) if there is one.
 fn time_system(
 	mut time: ResMut<Time>,
 	time_recv: Option<Res<TimeReceiver>>,
+	mock_clock: Option<Res<bevy_app::MockClock>>,
+	#[cfg(feature = "bevy_ci_testing")] replayer: Option<Res<bevy_app::InputReplayer>>,
 	mut has_received_time: Local<bool>,
 ) {
+	// replay the recorded deltas so the replayed frames see the same time as the recorded ones
+	#[cfg(feature = "bevy_ci_testing")]
+	if let Some(replayer) = replayer {
+		match (time.last_update(), replayer.delta()) {
+			(Some(last_update), Some(delta)) => time.update_with_instant(last_update + delta),
+			_ => time.update(),
+		}
+		return;
+	}
+
+	// tests advance the time manually, see `bevy_app::TestApp`
+	if let Some(mock_clock) = mock_clock {
+		time.update_with_instant(mock_clock.now());
+		return;
+	}
+
 	if let Some(time_recv) = time_recv {
 		// TODO: Figure out how to handle this when using pipelined rendering.
 		if let Ok(new_time) = time_recv.0.try_recv() {
@@ -80,3 +118,48 @@ fn time_system(
 		time.update();
 	}
 }
+
+#[cfg(feature = "bevy_ci_testing")]
+fn record_time_system(time: Res<Time>, recorder: Option<ResMut<bevy_app::InputRecorder>>) {
+	if let Some(mut recorder) = recorder {
+		recorder.record_delta(time.raw_delta());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Time, TimePlugin};
+	use bevy_app::{MockClock, TestApp};
+	use bevy_utils::Duration;
+
+	#[test]
+	fn time_follows_mock_clock() {
+		let mut app = TestApp::new();
+		app.add_plugin(TimePlugin);
+		app.set_frame_delta(Duration::from_millis(10));
+
+		app.step(1);
+		let last_update = app.world.resource::<Time>().last_update();
+		assert_eq!(last_update, Some(app.world.resource::<MockClock>().now()));
+
+		app.step(2);
+		let time = app.world.resource::<Time>();
+		assert_eq!(time.raw_delta(), Duration::from_millis(10));
+		assert_eq!(time.delta(), Duration::from_millis(10));
+		assert_eq!(
+			time.last_update(),
+			last_update.map(|last_update| last_update + Duration::from_millis(20))
+		);
+
+		// the virtual clock applies to the mocked deltas
+		app
+			.world
+			.resource_mut::<Time>()
+			.set_relative_speed(2.0);
+		app.step(1);
+		assert_eq!(
+			app.world.resource::<Time>().delta(),
+			Duration::from_millis(20)
+		);
+	}
+}
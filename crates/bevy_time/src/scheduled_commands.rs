@@ -0,0 +1,565 @@
+use crate::{Time, Timer};
+use bevy_ecs::{system::Commands, world::World};
+use bevy_utils::Duration;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The clock of [`Time`] that drives a scheduled command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimeClock {
+	/// Stops while [`Time`] is paused and follows its relative speed.
+	Virtual,
+	/// Keeps running while [`Time`] is paused.
+	Real,
+}
+
+/// Identifies a command scheduled in [`ScheduledCommands`], to be able to cancel it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ScheduledCommandId(u64);
+
+impl ScheduledCommandId {
+	fn next() -> Self {
+		static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+		ScheduledCommandId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+	}
+}
+
+enum ScheduledFn {
+	Once(Box<dyn FnOnce(&mut World) + Send + Sync>),
+	Repeating(Box<dyn FnMut(&mut World) + Send + Sync>),
+}
+
+struct ScheduledCommand {
+	id: ScheduledCommandId,
+	timer: Timer,
+	clock: TimeClock,
+	run: ScheduledFn,
+}
+
+/// A resource holding commands that run on the [`World`] once some [`Time`] has elapsed.
+///
+/// Commands are usually scheduled from systems through [`ScheduleCommandsExt`]. They run at the
+/// end of [`CoreStage::First`](bevy_app::CoreStage::First), in the order they were scheduled.
+#[derive(Default)]
+pub struct ScheduledCommands {
+	commands: Vec<ScheduledCommand>,
+	running: Option<ScheduledCommandId>,
+	cancel_running: bool,
+}
+
+impl ScheduledCommands {
+	/// Runs `command` once, after `delay` of virtual time.
+	pub fn after(
+		&mut self,
+		delay: Duration,
+		command: impl FnOnce(&mut World) + Send + Sync + 'static,
+	) -> ScheduledCommandId {
+		let id = ScheduledCommandId::next();
+		self.insert(
+			id,
+			delay,
+			TimeClock::Virtual,
+			ScheduledFn::Once(Box::new(command)),
+		);
+		id
+	}
+
+	/// Runs `command` once, after `delay` of real time.
+	pub fn after_real(
+		&mut self,
+		delay: Duration,
+		command: impl FnOnce(&mut World) + Send + Sync + 'static,
+	) -> ScheduledCommandId {
+		let id = ScheduledCommandId::next();
+		self.insert(
+			id,
+			delay,
+			TimeClock::Real,
+			ScheduledFn::Once(Box::new(command)),
+		);
+		id
+	}
+
+	/// Runs `command` every `interval` of virtual time, until it is [cancelled](Self::cancel).
+	///
+	/// # Panics
+	///
+	/// Panics if `interval` is zero.
+	pub fn every(
+		&mut self,
+		interval: Duration,
+		command: impl FnMut(&mut World) + Send + Sync + 'static,
+	) -> ScheduledCommandId {
+		let id = ScheduledCommandId::next();
+		self.insert(
+			id,
+			interval,
+			TimeClock::Virtual,
+			ScheduledFn::Repeating(Box::new(command)),
+		);
+		id
+	}
+
+	/// Runs `command` every `interval` of real time, until it is [cancelled](Self::cancel).
+	///
+	/// # Panics
+	///
+	/// Panics if `interval` is zero.
+	pub fn every_real(
+		&mut self,
+		interval: Duration,
+		command: impl FnMut(&mut World) + Send + Sync + 'static,
+	) -> ScheduledCommandId {
+		let id = ScheduledCommandId::next();
+		self.insert(
+			id,
+			interval,
+			TimeClock::Real,
+			ScheduledFn::Repeating(Box::new(command)),
+		);
+		id
+	}
+
+	/// Cancels a scheduled command. A repeating command can cancel itself while it runs.
+	///
+	/// Returns `false` if the command already ran or was already cancelled, or if it was
+	/// scheduled through [`Commands`] that haven't been applied yet: in that case the command
+	/// still runs, use [`ScheduleCommandsExt::cancel_scheduled`] from the same [`Commands`]
+	/// instead, which is applied after it.
+	pub fn cancel(&mut self, id: ScheduledCommandId) -> bool {
+		if self.running == Some(id) {
+			let was_cancelled = self.cancel_running;
+			self.cancel_running = true;
+			return !was_cancelled;
+		}
+		match self.index_of(id) {
+			Ok(index) => {
+				self.commands.remove(index);
+				true
+			},
+			Err(_) => false,
+		}
+	}
+
+	/// Returns `true` if the command is still scheduled to run.
+	pub fn contains(&self, id: ScheduledCommandId) -> bool {
+		self.index_of(id).is_ok() || (self.running == Some(id) && !self.cancel_running)
+	}
+
+	/// Returns the time left before the command runs next, according to its clock.
+	pub fn remaining(&self, id: ScheduledCommandId) -> Option<Duration> {
+		let command = &self.commands[self.index_of(id).ok()?];
+		Some(command.timer.duration() - command.timer.elapsed())
+	}
+
+	/// The number of scheduled commands.
+	pub fn len(&self) -> usize {
+		self.commands.len()
+	}
+
+	/// Returns `true` if no command is scheduled.
+	pub fn is_empty(&self) -> bool {
+		self.commands.is_empty()
+	}
+
+	fn insert(
+		&mut self,
+		id: ScheduledCommandId,
+		duration: Duration,
+		clock: TimeClock,
+		run: ScheduledFn,
+	) {
+		check_interval(duration, &run);
+		let repeating = matches!(run, ScheduledFn::Repeating(_));
+		self.reinsert(ScheduledCommand {
+			id,
+			timer: Timer::new(duration, repeating),
+			clock,
+			run,
+		});
+	}
+
+	/// Ids are allocated in increasing order, keeping the commands sorted by id keeps them in
+	/// the order they were scheduled.
+	fn reinsert(&mut self, command: ScheduledCommand) {
+		let index = self
+			.index_of(command.id)
+			.unwrap_or_else(|index| index);
+		self.commands.insert(index, command);
+	}
+
+	fn index_of(&self, id: ScheduledCommandId) -> Result<usize, usize> {
+		self
+			.commands
+			.binary_search_by_key(&id, |command| command.id)
+	}
+
+	/// Ticks every command with the delta of its clock, returning the ones that are due along with
+	/// the number of times they have to run.
+	fn tick(&mut self, time: &Time) -> Vec<(ScheduledCommandId, u32)> {
+		self
+			.commands
+			.iter_mut()
+			.filter_map(|command| {
+				let delta = match command.clock {
+					TimeClock::Virtual => time.delta(),
+					TimeClock::Real => time.raw_delta(),
+				};
+				let times = command
+					.timer
+					.tick(delta)
+					.times_finished_this_tick();
+				(times > 0).then_some((command.id, times))
+			})
+			.collect()
+	}
+
+	fn take(&mut self, id: ScheduledCommandId) -> Option<ScheduledCommand> {
+		let index = self.index_of(id).ok()?;
+		self.running = Some(id);
+		self.cancel_running = false;
+		Some(self.commands.remove(index))
+	}
+
+	fn finish(&mut self, command: Option<ScheduledCommand>) {
+		if let Some(command) = command {
+			if !self.cancel_running {
+				self.reinsert(command);
+			}
+		}
+		self.running = None;
+		self.cancel_running = false;
+	}
+}
+
+/// A repeating [`Timer`] can't tick with a zero duration.
+fn check_interval(duration: Duration, run: &ScheduledFn) {
+	assert!(
+		!(duration.is_zero() && matches!(run, ScheduledFn::Repeating(_))),
+		"cannot run a scheduled command every zero interval"
+	);
+}
+
+/// Runs the [`ScheduledCommands`] that are due.
+pub fn scheduled_commands_system(world: &mut World) {
+	let time = world.resource::<Time>().clone();
+	let due = world
+		.resource_mut::<ScheduledCommands>()
+		.tick(&time);
+
+	for (id, times) in due {
+		for _ in 0..times {
+			let command = world
+				.resource_mut::<ScheduledCommands>()
+				.take(id);
+			let mut command = match command {
+				Some(command) => command,
+				None => break,
+			};
+			let command = match command.run {
+				ScheduledFn::Once(run) => {
+					run(world);
+					None
+				},
+				ScheduledFn::Repeating(ref mut run) => {
+					run(world);
+					Some(command)
+				},
+			};
+			world
+				.resource_mut::<ScheduledCommands>()
+				.finish(command);
+		}
+	}
+}
+
+/// Extends [`Commands`] to run commands once some [`Time`] has elapsed.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_time::ScheduleCommandsExt;
+/// # use bevy_utils::Duration;
+/// #
+/// fn explode(mut commands: Commands, query: Query<Entity, Added<Name>>) {
+///     for entity in &query {
+///         commands.after(Duration::from_secs(3), move |world| {
+///             world.despawn(entity);
+///         });
+///     }
+/// }
+/// #
+/// # #[derive(Component)]
+/// # struct Name;
+/// # bevy_ecs::system::assert_is_system(explode);
+/// ```
+pub trait ScheduleCommandsExt {
+	/// Runs `command` once, after `delay` of virtual time.
+	fn after(
+		&mut self,
+		delay: Duration,
+		command: impl FnOnce(&mut World) + Send + Sync + 'static,
+	) -> ScheduledCommandId;
+
+	/// Runs `command` once, after `delay` of real time.
+	fn after_real(
+		&mut self,
+		delay: Duration,
+		command: impl FnOnce(&mut World) + Send + Sync + 'static,
+	) -> ScheduledCommandId;
+
+	/// Runs `command` every `interval` of virtual time, until it is cancelled.
+	///
+	/// # Panics
+	///
+	/// Panics if `interval` is zero.
+	fn every(
+		&mut self,
+		interval: Duration,
+		command: impl FnMut(&mut World) + Send + Sync + 'static,
+	) -> ScheduledCommandId;
+
+	/// Runs `command` every `interval` of real time, until it is cancelled.
+	///
+	/// # Panics
+	///
+	/// Panics if `interval` is zero.
+	fn every_real(
+		&mut self,
+		interval: Duration,
+		command: impl FnMut(&mut World) + Send + Sync + 'static,
+	) -> ScheduledCommandId;
+
+	/// Cancels a command scheduled with one of the other methods.
+	///
+	/// The cancellation is applied with the other commands, after the commands scheduling it
+	/// that were added before.
+	fn cancel_scheduled(&mut self, id: ScheduledCommandId);
+}
+
+impl<'w, 's> ScheduleCommandsExt for Commands<'w, 's> {
+	fn after(
+		&mut self,
+		delay: Duration,
+		command: impl FnOnce(&mut World) + Send + Sync + 'static,
+	) -> ScheduledCommandId {
+		schedule(
+			self,
+			delay,
+			TimeClock::Virtual,
+			ScheduledFn::Once(Box::new(command)),
+		)
+	}
+
+	fn after_real(
+		&mut self,
+		delay: Duration,
+		command: impl FnOnce(&mut World) + Send + Sync + 'static,
+	) -> ScheduledCommandId {
+		schedule(
+			self,
+			delay,
+			TimeClock::Real,
+			ScheduledFn::Once(Box::new(command)),
+		)
+	}
+
+	fn every(
+		&mut self,
+		interval: Duration,
+		command: impl FnMut(&mut World) + Send + Sync + 'static,
+	) -> ScheduledCommandId {
+		schedule(
+			self,
+			interval,
+			TimeClock::Virtual,
+			ScheduledFn::Repeating(Box::new(command)),
+		)
+	}
+
+	fn every_real(
+		&mut self,
+		interval: Duration,
+		command: impl FnMut(&mut World) + Send + Sync + 'static,
+	) -> ScheduledCommandId {
+		schedule(
+			self,
+			interval,
+			TimeClock::Real,
+			ScheduledFn::Repeating(Box::new(command)),
+		)
+	}
+
+	fn cancel_scheduled(&mut self, id: ScheduledCommandId) {
+		self.add(move |world: &mut World| {
+			world
+				.get_resource_or_insert_with(ScheduledCommands::default)
+				.cancel(id);
+		});
+	}
+}
+
+fn schedule(
+	commands: &mut Commands,
+	duration: Duration,
+	clock: TimeClock,
+	run: ScheduledFn,
+) -> ScheduledCommandId {
+	check_interval(duration, &run);
+	let id = ScheduledCommandId::next();
+	commands.add(move |world: &mut World| {
+		world
+			.get_resource_or_insert_with(ScheduledCommands::default)
+			.insert(id, duration, clock, run);
+	});
+	id
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy_ecs::prelude::*;
+	use bevy_utils::Instant;
+
+	type Count = usize;
+
+	fn setup() -> (World, Instant) {
+		let mut world = World::default();
+		let mut time = Time::default();
+		let instant = Instant::now();
+		time.update_with_instant(instant);
+		world.insert_resource(time);
+		world.insert_resource(ScheduledCommands::default());
+		world.insert_resource::<Count>(0);
+		(world, instant)
+	}
+
+	fn advance_time(world: &mut World, instant: Instant, seconds: f64) {
+		world
+			.resource_mut::<Time>()
+			.update_with_instant(instant + Duration::from_secs_f64(seconds));
+		scheduled_commands_system(world);
+	}
+
+	fn increment(world: &mut World) {
+		*world.resource_mut::<Count>() += 1;
+	}
+
+	#[test]
+	fn run_after_delay() {
+		let (mut world, instant) = setup();
+		let id = world
+			.resource_mut::<ScheduledCommands>()
+			.after(Duration::from_secs(1), increment);
+
+		advance_time(&mut world, instant, 0.5);
+		assert_eq!(0, *world.resource::<Count>());
+		assert_eq!(
+			world
+				.resource::<ScheduledCommands>()
+				.remaining(id),
+			Some(Duration::from_millis(500))
+		);
+
+		advance_time(&mut world, instant, 1.0);
+		assert_eq!(1, *world.resource::<Count>());
+		assert!(!world
+			.resource::<ScheduledCommands>()
+			.contains(id));
+
+		advance_time(&mut world, instant, 3.0);
+		assert_eq!(1, *world.resource::<Count>());
+	}
+
+	#[test]
+	fn repeat_and_cancel() {
+		let (mut world, instant) = setup();
+		let id = world
+			.resource_mut::<ScheduledCommands>()
+			.every(Duration::from_secs(1), increment);
+
+		advance_time(&mut world, instant, 1.0);
+		assert_eq!(1, *world.resource::<Count>());
+
+		// runs once per elapsed interval
+		advance_time(&mut world, instant, 3.5);
+		assert_eq!(3, *world.resource::<Count>());
+
+		assert!(world
+			.resource_mut::<ScheduledCommands>()
+			.cancel(id));
+		advance_time(&mut world, instant, 10.0);
+		assert_eq!(3, *world.resource::<Count>());
+		assert!(!world
+			.resource_mut::<ScheduledCommands>()
+			.cancel(id));
+	}
+
+	#[test]
+	fn cancel_while_running() {
+		struct CancelledId(ScheduledCommandId);
+
+		let (mut world, instant) = setup();
+		let id = world
+			.resource_mut::<ScheduledCommands>()
+			.every(Duration::from_secs(1), |world| {
+				increment(world);
+				if *world.resource::<Count>() == 2 {
+					let id = world.resource::<CancelledId>().0;
+					assert!(world
+						.resource_mut::<ScheduledCommands>()
+						.cancel(id));
+				}
+			});
+		world.insert_resource(CancelledId(id));
+
+		advance_time(&mut world, instant, 5.0);
+		assert_eq!(2, *world.resource::<Count>());
+		assert!(!world
+			.resource::<ScheduledCommands>()
+			.contains(id));
+		assert!(world.resource::<ScheduledCommands>().is_empty());
+	}
+
+	#[test]
+	#[should_panic(expected = "zero interval")]
+	fn zero_interval() {
+		let (mut world, _) = setup();
+		world
+			.resource_mut::<ScheduledCommands>()
+			.every(Duration::ZERO, increment);
+	}
+
+	#[test]
+	fn clocks() {
+		let (mut world, instant) = setup();
+		world
+			.resource_mut::<ScheduledCommands>()
+			.after(Duration::from_secs(1), increment);
+		world
+			.resource_mut::<ScheduledCommands>()
+			.after_real(Duration::from_secs(1), increment);
+
+		world.resource_mut::<Time>().pause();
+		advance_time(&mut world, instant, 2.0);
+		assert_eq!(1, *world.resource::<Count>());
+
+		world.resource_mut::<Time>().unpause();
+		advance_time(&mut world, instant, 3.0);
+		assert_eq!(2, *world.resource::<Count>());
+	}
+
+	#[test]
+	fn schedule_from_commands() {
+		let (mut world, instant) = setup();
+		let mut stage = SystemStage::single_threaded();
+		stage.add_system(|mut commands: Commands| {
+			commands.after(Duration::from_secs(1), increment);
+		});
+		stage.run(&mut world);
+		assert_eq!(1, world.resource::<ScheduledCommands>().len());
+
+		advance_time(&mut world, instant, 1.0);
+		assert_eq!(1, *world.resource::<Count>());
+	}
+}
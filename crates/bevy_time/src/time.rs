@@ -2,7 +2,20 @@ use bevy_ecs::reflect::ReflectResource;
 use bevy_reflect::Reflect;
 use bevy_utils::{Duration, Instant};
 
-/// Tracks elapsed time since the last update and since the App has started
+/// Tracks elapsed time since the last update and since the App has started.
+///
+/// [`Time`] keeps two clocks:
+///
+/// * the _real_ clock, exposed through the `raw_*` methods, which follows the wall-clock time
+///   between updates.
+/// * the _virtual_ clock, exposed through [`delta`](Time::delta),
+///   [`time_since_startup`](Time::time_since_startup) and their variants, which is the real clock
+///   clamped to [`maximum_delta`](Time::maximum_delta), scaled by
+///   [`relative_speed`](Time::relative_speed) and stopped while [`paused`](Time::is_paused).
+///
+/// Game logic, including [`FixedTimestep`](crate::FixedTimestep), timers and animations, should
+/// use the virtual clock so that pausing and slow motion apply to it automatically. The real
+/// clock is meant for things that must keep running regardless, such as UI or diagnostics.
 #[derive(Reflect, Debug, Clone)]
 #[reflect(Resource)]
 pub struct Time {
@@ -13,6 +26,14 @@ pub struct Time {
 	seconds_since_startup: f64,
 	time_since_startup: Duration,
 	startup: Instant,
+	raw_delta: Duration,
+	raw_delta_seconds_f64: f64,
+	raw_delta_seconds: f32,
+	raw_seconds_since_startup: f64,
+	raw_time_since_startup: Duration,
+	relative_speed: f64,
+	maximum_delta: Option<Duration>,
+	paused: bool,
 }
 
 impl Default for Time {
@@ -25,6 +46,14 @@ impl Default for Time {
 			seconds_since_startup: 0.0,
 			time_since_startup: Duration::from_secs(0),
 			delta_seconds: 0.0,
+			raw_delta: Duration::from_secs(0),
+			raw_delta_seconds_f64: 0.0,
+			raw_delta_seconds: 0.0,
+			raw_seconds_since_startup: 0.0,
+			raw_time_since_startup: Duration::from_secs(0),
+			relative_speed: 1.0,
+			maximum_delta: None,
+			paused: false,
 		}
 	}
 }
@@ -91,17 +120,44 @@ impl Time {
 	/// }
 	/// ```
 	pub fn update_with_instant(&mut self, instant: Instant) {
+		self.raw_time_since_startup = instant - self.startup;
+		self.raw_seconds_since_startup = self.raw_time_since_startup.as_secs_f64();
+
 		if let Some(last_update) = self.last_update {
-			self.delta = instant - last_update;
-			self.delta_seconds_f64 = self.delta.as_secs_f64();
-			self.delta_seconds = self.delta.as_secs_f32();
+			self.raw_delta = instant - last_update;
+			self.raw_delta_seconds_f64 = self.raw_delta.as_secs_f64();
+			self.raw_delta_seconds = self.raw_delta.as_secs_f32();
+
+			self.delta = self.virtual_delta(self.raw_delta);
+			self.time_since_startup += self.delta;
+		} else {
+			// The time spent before the first update is not affected by the virtual clock settings
+			self.time_since_startup = self.raw_time_since_startup;
 		}
 
-		self.time_since_startup = instant - self.startup;
+		self.delta_seconds_f64 = self.delta.as_secs_f64();
+		self.delta_seconds = self.delta.as_secs_f32();
 		self.seconds_since_startup = self.time_since_startup.as_secs_f64();
 		self.last_update = Some(instant);
 	}
 
+	/// Converts a real delta into a virtual one.
+	fn virtual_delta(&self, raw_delta: Duration) -> Duration {
+		if self.paused {
+			return Duration::ZERO;
+		}
+
+		let clamped = match self.maximum_delta {
+			Some(maximum_delta) => raw_delta.min(maximum_delta),
+			None => raw_delta,
+		};
+		if self.relative_speed == 1.0 {
+			clamped
+		} else {
+			clamped.mul_f64(self.relative_speed)
+		}
+	}
+
 	/// The delta between the current tick and last tick as a [`Duration`]
 	#[inline]
 	pub fn delta(&self) -> Duration {
@@ -143,6 +199,115 @@ impl Time {
 	pub fn time_since_startup(&self) -> Duration {
 		self.time_since_startup
 	}
+
+	/// The real delta between the current tick and last tick as a [`Duration`], ignoring pause,
+	/// speed and the maximum delta
+	#[inline]
+	pub fn raw_delta(&self) -> Duration {
+		self.raw_delta
+	}
+
+	/// The real delta between the current and last tick as [`f32`] seconds, ignoring pause,
+	/// speed and the maximum delta
+	#[inline]
+	pub fn raw_delta_seconds(&self) -> f32 {
+		self.raw_delta_seconds
+	}
+
+	/// The real delta between the current and last tick as [`f64`] seconds, ignoring pause,
+	/// speed and the maximum delta
+	#[inline]
+	pub fn raw_delta_seconds_f64(&self) -> f64 {
+		self.raw_delta_seconds_f64
+	}
+
+	/// The real time from startup to the last update in seconds
+	#[inline]
+	pub fn raw_seconds_since_startup(&self) -> f64 {
+		self.raw_seconds_since_startup
+	}
+
+	/// The real [`Duration`] from startup to the last update
+	#[inline]
+	pub fn raw_time_since_startup(&self) -> Duration {
+		self.raw_time_since_startup
+	}
+
+	/// The rate at which the virtual clock advances relative to the real clock, `1.0` by default
+	#[inline]
+	pub fn relative_speed(&self) -> f32 {
+		self.relative_speed as f32
+	}
+
+	/// The rate at which the virtual clock advances relative to the real clock as [`f64`]
+	#[inline]
+	pub fn relative_speed_f64(&self) -> f64 {
+		self.relative_speed
+	}
+
+	/// Sets the rate at which the virtual clock advances relative to the real clock, starting
+	/// from the next update.
+	///
+	/// For example, `2.0` makes the game run twice as fast and `0.5` runs it in slow motion.
+	///
+	/// # Panics
+	///
+	/// Panics if `ratio` is negative or not finite.
+	#[inline]
+	pub fn set_relative_speed(&mut self, ratio: f32) {
+		self.set_relative_speed_f64(ratio as f64);
+	}
+
+	/// Sets the rate at which the virtual clock advances relative to the real clock as [`f64`],
+	/// starting from the next update.
+	///
+	/// # Panics
+	///
+	/// Panics if `ratio` is negative or not finite.
+	#[inline]
+	pub fn set_relative_speed_f64(&mut self, ratio: f64) {
+		assert!(ratio.is_finite(), "tried to go infinitely fast");
+		assert!(ratio >= 0.0, "tried to go back in time");
+		self.relative_speed = ratio;
+	}
+
+	/// The maximum real delta that the virtual clock advances by in a single update, `None` by
+	/// default.
+	///
+	/// This prevents a long hitch, like a debugger breakpoint or a window being dragged, from
+	/// being followed by a huge virtual delta, which would in turn make every
+	/// [`FixedTimestep`](crate::FixedTimestep) try to catch up with many steps at once.
+	#[inline]
+	pub fn maximum_delta(&self) -> Option<Duration> {
+		self.maximum_delta
+	}
+
+	/// Sets the maximum real delta that the virtual clock advances by in a single update.
+	///
+	/// A value around `250ms` is a good fit for most games. `None` lets the virtual clock follow the
+	/// real clock no matter how long an update takes.
+	#[inline]
+	pub fn set_maximum_delta(&mut self, maximum_delta: Option<Duration>) {
+		self.maximum_delta = maximum_delta;
+	}
+
+	/// Stops the virtual clock, starting from the next update. The real clock keeps running.
+	#[inline]
+	pub fn pause(&mut self) {
+		self.paused = true;
+	}
+
+	/// Resumes the virtual clock, starting from the next update.
+	#[inline]
+	pub fn unpause(&mut self) {
+		self.paused = false;
+	}
+
+	/// Returns `true` if the virtual clock is paused.
+	#[inline]
+	pub fn is_paused(&self) -> bool {
+		self.paused
+	}
 }
 
 #[cfg(test)]
@@ -210,4 +375,69 @@ mod tests {
 		);
 		assert_eq!(time.delta_seconds(), time.delta().as_secs_f32());
 	}
+
+	#[test]
+	fn pause_test() {
+		let start_instant = Instant::now();
+		let mut time = Time {
+			startup: start_instant,
+			..Default::default()
+		};
+		time.update_with_instant(start_instant);
+
+		time.pause();
+		assert!(time.is_paused());
+		time.update_with_instant(start_instant + Duration::from_secs(1));
+
+		assert_eq!(time.delta(), Duration::ZERO);
+		assert_eq!(time.time_since_startup(), Duration::ZERO);
+		assert_eq!(time.raw_delta(), Duration::from_secs(1));
+		assert_eq!(time.raw_time_since_startup(), Duration::from_secs(1));
+
+		time.unpause();
+		time.update_with_instant(start_instant + Duration::from_secs(3));
+
+		assert_eq!(time.delta(), Duration::from_secs(2));
+		assert_eq!(time.time_since_startup(), Duration::from_secs(2));
+		assert_eq!(time.raw_delta(), Duration::from_secs(2));
+		assert_eq!(time.raw_time_since_startup(), Duration::from_secs(3));
+	}
+
+	#[test]
+	fn relative_speed_test() {
+		let start_instant = Instant::now();
+		let mut time = Time {
+			startup: start_instant,
+			..Default::default()
+		};
+		time.update_with_instant(start_instant);
+
+		time.set_relative_speed(2.0);
+		time.update_with_instant(start_instant + Duration::from_millis(250));
+
+		assert_eq!(time.relative_speed(), 2.0);
+		assert_eq!(time.delta(), Duration::from_millis(500));
+		assert_eq!(time.delta_seconds(), 0.5);
+		assert_eq!(time.raw_delta(), Duration::from_millis(250));
+		assert_eq!(time.seconds_since_startup(), 0.5);
+		assert_eq!(time.raw_seconds_since_startup(), 0.25);
+	}
+
+	#[test]
+	fn maximum_delta_test() {
+		let start_instant = Instant::now();
+		let mut time = Time {
+			startup: start_instant,
+			..Default::default()
+		};
+		time.update_with_instant(start_instant);
+
+		time.set_maximum_delta(Some(Duration::from_millis(100)));
+		time.set_relative_speed(0.5);
+		time.update_with_instant(start_instant + Duration::from_secs(5));
+
+		assert_eq!(time.delta(), Duration::from_millis(50));
+		assert_eq!(time.time_since_startup(), Duration::from_millis(50));
+		assert_eq!(time.raw_delta(), Duration::from_secs(5));
+	}
 }
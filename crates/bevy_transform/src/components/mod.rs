@@ -1,5 +1,7 @@
 mod global_transform;
 mod transform;
+mod transform_interpolation;
 
 pub use global_transform::*;
 pub use transform::*;
+pub use transform_interpolation::*;
@@ -0,0 +1,45 @@
+use super::Transform;
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_reflect::Reflect;
+
+/// Smooths the movement of an entity whose [`Transform`] is only updated in the
+/// [`FixedUpdateStage`](bevy_time::FixedUpdateStage).
+///
+/// When the fixed step is longer than a frame, an entity moved in the fixed update only changes
+/// position on some frames, which looks jittery. Adding this component makes the
+/// [`TransformInterpolationPlugin`](crate::TransformInterpolationPlugin) render the entity between
+/// its [`Transform`] before and after the last fixed step, using
+/// [`FixedTime::alpha`](bevy_time::FixedTime::alpha).
+///
+/// The interpolated [`Transform`] is only visible outside of the fixed update. Fixed update
+/// systems always see the actual state of the simulation.
+///
+/// Changing the [`Transform`] outside of the fixed update moves the entity instantly, without
+/// interpolation.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct TransformInterpolation {
+	pub(crate) previous: Option<Transform>,
+	pub(crate) current: Option<Transform>,
+	pub(crate) interpolated: Option<Transform>,
+}
+
+impl TransformInterpolation {
+	/// The [`Transform`] before the last fixed step, if it was recorded.
+	#[inline]
+	pub fn previous(&self) -> Option<Transform> {
+		self.previous
+	}
+
+	/// The [`Transform`] after the last fixed step, if it was recorded.
+	#[inline]
+	pub fn current(&self) -> Option<Transform> {
+		self.current
+	}
+
+	/// Drops the recorded steps, so the next one is interpolated from `transform`.
+	pub(crate) fn reset(&mut self, transform: Transform) {
+		self.previous = Some(transform);
+		self.current = Some(transform);
+	}
+}
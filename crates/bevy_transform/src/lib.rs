@@ -4,17 +4,21 @@
 /// The basic components of the transform crate
 pub mod components;
 mod systems;
-pub use crate::systems::transform_propagate_system;
+pub use crate::systems::{
+	record_transform_interpolation_system, restore_transform_interpolation_system,
+	transform_interpolation_system, transform_propagate_system,
+};
 
 #[doc(hidden)]
 pub mod prelude {
 	#[doc(hidden)]
-	pub use crate::{components::*, TransformBundle, TransformPlugin};
+	pub use crate::{components::*, TransformBundle, TransformInterpolationPlugin, TransformPlugin};
 }
 
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
-use prelude::{GlobalTransform, Transform};
+use bevy_time::FixedUpdateStage;
+use prelude::{GlobalTransform, Transform, TransformInterpolation};
 
 /// A [`Bundle`] of the [`Transform`] and [`GlobalTransform`]
 /// [`Component`](bevy_ecs::component::Component)s, which describe the position of an entity.
@@ -81,6 +85,9 @@ impl From<Transform> for TransformBundle {
 pub enum TransformSystem {
 	/// Propagates changes in transform to children's [`GlobalTransform`](crate::components::GlobalTransform)
 	TransformPropagate,
+	/// Interpolates the [`Transform`] of entities with a
+	/// [`TransformInterpolation`](crate::components::TransformInterpolation) between fixed steps
+	TransformInterpolate,
 }
 
 /// The base plugin for handling [`Transform`] components
@@ -102,3 +109,32 @@ impl Plugin for TransformPlugin {
 		);
 	}
 }
+
+/// Adds support for the [`TransformInterpolation`] component.
+///
+/// Requires the [`TimePlugin`](bevy_time::TimePlugin), which provides the
+/// [`FixedUpdateStage`].
+#[derive(Default)]
+pub struct TransformInterpolationPlugin;
+
+impl Plugin for TransformInterpolationPlugin {
+	fn build(&self, app: &mut App) {
+		app.register_type::<TransformInterpolation>();
+		app.add_system_to_stage(
+			CoreStage::PreUpdate,
+			systems::restore_transform_interpolation_system,
+		);
+		app.add_system_to_stage(
+			FixedUpdateStage,
+			systems::record_transform_interpolation_system
+				.exclusive_system()
+				.at_end(),
+		);
+		app.add_system_to_stage(
+			CoreStage::PostUpdate,
+			systems::transform_interpolation_system
+				.label(TransformSystem::TransformInterpolate)
+				.before(TransformSystem::TransformPropagate),
+		);
+	}
+}
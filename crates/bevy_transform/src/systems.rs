@@ -1,6 +1,7 @@
-use crate::components::{GlobalTransform, Transform};
-use bevy_ecs::prelude::{Changed, Entity, Query, With, Without};
+use crate::components::{GlobalTransform, Transform, TransformInterpolation};
+use bevy_ecs::prelude::{Changed, Entity, Query, Res, With, Without};
 use bevy_hierarchy::{Children, Parent};
+use bevy_time::FixedTime;
 
 /// Update [`GlobalTransform`] component of entities based on entity hierarchy and
 /// [`Transform`] component.
@@ -93,6 +94,57 @@ fn propagate_recursive(
 	Ok(())
 }
 
+/// Restores the [`Transform`] of interpolated entities to the state of the last fixed step, before
+/// the [`FixedUpdateStage`](bevy_time::FixedUpdateStage) runs.
+pub fn restore_transform_interpolation_system(
+	mut query: Query<(&mut Transform, &mut TransformInterpolation)>,
+) {
+	query.for_each_mut(|(mut transform, mut interpolation)| {
+		match (interpolation.interpolated.take(), interpolation.current) {
+			(Some(interpolated), Some(current)) if *transform == interpolated => *transform = current,
+			(None, Some(_)) => {},
+			// The transform was changed after being interpolated or was never recorded, keep it as is
+			_ => interpolation.reset(*transform),
+		}
+	});
+}
+
+/// Records the [`Transform`] of interpolated entities at the end of each fixed step.
+pub fn record_transform_interpolation_system(
+	mut query: Query<(&Transform, &mut TransformInterpolation)>,
+) {
+	query.for_each_mut(|(transform, mut interpolation)| {
+		interpolation.previous = Some(interpolation.current.unwrap_or(*transform));
+		interpolation.current = Some(*transform);
+	});
+}
+
+/// Sets the [`Transform`] of interpolated entities between the two last fixed steps, according
+/// to [`FixedTime::alpha`].
+pub fn transform_interpolation_system(
+	fixed_time: Res<FixedTime>,
+	mut query: Query<(&mut Transform, &mut TransformInterpolation)>,
+) {
+	let alpha = fixed_time.alpha();
+	query.for_each_mut(|(mut transform, mut interpolation)| {
+		match (interpolation.previous, interpolation.current) {
+			(Some(previous), Some(current)) if *transform == current => {
+				let interpolated = Transform {
+					translation: previous
+						.translation
+						.lerp(current.translation, alpha),
+					rotation: previous.rotation.slerp(current.rotation, alpha),
+					scale: previous.scale.lerp(current.scale, alpha),
+				};
+				*transform = interpolated;
+				interpolation.interpolated = Some(interpolated);
+			},
+			// The transform was changed outside of the fixed update, teleport
+			_ => interpolation.reset(*transform),
+		}
+	});
+}
+
 #[cfg(test)]
 mod test {
 	use bevy_app::prelude::*;
@@ -100,10 +152,15 @@ mod test {
 	use bevy_ecs::system::CommandQueue;
 	use bevy_math::vec3;
 
-	use crate::components::{GlobalTransform, Transform};
-	use crate::systems::transform_propagate_system;
+	use crate::components::{GlobalTransform, Transform, TransformInterpolation};
+	use crate::systems::{
+		record_transform_interpolation_system, restore_transform_interpolation_system,
+		transform_interpolation_system, transform_propagate_system,
+	};
 	use crate::TransformBundle;
 	use bevy_hierarchy::{BuildChildren, BuildWorldChildren, Children, Parent};
+	use bevy_time::{fixed_update_run_criteria, FixedTime, FixedUpdateStage, Time};
+	use bevy_utils::{Duration, Instant};
 
 	#[test]
 	fn did_propagate() {
@@ -377,4 +434,74 @@ mod test {
 
 		app.update();
 	}
+
+	#[test]
+	fn interpolate_between_fixed_steps() {
+		let mut world = World::default();
+		let start = Instant::now();
+		let mut time = Time::default();
+		time.update_with_instant(start);
+		world.insert_resource(time);
+		world.insert_resource(FixedTime::from_seconds(0.5));
+
+		let mut schedule = Schedule::default();
+		schedule.add_stage(
+			CoreStage::PreUpdate,
+			SystemStage::single_threaded().with_system(restore_transform_interpolation_system),
+		);
+		schedule.add_stage(
+			FixedUpdateStage,
+			SystemStage::single_threaded()
+				.with_run_criteria(fixed_update_run_criteria)
+				.with_system(|mut query: Query<&mut Transform>| {
+					for mut transform in &mut query {
+						transform.translation.x += 1.0;
+					}
+				})
+				.with_system(
+					record_transform_interpolation_system
+						.exclusive_system()
+						.at_end(),
+				),
+		);
+		schedule.add_stage(
+			CoreStage::PostUpdate,
+			SystemStage::single_threaded().with_system(transform_interpolation_system),
+		);
+
+		let entity = world
+			.spawn()
+			.insert_bundle((Transform::IDENTITY, TransformInterpolation::default()))
+			.id();
+		let mut advance = |world: &mut World, seconds: f64| {
+			world
+				.resource_mut::<Time>()
+				.update_with_instant(start + Duration::from_secs_f64(seconds));
+			schedule.run(world);
+			world
+				.get::<Transform>(entity)
+				.unwrap()
+				.translation
+				.x
+		};
+
+		// no step ran yet, nothing to interpolate
+		assert_eq!(advance(&mut world, 0.25), 0.0);
+		// first step, interpolated from the state before it
+		assert_eq!(advance(&mut world, 0.75), 0.5);
+		// second step, halfway between the first and second step
+		assert_eq!(advance(&mut world, 1.25), 1.5);
+		// no step ran, further between the first and second step
+		assert_eq!(advance(&mut world, 1.375), 1.75);
+
+		// teleporting outside of the fixed update isn't interpolated
+		world
+			.get_mut::<Transform>(entity)
+			.unwrap()
+			.translation
+			.x = 10.0;
+		assert_eq!(advance(&mut world, 1.375), 10.0);
+		// the next step starts from the new position
+		assert_eq!(advance(&mut world, 1.75), 10.5);
+	}
 }
@@ -5,6 +5,7 @@ use bevy_math::{IVec2, Vec2};
 
 /// A window event that is sent whenever a window's logical size has changed.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowResized {
 	pub id: WindowId,
 	/// The new logical width of the window.
@@ -46,6 +47,7 @@ pub struct WindowCreated {
 /// [`Window`]: crate::Window
 /// [closing]: crate::Window::close
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowCloseRequested {
 	pub id: WindowId,
 }
@@ -75,6 +77,7 @@ impl fmt::Display for WindowClosed {
 /// [`WindowEvent::CursorMoved`]: https://docs.rs/winit/latest/winit/event/enum.WindowEvent.html#variant.CursorMoved
 /// [`MouseMotion`]: bevy_input::mouse::MouseMotion
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct CursorMoved {
 	/// The identifier of the window the cursor has moved on.
 	pub id: WindowId,
@@ -84,17 +87,20 @@ pub struct CursorMoved {
 }
 /// An event that is sent whenever the user's cursor enters a window.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct CursorEntered {
 	pub id: WindowId,
 }
 /// An event that is sent whenever the user's cursor leaves a window.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct CursorLeft {
 	pub id: WindowId,
 }
 
 /// An event that is sent whenever a window receives a character from the OS or underlying system.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReceivedCharacter {
 	pub id: WindowId,
 	pub char: char,
@@ -108,6 +114,7 @@ impl fmt::Display for ReceivedCharacter {
 
 /// An event that indicates a window has received or lost focus.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowFocused {
 	pub id: WindowId,
 	pub focused: bool,
@@ -115,12 +122,14 @@ pub struct WindowFocused {
 
 /// An event that indicates a window's scale factor has changed.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowScaleFactorChanged {
 	pub id: WindowId,
 	pub scale_factor: f64,
 }
 /// An event that indicates a window's OS-reported scale factor has changed.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowBackendScaleFactorChanged {
 	pub id: WindowId,
 	pub scale_factor: f64,
@@ -128,6 +137,7 @@ pub struct WindowBackendScaleFactorChanged {
 
 /// Events related to files being dragged and dropped on a window.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum FileDragAndDrop {
 	DroppedFile { id: WindowId, path_buf: PathBuf },
 
@@ -138,6 +148,7 @@ pub enum FileDragAndDrop {
 
 /// An event that is sent when a window is repositioned in physical pixels.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowMoved {
 	pub id: WindowId,
 	pub position: IVec2,
@@ -35,6 +35,21 @@ impl Plugin for WindowPlugin {
 		app.add_event::<WindowMoved>();
 		app.init_resource::<Windows>();
 
+		#[cfg(feature = "bevy_ci_testing")]
+		{
+			app.add_recorded_event::<WindowResized>();
+			app.add_recorded_event::<WindowCloseRequested>();
+			app.add_recorded_event::<CursorMoved>();
+			app.add_recorded_event::<CursorEntered>();
+			app.add_recorded_event::<CursorLeft>();
+			app.add_recorded_event::<ReceivedCharacter>();
+			app.add_recorded_event::<WindowFocused>();
+			app.add_recorded_event::<WindowScaleFactorChanged>();
+			app.add_recorded_event::<WindowBackendScaleFactorChanged>();
+			app.add_recorded_event::<FileDragAndDrop>();
+			app.add_recorded_event::<WindowMoved>();
+		}
+
 		let settings = app
 			.world
 			.get_resource::<WindowSettings>()
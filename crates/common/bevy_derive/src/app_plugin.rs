@@ -14,5 +14,16 @@ pub fn derive_dynamic_plugin(input: TokenStream) -> TokenStream {
 			let boxed = Box::new(object);
 			Box::into_raw(boxed)
 		}
+
+		#[no_mangle]
+		pub extern "C" fn _bevy_plugin_build_fingerprint() -> *const ::std::os::raw::c_char {
+			// the fingerprint of the bevy build the plugin is compiled against
+			bevy::BUILD_FINGERPRINT.as_ptr().cast()
+		}
+
+		#[no_mangle]
+		pub extern "C" fn _bevy_plugin_crate_name() -> *const ::std::os::raw::c_char {
+			concat!(env!("CARGO_CRATE_NAME"), "\0").as_ptr().cast()
+		}
 	})
 }
@@ -10,7 +10,9 @@ use bevy_manifest::BevyManifest;
 use proc_macro::TokenStream;
 use quote::format_ident;
 
-/// Generates a dynamic plugin entry point function for the given `Plugin` type.
+/// Generates a dynamic plugin entry point function for the given `Plugin` type, along with the
+/// functions returning the build fingerprint and the crate name of the plugin checked by the
+/// dynamic plugin loader.
 #[proc_macro_derive(DynamicPlugin)]
 pub fn derive_dynamic_plugin(input: TokenStream) -> TokenStream {
 	app_plugin::derive_dynamic_plugin(input)
@@ -244,6 +244,26 @@ impl Archetype {
 		&mut self.unique_components
 	}
 
+	/// Removes the component `id` from the archetype, once its type is
+	/// [forgotten](crate::world::World::forget_components). The archetype must not store any
+	/// value of the component.
+	pub(crate) fn forget_component(&mut self, id: ComponentId) {
+		self.components.remove(id);
+		self.unique_components.remove(id);
+		self.table_components = self
+			.table_components
+			.iter()
+			.copied()
+			.filter(|component_id| *component_id != id)
+			.collect();
+		self.sparse_set_components = self
+			.sparse_set_components
+			.iter()
+			.copied()
+			.filter(|component_id| *component_id != id)
+			.collect();
+	}
+
 	#[inline]
 	pub fn components(&self) -> impl Iterator<Item = ComponentId> + '_ {
 		self.components.indices()
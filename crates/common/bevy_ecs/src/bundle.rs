@@ -604,6 +604,17 @@ impl Bundles {
 		self.bundle_ids.get(&type_id).cloned()
 	}
 
+	/// Forgets the bundle types containing the component `id`, so that they are initialized again
+	/// with new [`ComponentId`]s after the component is [forgotten](Components::forget).
+	pub(crate) fn forget_component(&mut self, id: ComponentId) {
+		let bundle_infos = &self.bundle_infos;
+		self.bundle_ids.retain(|_, bundle_id| {
+			!bundle_infos[bundle_id.index()]
+				.component_ids
+				.contains(&id)
+		});
+	}
+
 	pub(crate) fn init_info<'a, T: Bundle>(
 		&'a mut self,
 		components: &mut Components,
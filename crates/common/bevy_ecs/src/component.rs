@@ -522,6 +522,50 @@ impl Components {
 	pub fn iter(&self) -> impl Iterator<Item = &ComponentInfo> + '_ {
 		self.components.iter()
 	}
+
+	/// Forgets the type of the component or resource `id`: registering the type again creates a
+	/// new [`ComponentId`], and the [`ComponentInfo`] of `id` no longer refers to the code or
+	/// static data of the type.
+	///
+	/// # Safety
+	///
+	/// No value of the component or resource may be stored.
+	pub(crate) unsafe fn forget(&mut self, id: ComponentId) {
+		let info = &mut self.components[id.0];
+		if let Some(type_id) = info.descriptor.type_id.take() {
+			if self.indices.get(&type_id) == Some(&id.0) {
+				self.indices.remove(&type_id);
+			}
+			if self.resource_indices.get(&type_id) == Some(&id.0) {
+				self.resource_indices.remove(&type_id);
+			}
+		}
+		info.descriptor.name = Cow::Owned(info.descriptor.name.to_string());
+		info.descriptor.drop = None;
+	}
+}
+
+/// An error returned by [`World::forget_components`](crate::world::World::forget_components).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForgetComponentsError {
+	/// A value of the named component or resource type is still stored in the world.
+	InUse(String),
+}
+
+impl std::error::Error for ForgetComponentsError {}
+
+impl std::fmt::Display for ForgetComponentsError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			ForgetComponentsError::InUse(name) => {
+				write!(
+					f,
+					"a value of the type {} is still stored in the world",
+					name
+				)
+			},
+		}
+	}
 }
 
 /// Records when a component was added and when it was last mutably dereferenced (or added).
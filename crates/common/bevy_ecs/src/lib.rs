@@ -24,7 +24,7 @@ pub use bevy_ptr as ptr;
 pub mod prelude {
 	#[doc(hidden)]
 	#[cfg(feature = "bevy_reflect")]
-	pub use crate::reflect::{ReflectComponent, ReflectResource};
+	pub use crate::reflect::{ReflectComponent, ReflectEvent, ReflectResource};
 	#[doc(hidden)]
 	pub use crate::{
 		bundle::Bundle,
@@ -4,6 +4,7 @@ pub use crate::change_detection::ReflectMut;
 use crate::{
 	component::Component,
 	entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+	event::Event,
 	system::Resource,
 	world::{FromWorld, World},
 };
@@ -288,6 +289,34 @@ impl<C: Resource + Reflect + FromWorld> FromType<C> for ReflectResource {
 	}
 }
 
+/// A struct used to send reflected [`Event`]s of a type.
+///
+/// A [`ReflectEvent`] for type `T` can be obtained via
+/// [`bevy_reflect::TypeRegistration::data`].
+#[derive(Clone)]
+pub struct ReflectEvent {
+	send: fn(&mut World, &dyn Reflect),
+}
+
+impl ReflectEvent {
+	/// Sends a reflected [`Event`] like [`send_event()`](World::send_event).
+	pub fn send(&self, world: &mut World, event: &dyn Reflect) {
+		(self.send)(world, event);
+	}
+}
+
+impl<E: Event + Reflect + FromWorld> FromType<E> for ReflectEvent {
+	fn from_type() -> Self {
+		ReflectEvent {
+			send: |world, reflected_event| {
+				let mut event = E::from_world(world);
+				event.apply(reflected_event);
+				world.send_event(event);
+			},
+		}
+	}
+}
+
 impl_reflect_value!(Entity(Hash, PartialEq, Serialize, Deserialize));
 impl_from_reflect_value!(Entity);
 
@@ -320,3 +349,34 @@ impl<C: Component + MapEntities> FromType<C> for ReflectMapEntities {
 		}
 	}
 }
+
+/// The [`ReflectMapEntities`] of resources, remapping the entities stored in a [`Resource`].
+#[derive(Clone)]
+pub struct ReflectMapEntitiesResource {
+	map_entities: fn(&mut World, &EntityMap) -> Result<(), MapEntitiesError>,
+}
+
+impl ReflectMapEntitiesResource {
+	/// Maps the entities stored in this [`Resource`] type in the world. Does nothing if it doesn't
+	/// exist.
+	pub fn map_entities(
+		&self,
+		world: &mut World,
+		entity_map: &EntityMap,
+	) -> Result<(), MapEntitiesError> {
+		(self.map_entities)(world, entity_map)
+	}
+}
+
+impl<R: Resource + MapEntities> FromType<R> for ReflectMapEntitiesResource {
+	fn from_type() -> Self {
+		ReflectMapEntitiesResource {
+			map_entities: |world, entity_map| {
+				if let Some(mut resource) = world.get_resource_mut::<R>() {
+					resource.map_entities(entity_map)?;
+				}
+				Ok(())
+			},
+		}
+	}
+}
@@ -334,6 +334,26 @@ impl Schedule {
 			.and_then(|stage| stage.downcast_mut::<T>())
 	}
 
+	/// Removes the systems whose name matches `predicate` from the [`SystemStage`]s of the
+	/// schedule and of its nested schedules, returning the number of removed systems.
+	///
+	/// See [`SystemStage::remove_systems`].
+	pub fn remove_systems(&mut self, mut predicate: impl FnMut(&str) -> bool) -> usize {
+		self.remove_systems_inner(&mut predicate)
+	}
+
+	fn remove_systems_inner(&mut self, predicate: &mut dyn FnMut(&str) -> bool) -> usize {
+		let mut removed = 0;
+		for stage in self.stages.values_mut() {
+			if let Some(stage) = stage.downcast_mut::<SystemStage>() {
+				removed += stage.remove_systems(&mut *predicate);
+			} else if let Some(schedule) = stage.downcast_mut::<Schedule>() {
+				removed += schedule.remove_systems_inner(predicate);
+			}
+		}
+		removed
+	}
+
 	/// Executes each [`Stage`] contained in the schedule, one at a time.
 	pub fn run_once(&mut self, world: &mut World) {
 		for label in &self.stage_order {
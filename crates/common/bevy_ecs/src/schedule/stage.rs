@@ -201,6 +201,34 @@ impl SystemStage {
 		}
 	}
 
+	/// Removes the systems whose name matches `predicate`, returning the number of removed
+	/// systems. The other systems are ordered again the next time the stage runs.
+	///
+	/// The run criteria of the removed systems are kept.
+	pub fn remove_systems(&mut self, mut predicate: impl FnMut(&str) -> bool) -> usize {
+		let removed = remove_containers(
+			&mut self.exclusive_at_start,
+			&mut self.uninitialized_at_start,
+			&mut predicate,
+		) + remove_containers(
+			&mut self.exclusive_before_commands,
+			&mut self.uninitialized_before_commands,
+			&mut predicate,
+		) + remove_containers(
+			&mut self.exclusive_at_end,
+			&mut self.uninitialized_at_end,
+			&mut predicate,
+		) + remove_containers(
+			&mut self.parallel,
+			&mut self.uninitialized_parallel,
+			&mut predicate,
+		);
+		if removed > 0 {
+			self.systems_modified = true;
+		}
+		removed
+	}
+
 	pub fn apply_buffers(&mut self, world: &mut World) {
 		for container in &mut self.parallel {
 			let system = container.system_mut();
@@ -806,6 +834,31 @@ fn find_ambiguities(systems: &[impl SystemContainer]) -> Vec<(usize, usize, Vec<
 	ambiguities
 }
 
+/// Removes the system containers whose name matches `predicate`, updating the indices of the
+/// uninitialized containers, and returns the number of removed containers.
+fn remove_containers<T: SystemContainer>(
+	containers: &mut Vec<T>,
+	uninitialized: &mut Vec<usize>,
+	predicate: &mut impl FnMut(&str) -> bool,
+) -> usize {
+	let mut new_indices = Vec::with_capacity(containers.len());
+	let mut removed = 0;
+	for container in std::mem::take(containers) {
+		if predicate(&container.name()) {
+			new_indices.push(None);
+			removed += 1;
+		} else {
+			new_indices.push(Some(containers.len()));
+			containers.push(container);
+		}
+	}
+	*uninitialized = uninitialized
+		.iter()
+		.filter_map(|index| new_indices[*index])
+		.collect();
+	removed
+}
+
 impl Stage for SystemStage {
 	fn run(&mut self, world: &mut World) {
 		if let Some(world_id) = self.world_id {
@@ -1411,6 +1464,30 @@ mod tests {
 		assert_eq!(*world.resource::<Vec<usize>>(), vec![0, 1, 2, 0, 1, 2]);
 	}
 
+	#[test]
+	fn remove_systems() {
+		fn kept(mut resource: ResMut<Vec<usize>>) {
+			resource.push(0);
+		}
+		fn removed(mut resource: ResMut<Vec<usize>>) {
+			resource.push(1);
+		}
+		fn removed_exclusive(world: &mut World) {
+			world.resource_mut::<Vec<usize>>().push(2);
+		}
+
+		let mut world = World::new();
+		world.insert_resource(Vec::<usize>::new());
+		let mut stage = SystemStage::parallel()
+			.with_system(removed.label("1"))
+			.with_system(removed_exclusive.exclusive_system().at_start())
+			.with_system(kept.after("1"));
+		stage.run(&mut world);
+		assert_eq!(stage.remove_systems(|name| name.contains("removed")), 2);
+		stage.run(&mut world);
+		assert_eq!(*world.resource::<Vec<usize>>(), vec![2, 1, 0, 0]);
+	}
+
 	#[test]
 	fn parallel_before() {
 		let mut world = World::new();
@@ -452,6 +452,12 @@ impl SparseSets {
 		}
 	}
 
+	/// Removes the sparse set of the component `id`, once its type is
+	/// [forgotten](crate::world::World::forget_components).
+	pub(crate) fn forget(&mut self, component_id: ComponentId) {
+		self.sets.remove(component_id);
+	}
+
 	pub(crate) fn check_change_ticks(&mut self, change_tick: u32) {
 		for set in self.sets.values_mut() {
 			set.check_change_ticks(change_tick);
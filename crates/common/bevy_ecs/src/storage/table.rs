@@ -508,6 +508,16 @@ impl Tables {
 		self.tables.iter()
 	}
 
+	/// Removes the columns of the component `id` from the tables, once its type is
+	/// [forgotten](crate::world::World::forget_components). The tables storing the component must
+	/// be empty.
+	pub(crate) fn forget_column(&mut self, id: ComponentId) {
+		for table in &mut self.tables {
+			debug_assert!(!table.has_column(id) || table.is_empty());
+			table.columns.remove(id);
+		}
+	}
+
 	pub(crate) fn clear(&mut self) {
 		for table in &mut self.tables {
 			table.clear();
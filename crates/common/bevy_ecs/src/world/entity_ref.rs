@@ -126,6 +126,19 @@ impl<'w> EntityRef<'w> {
 		// SAFETY: entity_location is valid, component_id is valid as checked by the line above
 		unsafe { get_component(self.world, component_id, self.entity, self.location) }
 	}
+
+	/// Retrieves the change ticks for the component of the given [`ComponentId`].
+	///
+	/// **You should prefer to use the typed API [`EntityRef::get_change_ticks`] where possible and
+	/// only use this in cases where the actual component types are not known at compile time.**
+	#[inline]
+	pub fn get_change_ticks_by_id(&self, component_id: ComponentId) -> Option<&'w ComponentTicks> {
+		self.world.components().get_info(component_id)?;
+		// SAFETY: entity_location is valid, component_id is valid as checked by the line above
+		unsafe {
+			get_ticks(self.world, component_id, self.entity, self.location).map(|ticks| ticks.deref())
+		}
+	}
 }
 
 /// A mutable reference to a particular [`Entity`] and all of its components
@@ -13,7 +13,7 @@ use crate::{
 	change_detection::{MutUntyped, Ticks},
 	component::{
 		Component, ComponentDescriptor, ComponentId, ComponentInfo, ComponentTicks, Components,
-		StorageType,
+		ForgetComponentsError, StorageType,
 	},
 	entity::{AllocAtWithoutReplacement, Entities, Entity},
 	query::{QueryState, WorldQuery},
@@ -1373,6 +1373,32 @@ impl World {
 			})
 	}
 
+	/// Makes `thread` the main thread of this [`World`], the only thread allowed to access its
+	/// `NonSend` resources and to run its systems with `NonSend` parameters.
+	///
+	/// This is used when moving a whole world to another thread, for example to run it
+	/// alongside the world of the main thread.
+	///
+	/// # Panics
+	///
+	/// Panics if the world contains non-send resources, as they can't leave the thread they
+	/// were inserted on.
+	pub fn set_main_thread(&mut self, thread: std::thread::ThreadId) {
+		if thread == self.thread {
+			return;
+		}
+		let resource_archetype = self.archetypes.resource();
+		for (component_id, column) in resource_archetype.unique_components().iter() {
+			let info = self.components.get_info(*component_id).unwrap();
+			assert!(
+				info.is_send_and_sync() || column.is_empty(),
+				"cannot move a world containing the NonSend resource {} to another thread",
+				info.name()
+			);
+		}
+		self.thread = thread;
+	}
+
 	pub(crate) fn validate_non_send_access<T: 'static>(&self) {
 		assert!(
 			self.thread == std::thread::current().id(),
@@ -1471,6 +1497,21 @@ impl World {
 		Some(column.get_data_ptr())
 	}
 
+	/// Retrieves the change ticks of the resource with the id [`ComponentId`] if it exists.
+	///
+	/// **You should prefer to use the typed API [`World::is_resource_changed`] where possible and
+	/// only use this in cases where the actual types are not known at compile time.**
+	#[inline]
+	pub fn get_resource_change_ticks_by_id(
+		&self,
+		component_id: ComponentId,
+	) -> Option<&ComponentTicks> {
+		self.components.get_info(component_id)?;
+		let column = self.get_populated_resource_column(component_id)?;
+		// SAFETY: resources table always have row 0
+		Some(unsafe { column.get_ticks_unchecked(0).deref() })
+	}
+
 	/// Gets a resource to the resource with the id [`ComponentId`] if it exists.
 	/// The returned pointer may be used to modify the resource, as long as the mutable borrow
 	/// of the [`World`] is still valid.
@@ -1526,6 +1567,56 @@ impl World {
 		Some(())
 	}
 
+	/// Forgets the component and resource types whose [`ComponentInfo`] matches `predicate`,
+	/// returning the number of forgotten types.
+	///
+	/// Using a forgotten type again registers it with a new [`ComponentId`], and the
+	/// [`ComponentInfo`]s of forgotten types no longer refer to their code or static data, so the
+	/// code defining the types can be unloaded, for example a dynamically loaded plugin. The
+	/// storages of the forgotten types are removed from the tables, sparse sets and archetypes of
+	/// the world, with the drop functions of the types.
+	///
+	/// Nothing is forgotten if an entity still has one of the components or one of the resources is
+	/// still inserted: they must be removed first.
+	pub fn forget_components(
+		&mut self,
+		mut predicate: impl FnMut(&ComponentInfo) -> bool,
+	) -> Result<usize, ForgetComponentsError> {
+		let component_ids: Vec<_> = self
+			.components
+			.iter()
+			.filter(|info| predicate(info))
+			.map(|info| info.id())
+			.collect();
+		let resource_archetype = self.archetypes.resource();
+		for &component_id in &component_ids {
+			let in_use = self
+				.archetypes
+				.iter()
+				.any(|archetype| archetype.contains(component_id) && !archetype.is_empty())
+				|| matches!(
+					resource_archetype.unique_components().get(component_id),
+					Some(column) if !column.is_empty()
+				);
+			if in_use {
+				// SAFETY: the id was just returned by `Components::iter`
+				let name = unsafe { self.components.get_info_unchecked(component_id) }.name();
+				return Err(ForgetComponentsError::InUse(name.to_string()));
+			}
+		}
+		for &component_id in &component_ids {
+			self.bundles.forget_component(component_id);
+			self.storages.tables.forget_column(component_id);
+			self.storages.sparse_sets.forget(component_id);
+			for archetype in &mut self.archetypes.archetypes {
+				archetype.forget_component(component_id);
+			}
+			// SAFETY: no entity has the component and the resource is not inserted, as checked above
+			unsafe { self.components.forget(component_id) };
+		}
+		Ok(component_ids.len())
+	}
+
 	/// Retrieves an immutable untyped reference to the given `entity`'s [Component] of the given [`ComponentId`].
 	/// Returns [None] if the `entity` does not have a [Component] of the given type.
 	///
@@ -1615,7 +1706,9 @@ mod tests {
 	use super::World;
 	use crate::{
 		change_detection::DetectChanges,
-		component::{ComponentDescriptor, ComponentId, ComponentInfo, StorageType},
+		component::{
+			ComponentDescriptor, ComponentId, ComponentInfo, ForgetComponentsError, StorageType,
+		},
 		ptr::OwningPtr,
 	};
 	use bevy_ecs_macros::Component;
@@ -1922,4 +2015,69 @@ mod tests {
 			[Some(baz_id)].into()
 		);
 	}
+
+	#[test]
+	fn forget_components() {
+		#[derive(Component)]
+		struct Foo(String);
+		#[derive(Component)]
+		struct Bar;
+		struct Baz(String);
+
+		let mut world = World::new();
+		let entity = world
+			.spawn()
+			.insert_bundle((Foo("foo".into()), Bar))
+			.id();
+		world.insert_resource(Baz("baz".into()));
+		let foo_id = world
+			.components()
+			.component_id::<Foo>()
+			.unwrap();
+		let baz_id = world
+			.components()
+			.get_resource_id(TypeId::of::<Baz>())
+			.unwrap();
+		let is_forgotten = |info: &ComponentInfo| info.id() == foo_id || info.id() == baz_id;
+
+		assert_eq!(
+			world.forget_components(is_forgotten),
+			Err(ForgetComponentsError::InUse(
+				std::any::type_name::<Foo>().to_string()
+			))
+		);
+		world.entity_mut(entity).remove::<Foo>();
+		assert!(world.forget_components(is_forgotten).is_err());
+		world.remove_resource::<Baz>();
+		assert_eq!(world.forget_components(is_forgotten), Ok(2));
+
+		assert_eq!(world.components().component_id::<Foo>(), None);
+		assert_eq!(
+			world
+				.components()
+				.get_resource_id(TypeId::of::<Baz>()),
+			None
+		);
+		// the storages of the forgotten types are removed
+		assert!(world
+			.storages()
+			.tables
+			.iter()
+			.all(|table| !table.has_column(foo_id)));
+		assert!(world
+			.archetypes()
+			.iter()
+			.all(|archetype| !archetype.contains(foo_id) && !archetype.contains(baz_id)));
+		let foo_info = world.components().get_info(foo_id).unwrap();
+		assert_eq!(foo_info.type_id(), None);
+		assert!(foo_info.drop().is_none());
+
+		world
+			.entity_mut(entity)
+			.insert_bundle((Foo("foo".into()), Bar));
+		world.insert_resource(Baz("baz".into()));
+		assert_ne!(world.components().component_id::<Foo>(), Some(foo_id));
+		assert_eq!(world.get::<Foo>(entity).unwrap().0, "foo");
+		assert_eq!(world.resource::<Baz>().0, "baz");
+	}
 }
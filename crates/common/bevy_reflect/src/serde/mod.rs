@@ -1,7 +1,11 @@
 mod de;
+#[cfg(feature = "json_schema")]
+mod schema;
 mod ser;
 
 pub use de::*;
+#[cfg(feature = "json_schema")]
+pub use schema::*;
 pub use ser::*;
 
 pub(crate) mod type_fields {
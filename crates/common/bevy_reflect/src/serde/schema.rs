@@ -0,0 +1,521 @@
+use crate::{
+	serde::type_fields, ArrayInfo, ListInfo, MapInfo, ReflectSerialize, StructInfo, TupleInfo,
+	TupleStructInfo, TypeInfo, TypeRegistry, ValueInfo,
+};
+use serde_json::{json, Map, Value};
+use std::{any::TypeId, collections::VecDeque, time::Duration};
+
+/// The JSON Schema dialect emitted by [`ReflectSchemaGenerator`].
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Generates [JSON Schema] documents describing the output of [`ReflectSerializer`].
+///
+/// Every type becomes one entry in the `$defs` of the generated document, keyed by its
+/// [type name]. Fields and items reference the definition of their own type, so the schema
+/// follows the exact same nesting as the serialized data. Maps only accept an empty object, as
+/// their keys are serialized as objects which JSON object keys cannot represent.
+///
+/// Values (see [`TypeInfo::Value`]) are serialized through their [`ReflectSerialize`] type data,
+/// which is opaque to reflection. Primitives, [`String`], [`char`] and [`Duration`] get a precise
+/// schema, any other serializable value accepts anything, and values that did not register
+/// [`ReflectSerialize`] accept nothing, since they cannot be serialized in the first place.
+///
+/// Types referenced by a registered type but missing from the registry only have their
+/// `type` field validated.
+///
+/// # Example
+///
+/// ```
+/// use bevy_reflect::{serde::ReflectSchemaGenerator, Reflect, TypeRegistry};
+///
+/// #[derive(Reflect)]
+/// struct Player {
+///   name: String,
+///   health: f32,
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<Player>();
+/// registry.register::<String>();
+///
+/// let generator = ReflectSchemaGenerator::new(&registry);
+/// let schema = generator.type_schema::<Player>().unwrap();
+/// assert!(schema["$defs"]["alloc::string::String"].is_object());
+/// ```
+///
+/// [JSON Schema]: https://json-schema.org
+/// [`ReflectSerializer`]: crate::serde::ReflectSerializer
+/// [type name]: std::any::type_name
+pub struct ReflectSchemaGenerator<'a> {
+	registry: &'a TypeRegistry,
+}
+
+impl<'a> ReflectSchemaGenerator<'a> {
+	pub fn new(registry: &'a TypeRegistry) -> Self {
+		ReflectSchemaGenerator { registry }
+	}
+
+	/// Returns a schema accepting a serialized value of any registered type.
+	///
+	/// The `$defs` of the returned document contain a definition for every registered type.
+	pub fn registry_schema(&self) -> Value {
+		let mut type_names = self
+			.registry
+			.iter()
+			.map(|registration| registration.type_name())
+			.collect::<Vec<_>>();
+		type_names.sort_unstable();
+
+		let any_of = type_names
+			.iter()
+			.map(|type_name| definition_ref(type_name))
+			.collect::<Vec<_>>();
+		let definitions = self.definitions(
+			self
+				.registry
+				.iter()
+				.map(|registration| (registration.type_id(), registration.type_name())),
+		);
+
+		json!({
+			"$schema": JSON_SCHEMA_DIALECT,
+			"anyOf": any_of,
+			"$defs": definitions,
+		})
+	}
+
+	/// Returns a schema accepting a serialized value of type `T`.
+	///
+	/// Returns `None` if `T` has not been registered.
+	pub fn type_schema<T: 'static>(&self) -> Option<Value> {
+		self.type_schema_by_id(TypeId::of::<T>())
+	}
+
+	/// Returns a schema accepting a serialized value of the type with the given [`TypeId`].
+	///
+	/// The `$defs` of the returned document only contain the definitions reachable from that type.
+	///
+	/// Returns `None` if the type has not been registered.
+	pub fn type_schema_by_id(&self, type_id: TypeId) -> Option<Value> {
+		let type_name = self.registry.get(type_id)?.type_name();
+		Some(json!({
+			"$schema": JSON_SCHEMA_DIALECT,
+			"$ref": definition_ref(type_name)["$ref"],
+			"$defs": self.definitions(std::iter::once((type_id, type_name))),
+		}))
+	}
+
+	/// Returns a schema accepting a serialized value of the type with the given [type name].
+	///
+	/// Returns `None` if no type with that name has been registered.
+	///
+	/// [type name]: std::any::type_name
+	pub fn type_schema_by_name(&self, type_name: &str) -> Option<Value> {
+		let type_id = self
+			.registry
+			.get_with_name(type_name)?
+			.type_id();
+		self.type_schema_by_id(type_id)
+	}
+
+	/// Returns the definition of a single type, referencing the definitions of the types it
+	/// contains through `#/$defs/<type name>`.
+	pub fn definition(&self, type_info: &TypeInfo) -> Value {
+		match type_info {
+			TypeInfo::Struct(info) => self.struct_definition(info),
+			TypeInfo::TupleStruct(info) => self.tuple_struct_definition(info),
+			TypeInfo::Tuple(info) => self.tuple_definition(info),
+			TypeInfo::List(info) => self.list_definition(info),
+			TypeInfo::Array(info) => self.array_definition(info),
+			TypeInfo::Map(info) => self.map_definition(info),
+			TypeInfo::Value(info) => self.value_definition(info),
+			// Dynamic types serialize as whatever they currently represent.
+			TypeInfo::Dynamic(info) => json!({ "title": info.type_name() }),
+		}
+	}
+
+	/// Collects the definitions of `roots` and of every type they transitively reference.
+	fn definitions(&self, roots: impl Iterator<Item = (TypeId, &'static str)>) -> Map<String, Value> {
+		let mut definitions = Map::new();
+		let mut queue = roots.collect::<VecDeque<_>>();
+		while let Some((type_id, type_name)) = queue.pop_front() {
+			if definitions.contains_key(type_name) {
+				continue;
+			}
+			let definition = match self.registry.get_type_info(type_id) {
+				Some(type_info) => {
+					queue.extend(dependencies(type_info));
+					self.definition(type_info)
+				},
+				None => unregistered_definition(type_name),
+			};
+			definitions.insert(type_name.to_string(), definition);
+		}
+		definitions
+	}
+
+	fn struct_definition(&self, info: &StructInfo) -> Value {
+		let properties = info
+			.iter()
+			.map(|field| (field.name().to_string(), definition_ref(field.type_name())))
+			.collect::<Map<_, _>>();
+		let required = info
+			.iter()
+			.map(|field| field.name().to_string())
+			.collect::<Vec<_>>();
+		tagged(
+			info.type_name(),
+			type_fields::STRUCT,
+			json!({
+				"type": "object",
+				"properties": properties,
+				"required": required,
+				"additionalProperties": false,
+			}),
+		)
+	}
+
+	fn tuple_struct_definition(&self, info: &TupleStructInfo) -> Value {
+		let items = info
+			.iter()
+			.map(|field| definition_ref(field.type_name()))
+			.collect::<Vec<_>>();
+		tagged(
+			info.type_name(),
+			type_fields::TUPLE_STRUCT,
+			tuple_items(items),
+		)
+	}
+
+	fn tuple_definition(&self, info: &TupleInfo) -> Value {
+		let items = info
+			.iter()
+			.map(|field| definition_ref(field.type_name()))
+			.collect::<Vec<_>>();
+		tagged(info.type_name(), type_fields::TUPLE, tuple_items(items))
+	}
+
+	fn list_definition(&self, info: &ListInfo) -> Value {
+		tagged(
+			info.type_name(),
+			type_fields::LIST,
+			json!({
+				"type": "array",
+				"items": definition_ref(info.item_type_name()),
+			}),
+		)
+	}
+
+	fn array_definition(&self, info: &ArrayInfo) -> Value {
+		tagged(
+			info.type_name(),
+			type_fields::ARRAY,
+			json!({
+				"type": "array",
+				"items": definition_ref(info.item_type_name()),
+				"minItems": info.capacity(),
+				"maxItems": info.capacity(),
+			}),
+		)
+	}
+
+	fn map_definition(&self, info: &MapInfo) -> Value {
+		// Map keys are serialized with `ReflectSerializer` as well, as objects which JSON object
+		// keys cannot represent: only empty maps can be written as JSON.
+		tagged(
+			info.type_name(),
+			type_fields::MAP,
+			json!({
+				"type": "object",
+				"maxProperties": 0,
+			}),
+		)
+	}
+
+	fn value_definition(&self, info: &ValueInfo) -> Value {
+		let value = if self
+			.registry
+			.get_type_data::<ReflectSerialize>(info.type_id())
+			.is_some()
+		{
+			primitive_schema(info.type_id()).unwrap_or(Value::Bool(true))
+		} else {
+			Value::Bool(false)
+		};
+		tagged(info.type_name(), type_fields::VALUE, value)
+	}
+}
+
+/// Wraps `body` the way [`ReflectSerializer`](crate::serde::ReflectSerializer) wraps every value:
+/// `{ "type": <type name>, <kind>: <body> }`.
+fn tagged(type_name: &str, kind: &str, body: Value) -> Value {
+	let mut properties = Map::new();
+	properties.insert(type_fields::TYPE.to_string(), json!({ "const": type_name }));
+	properties.insert(kind.to_string(), body);
+	json!({
+		"title": type_name,
+		"type": "object",
+		"properties": properties,
+		"required": [type_fields::TYPE, kind],
+		"additionalProperties": false,
+	})
+}
+
+fn tuple_items(items: Vec<Value>) -> Value {
+	let len = items.len();
+	json!({
+		"type": "array",
+		"prefixItems": items,
+		"items": false,
+		"minItems": len,
+		"maxItems": len,
+	})
+}
+
+fn unregistered_definition(type_name: &str) -> Value {
+	json!({
+		"title": type_name,
+		"type": "object",
+		"properties": {
+			type_fields::TYPE: { "const": type_name },
+		},
+		"required": [type_fields::TYPE],
+	})
+}
+
+/// Returns a `$ref` to the definition of the type with the given name.
+///
+/// Type names are escaped as JSON pointer tokens and then percent-encoded, since they
+/// usually contain characters such as `<`, `>` or spaces that are not allowed in a URI fragment.
+fn definition_ref(type_name: &str) -> Value {
+	let mut reference = String::from("#/$defs/");
+	for byte in type_name.bytes() {
+		match byte {
+			b'~' => reference.push_str("~0"),
+			b'/' => reference.push_str("~1"),
+			b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'-' | b'.' | b':' => {
+				reference.push(byte as char);
+			},
+			_ => reference.push_str(&format!("%{:02X}", byte)),
+		}
+	}
+	json!({ "$ref": reference })
+}
+
+/// Returns the types referenced by the definition of `type_info`.
+fn dependencies(type_info: &TypeInfo) -> Vec<(TypeId, &'static str)> {
+	match type_info {
+		TypeInfo::Struct(info) => info
+			.iter()
+			.map(|field| (field.type_id(), field.type_name()))
+			.collect(),
+		TypeInfo::TupleStruct(info) => info
+			.iter()
+			.map(|field| (field.type_id(), field.type_name()))
+			.collect(),
+		TypeInfo::Tuple(info) => info
+			.iter()
+			.map(|field| (field.type_id(), field.type_name()))
+			.collect(),
+		TypeInfo::List(info) => vec![(info.item_type_id(), info.item_type_name())],
+		TypeInfo::Array(info) => vec![(info.item_type_id(), info.item_type_name())],
+		// the entries of maps are not described, see `map_definition`
+		TypeInfo::Map(_) | TypeInfo::Value(_) | TypeInfo::Dynamic(_) => Vec::new(),
+	}
+}
+
+/// Returns the schema of the serde representation of well-known value types.
+fn primitive_schema(type_id: TypeId) -> Option<Value> {
+	macro_rules! integer {
+		($($ty:ty),*) => {
+			$(
+				if type_id == TypeId::of::<$ty>() {
+					return Some(json!({
+						"type": "integer",
+						"minimum": <$ty>::MIN,
+						"maximum": <$ty>::MAX,
+					}));
+				}
+			)*
+		};
+	}
+
+	integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+	if type_id == TypeId::of::<u128>() {
+		Some(json!({ "type": "integer", "minimum": 0 }))
+	} else if type_id == TypeId::of::<i128>() {
+		Some(json!({ "type": "integer" }))
+	} else if type_id == TypeId::of::<f32>() || type_id == TypeId::of::<f64>() {
+		Some(json!({ "type": "number" }))
+	} else if type_id == TypeId::of::<bool>() {
+		Some(json!({ "type": "boolean" }))
+	} else if type_id == TypeId::of::<String>() {
+		Some(json!({ "type": "string" }))
+	} else if type_id == TypeId::of::<char>() {
+		Some(json!({ "type": "string", "minLength": 1, "maxLength": 1 }))
+	} else if type_id == TypeId::of::<Duration>() {
+		Some(json!({
+			"type": "object",
+			"properties": {
+				"secs": { "type": "integer", "minimum": 0, "maximum": u64::MAX },
+				"nanos": { "type": "integer", "minimum": 0, "maximum": 999_999_999 },
+			},
+			"required": ["secs", "nanos"],
+			"additionalProperties": false,
+		}))
+	} else {
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{definition_ref, ReflectSchemaGenerator};
+	use crate as bevy_reflect;
+	use crate::{serde::ReflectSerializer, FromReflect, Reflect, TypeRegistry};
+	use bevy_utils::HashMap;
+	use serde_json::{json, Value};
+	use std::time::Instant;
+
+	#[derive(Reflect)]
+	struct Foo {
+		a: u32,
+		b: Bar,
+		c: Vec<Bar>,
+		d: [u8; 2],
+		e: (f32, String),
+		f: HashMap<String, u32>,
+	}
+
+	#[derive(Reflect, FromReflect)]
+	struct Bar(i16);
+
+	fn get_registry() -> TypeRegistry {
+		let mut registry = TypeRegistry::default();
+		registry.register::<Foo>();
+		registry.register::<Bar>();
+		registry.register::<Vec<Bar>>();
+		registry.register::<[u8; 2]>();
+		registry.register::<(f32, String)>();
+		registry.register::<HashMap<String, u32>>();
+		registry.register::<String>();
+		registry.register::<Instant>();
+		registry
+	}
+
+	fn definition<'a>(schema: &'a Value, type_name: &str) -> &'a Value {
+		&schema["$defs"][type_name]
+	}
+
+	#[test]
+	fn struct_schema_matches_serializer_output() {
+		let registry = get_registry();
+		let generator = ReflectSchemaGenerator::new(&registry);
+		let schema = generator.type_schema::<Foo>().unwrap();
+
+		let foo_name = std::any::type_name::<Foo>();
+		assert_eq!(schema["$ref"], definition_ref(foo_name)["$ref"]);
+
+		let foo = definition(&schema, foo_name);
+		assert_eq!(foo["properties"]["type"], json!({ "const": foo_name }));
+		assert_eq!(foo["required"], json!(["type", "struct"]));
+
+		let value = Foo {
+			a: 1,
+			b: Bar(2),
+			c: vec![Bar(3)],
+			d: [4, 5],
+			e: (6.0, "seven".to_string()),
+			f: HashMap::default(),
+		};
+		let serialized = serde_json::to_value(ReflectSerializer::new(&value, &registry)).unwrap();
+		let fields = serialized["struct"].as_object().unwrap();
+		let properties = foo["properties"]["struct"]["properties"]
+			.as_object()
+			.unwrap();
+		assert_eq!(fields.len(), properties.len());
+		for (name, field) in fields {
+			let reference = properties[name]["$ref"].as_str().unwrap();
+			let field_type = field["type"].as_str().unwrap();
+			assert_eq!(reference, definition_ref(field_type)["$ref"]);
+			assert!(definition(&schema, field_type).is_object());
+		}
+	}
+
+	#[test]
+	fn container_schemas() {
+		let registry = get_registry();
+		let generator = ReflectSchemaGenerator::new(&registry);
+		let schema = generator.type_schema::<Foo>().unwrap();
+
+		let bar = definition(&schema, std::any::type_name::<Bar>());
+		assert_eq!(bar["properties"]["tuple_struct"]["maxItems"], 1);
+
+		let array = definition(&schema, std::any::type_name::<[u8; 2]>());
+		assert_eq!(array["properties"]["array"]["minItems"], 2);
+		assert_eq!(array["properties"]["array"]["maxItems"], 2);
+
+		let list = definition(&schema, std::any::type_name::<Vec<Bar>>());
+		assert_eq!(
+			list["properties"]["list"]["items"],
+			definition_ref(std::any::type_name::<Bar>())
+		);
+
+		let map = definition(&schema, std::any::type_name::<HashMap<String, u32>>());
+		assert_eq!(
+			map["properties"]["map"],
+			json!({ "type": "object", "maxProperties": 0 })
+		);
+		// the keys of a map are serialized as objects, which JSON cannot use as keys
+		let mut entries = HashMap::default();
+		entries.insert("a".to_string(), 1u32);
+		assert!(serde_json::to_value(ReflectSerializer::new(&entries, &registry)).is_err());
+
+		let byte = definition(&schema, "u8");
+		assert_eq!(
+			byte["properties"]["value"],
+			json!({ "type": "integer", "minimum": 0, "maximum": 255 })
+		);
+	}
+
+	#[test]
+	fn registry_schema_contains_every_type() {
+		let registry = get_registry();
+		let generator = ReflectSchemaGenerator::new(&registry);
+		let schema = generator.registry_schema();
+
+		assert_eq!(
+			schema["anyOf"].as_array().unwrap().len(),
+			registry.iter().count()
+		);
+		for registration in registry.iter() {
+			assert!(definition(&schema, registration.type_name()).is_object());
+		}
+
+		let not_serializable = definition(&schema, std::any::type_name::<Instant>());
+		assert_eq!(not_serializable["properties"]["value"], json!(false));
+	}
+
+	#[test]
+	fn unregistered_type_has_no_schema() {
+		let registry = TypeRegistry::empty();
+		let generator = ReflectSchemaGenerator::new(&registry);
+		assert!(generator.type_schema::<Foo>().is_none());
+		assert!(generator
+			.type_schema_by_name(std::any::type_name::<Foo>())
+			.is_none());
+	}
+
+	#[test]
+	fn definition_ref_escapes_type_names() {
+		assert_eq!(
+			definition_ref("alloc::vec::Vec<u8>"),
+			json!({ "$ref": "#/$defs/alloc::vec::Vec%3Cu8%3E" })
+		);
+		assert_eq!(
+			definition_ref("a/b~c"),
+			json!({ "$ref": "#/$defs/a~1b~0c" })
+		);
+	}
+}
@@ -5,6 +5,7 @@ use downcast_rs::{impl_downcast, Downcast};
 use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use serde::Deserialize;
 use std::{any::TypeId, fmt::Debug, sync::Arc};
+use thiserror::Error;
 
 /// A registry of reflected types.
 pub struct TypeRegistry {
@@ -12,6 +13,7 @@ pub struct TypeRegistry {
 	short_name_to_id: HashMap<String, TypeId>,
 	full_name_to_id: HashMap<String, TypeId>,
 	ambiguous_names: HashSet<String>,
+	alias_to_id: HashMap<String, TypeId>,
 }
 
 // TODO:  remove this wrapper once we migrate to Atelier Assets and the Scene AssetLoader doesn't
@@ -54,6 +56,7 @@ impl TypeRegistry {
 			short_name_to_id: Default::default(),
 			full_name_to_id: Default::default(),
 			ambiguous_names: Default::default(),
+			alias_to_id: Default::default(),
 		}
 	}
 
@@ -111,6 +114,28 @@ impl TypeRegistry {
 			.insert(registration.type_id(), registration);
 	}
 
+	/// Removes the registration of the type with the given [`TypeId`], and the aliases of the
+	/// type, returning the registration if the type was registered.
+	///
+	/// A short name left ambiguous by the removed type stays ambiguous.
+	pub fn unregister(&mut self, type_id: TypeId) -> Option<TypeRegistration> {
+		let registration = self.registrations.remove(&type_id)?;
+		if self
+			.short_name_to_id
+			.get(registration.short_name())
+			== Some(&type_id)
+		{
+			self
+				.short_name_to_id
+				.remove(registration.short_name());
+		}
+		self
+			.full_name_to_id
+			.remove(registration.type_name());
+		self.alias_to_id.retain(|_, id| *id != type_id);
+		Some(registration)
+	}
+
 	/// Registers the type data `D` for type `T`.
 	///
 	/// Most of the time [`TypeRegistry::register`] can be used instead to register a type you derived [`Reflect`] for.
@@ -138,6 +163,62 @@ impl TypeRegistry {
 		data.insert(D::from_type());
 	}
 
+	/// Registers `alias` as another name of type `T`, such as its name before it was renamed or
+	/// moved to another module, so values written with that name still deserialize.
+	///
+	/// Aliases are only used by the methods looking up types by their full name, when no type
+	/// has that name.
+	pub fn register_type_alias<T: Reflect + 'static>(&mut self, alias: impl Into<String>) {
+		self
+			.alias_to_id
+			.insert(alias.into(), TypeId::of::<T>());
+	}
+
+	/// Returns `true` if an [alias](Self::register_type_alias) was registered for any type.
+	pub fn has_type_aliases(&self) -> bool {
+		!self.alias_to_id.is_empty()
+	}
+
+	/// Sets the version of type `T`, see [`TypeRegistration::set_version`].
+	///
+	/// # Panics
+	///
+	/// Panics if `T` is not registered, or if `version` is lower than the version one of its
+	/// migrations migrates to.
+	pub fn set_type_version<T: Reflect + 'static>(&mut self, version: u32) {
+		self
+			.get_registered_mut::<T>("set_type_version")
+			.set_version(version);
+	}
+
+	/// Adds a migration of the values of type `T` from version `from_version`, see
+	/// [`TypeRegistration::add_migration`].
+	///
+	/// # Panics
+	///
+	/// Panics if `T` is not registered.
+	pub fn add_type_migration<T: Reflect + 'static>(
+		&mut self,
+		from_version: u32,
+		migration: impl Fn(Box<dyn Reflect>) -> Box<dyn Reflect> + Send + Sync + 'static,
+	) {
+		self
+			.get_registered_mut::<T>("add_type_migration")
+			.add_migration(from_version, migration);
+	}
+
+	fn get_registered_mut<T: Reflect + 'static>(&mut self, method: &str) -> &mut TypeRegistration {
+		self
+			.get_mut(TypeId::of::<T>())
+			.unwrap_or_else(|| {
+				panic!(
+					"attempted to call `TypeRegistry::{}` for type `{}` without registering it first",
+					method,
+					std::any::type_name::<T>(),
+				)
+			})
+	}
+
 	/// Returns a reference to the [`TypeRegistration`] of the type with the
 	/// given [`TypeId`].
 	///
@@ -161,22 +242,26 @@ impl TypeRegistry {
 	/// Returns a reference to the [`TypeRegistration`] of the type with the
 	/// given name.
 	///
-	/// If no type with the given name has been registered, returns `None`.
+	/// If no type with the given name or [alias](Self::register_type_alias) has been registered,
+	/// returns `None`.
 	pub fn get_with_name(&self, type_name: &str) -> Option<&TypeRegistration> {
 		self
 			.full_name_to_id
 			.get(type_name)
+			.or_else(|| self.alias_to_id.get(type_name))
 			.and_then(|id| self.get(*id))
 	}
 
 	/// Returns a mutable reference to the [`TypeRegistration`] of the type with
 	/// the given name.
 	///
-	/// If no type with the given name has been registered, returns `None`.
+	/// If no type with the given name or [alias](Self::register_type_alias) has been registered,
+	/// returns `None`.
 	pub fn get_with_name_mut(&mut self, type_name: &str) -> Option<&mut TypeRegistration> {
 		self
 			.full_name_to_id
 			.get(type_name)
+			.or_else(|| self.alias_to_id.get(type_name))
 			.cloned()
 			.and_then(move |id| self.get_mut(id))
 	}
@@ -284,6 +369,26 @@ pub struct TypeRegistration {
 	short_name: String,
 	data: HashMap<TypeId, Box<dyn TypeData>>,
 	type_info: &'static TypeInfo,
+	version: u32,
+	migrations: HashMap<u32, TypeMigration>,
+}
+
+/// A migration of the reflected values of a type from a version to the next one, see
+/// [`TypeRegistration::add_migration`].
+pub type TypeMigration = Arc<dyn Fn(Box<dyn Reflect>) -> Box<dyn Reflect> + Send + Sync>;
+
+/// An error migrating a reflected value to the current version of its type.
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum MigrationError {
+	/// The value was written by a newer version of the type, which can't be migrated back.
+	#[error(
+		"version {version} of `{type_name}` is newer than its current version {current_version}"
+	)]
+	NewerVersion {
+		type_name: &'static str,
+		version: u32,
+		current_version: u32,
+	},
 }
 
 impl TypeRegistration {
@@ -338,7 +443,80 @@ impl TypeRegistration {
 			data: HashMap::default(),
 			short_name: bevy_utils::get_short_name(type_name),
 			type_info: T::type_info(),
+			version: 0,
+			migrations: HashMap::default(),
+		}
+	}
+
+	/// Returns the version of the type, 0 unless it was changed.
+	///
+	/// Serialized data records the versions of its types, so data written by older versions can
+	/// be [migrated](Self::migrate).
+	pub fn version(&self) -> u32 {
+		self.version
+	}
+
+	/// Sets the version of the type, to bump when its serialized data changes.
+	///
+	/// Versions without a [migration](Self::add_migration) leave the values unchanged, for
+	/// example when a field with a default value is added.
+	///
+	/// # Panics
+	///
+	/// Panics if `version` is lower than the version a migration of the type migrates to.
+	pub fn set_version(&mut self, version: u32) {
+		if let Some(from_version) = self
+			.migrations
+			.keys()
+			.copied()
+			.find(|from_version| *from_version >= version)
+		{
+			panic!(
+				"attempted to set the version of type `{}` to {}, below the version {} of one of its migrations",
+				self.type_name(),
+				version,
+				from_version + 1,
+			);
 		}
+		self.version = version;
+	}
+
+	/// Adds a migration transforming the reflected values of version `from_version` of the type,
+	/// usually dynamic values such as a [`DynamicStruct`](crate::DynamicStruct), into values of
+	/// version `from_version + 1`.
+	///
+	/// The version of the type is raised to `from_version + 1` if it is lower.
+	pub fn add_migration(
+		&mut self,
+		from_version: u32,
+		migration: impl Fn(Box<dyn Reflect>) -> Box<dyn Reflect> + Send + Sync + 'static,
+	) {
+		self
+			.migrations
+			.insert(from_version, Arc::new(migration));
+		self.version = self.version.max(from_version + 1);
+	}
+
+	/// Migrates a reflected value of version `version` of the type to its current version, by
+	/// applying the migrations of each version in order.
+	pub fn migrate(
+		&self,
+		mut value: Box<dyn Reflect>,
+		version: u32,
+	) -> Result<Box<dyn Reflect>, MigrationError> {
+		if version > self.version {
+			return Err(MigrationError::NewerVersion {
+				type_name: self.type_name(),
+				version,
+				current_version: self.version,
+			});
+		}
+		for version in version..self.version {
+			if let Some(migration) = self.migrations.get(&version) {
+				value = migration(value);
+			}
+		}
+		Ok(value)
 	}
 
 	/// Returns the [short name] of the type.
@@ -367,6 +545,8 @@ impl Clone for TypeRegistration {
 			data,
 			short_name: self.short_name.clone(),
 			type_info: self.type_info,
+			version: self.version,
+			migrations: self.migrations.clone(),
 		}
 	}
 }
@@ -543,7 +723,10 @@ impl<T: Reflect> FromType<T> for ReflectFromPtr {
 mod test {
 	use std::ptr::NonNull;
 
-	use crate::{GetTypeRegistration, ReflectFromPtr, TypeRegistration};
+	use crate::{
+		DynamicStruct, GetTypeRegistration, MigrationError, ReflectFromPtr, Struct, TypeRegistration,
+		TypeRegistry,
+	};
 	use bevy_ptr::{Ptr, PtrMut};
 	use bevy_utils::HashMap;
 
@@ -632,4 +815,102 @@ mod test {
 			"Option<HashMap<Option<String>, (String, Option<String>)>>"
 		);
 	}
+
+	#[test]
+	fn type_migrations() {
+		#[derive(Reflect)]
+		struct Player {
+			health: f32,
+		}
+
+		let mut registration = TypeRegistration::of::<Player>();
+		assert_eq!(registration.version(), 0);
+		// version 0 stored the health as a percentage, version 1 renamed `hp` to `health`
+		registration.add_migration(0, |value| {
+			let value = value.downcast::<DynamicStruct>().unwrap();
+			let hp = *value
+				.field("hp")
+				.unwrap()
+				.downcast_ref::<f32>()
+				.unwrap();
+			let mut migrated = DynamicStruct::default();
+			migrated.insert("hp", hp * 2.0);
+			Box::new(migrated)
+		});
+		registration.add_migration(1, |value| {
+			let value = value.downcast::<DynamicStruct>().unwrap();
+			let mut migrated = DynamicStruct::default();
+			migrated.insert_boxed("health", value.field("hp").unwrap().clone_value());
+			Box::new(migrated)
+		});
+		registration.set_version(3);
+
+		let mut old = DynamicStruct::default();
+		old.insert("hp", 40.0f32);
+		let migrated = registration.migrate(Box::new(old), 0).unwrap();
+		let mut player = Player { health: 0.0 };
+		player.apply(&*migrated);
+		assert_eq!(player.health, 80.0);
+
+		assert_eq!(
+			registration
+				.migrate(Box::new(DynamicStruct::default()), 4)
+				.err(),
+			Some(MigrationError::NewerVersion {
+				type_name: std::any::type_name::<Player>(),
+				version: 4,
+				current_version: 3,
+			})
+		);
+	}
+
+	#[test]
+	#[should_panic]
+	fn set_version_below_migration() {
+		#[derive(Reflect)]
+		struct Player;
+
+		let mut registration = TypeRegistration::of::<Player>();
+		registration.add_migration(1, |value| value);
+		registration.set_version(1);
+	}
+
+	#[test]
+	fn unregister() {
+		#[derive(Reflect)]
+		struct Player;
+
+		let mut registry = TypeRegistry::empty();
+		registry.register::<Player>();
+		registry.register_type_alias::<Player>("old_game::Player");
+		assert!(registry
+			.unregister(std::any::TypeId::of::<Player>())
+			.is_some());
+		assert!(registry.get_with_short_name("Player").is_none());
+		assert!(registry
+			.get_with_name("old_game::Player")
+			.is_none());
+		assert!(registry
+			.unregister(std::any::TypeId::of::<Player>())
+			.is_none());
+	}
+
+	#[test]
+	fn type_aliases() {
+		#[derive(Reflect)]
+		struct Player;
+
+		let mut registry = TypeRegistry::empty();
+		registry.register::<Player>();
+		registry.register_type_alias::<Player>("old_game::Player");
+		assert_eq!(
+			registry
+				.get_with_name("old_game::Player")
+				.map(|registration| registration.type_id()),
+			Some(std::any::TypeId::of::<Player>())
+		);
+		assert!(registry
+			.get_with_name("old_game::Enemy")
+			.is_none());
+	}
 }
@@ -18,6 +18,8 @@ pub struct CustomAsset {
 pub struct CustomAssetLoader;
 
 impl AssetLoader for CustomAssetLoader {
+	type Settings = ();
+
 	fn load<'a>(
 		&'a self,
 		bytes: &'a [u8],
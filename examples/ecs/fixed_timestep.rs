@@ -8,17 +8,21 @@ use bevy::{
 const LABEL: &str = "my_fixed_timestep";
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
-struct FixedUpdateStage;
+struct TwiceASecondStage;
 
 fn main() {
 	let mut app = App::new();
 	app.add_plugins(DefaultPlugins);
+	// the built-in fixed update stage runs 60 times per second by default
+	app.insert_resource(FixedTime::from_steps_per_second(4.0));
 	// this system will run once every update (it should match your screen's refresh rate)
 	app.add_system(frame_update);
+	// this system will run four times a second
+	app.add_system_to_stage(FixedUpdateStage, fixed_update);
 	// add a new stage that runs twice a second
 	app.add_stage_after(
 		CoreStage::Update,
-		FixedUpdateStage,
+		TwiceASecondStage,
 		SystemStage::parallel()
 			.with_run_criteria(
 				FixedTimestep::step(0.5)
@@ -26,19 +30,33 @@ fn main() {
 					// FixedTimestep state from within a system
 					.with_label(LABEL),
 			)
-			.with_system(fixed_update),
+			.with_system(twice_a_second),
 	);
 	app.run();
 }
 
-fn frame_update(mut last_time: Local<f64>, time: Res<Time>) {
+fn frame_update(mut last_time: Local<f64>, time: Res<Time>, fixed_time: Res<FixedTime>) {
 	info!("update: {}", time.seconds_since_startup() - *last_time);
+	info!("  fixed update alpha: {}", fixed_time.alpha());
 	*last_time = time.seconds_since_startup();
 }
 
-fn fixed_update(mut last_time: Local<f64>, time: Res<Time>, fixed_timesteps: Res<FixedTimesteps>) {
+fn fixed_update(mut last_time: Local<f64>, time: Res<Time>, fixed_time: Res<FixedTime>) {
 	info!(
-		"fixed_update: {}",
+		"fixed_update: {}, step: {}",
+		time.seconds_since_startup() - *last_time,
+		fixed_time.step_seconds(),
+	);
+	*last_time = time.seconds_since_startup();
+}
+
+fn twice_a_second(
+	mut last_time: Local<f64>,
+	time: Res<Time>,
+	fixed_timesteps: Res<FixedTimesteps>,
+) {
+	info!(
+		"twice_a_second: {}",
 		time.seconds_since_startup() - *last_time,
 	);
 
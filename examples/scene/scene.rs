@@ -7,6 +7,7 @@ fn main() {
 	app.add_plugins(DefaultPlugins);
 	app.register_type::<ComponentA>();
 	app.register_type::<ComponentB>();
+	app.register_type::<ResourceA>();
 	app.add_startup_system(save_scene_system.exclusive_system());
 	app.add_startup_system(load_scene_system);
 	app.add_startup_system(infotext_system);
@@ -39,6 +40,14 @@ struct ComponentB {
 	pub _time_since_startup: Duration,
 }
 
+// Resources can be part of scenes too. Registered resources are reflected with
+// `#[reflect(Resource)]` and are inserted into the world when the scene is spawned.
+#[derive(Reflect, Default)]
+#[reflect(Resource)]
+struct ResourceA {
+	pub score: u32,
+}
+
 impl FromWorld for ComponentB {
 	fn from_world(world: &mut World) -> Self {
 		let time = world.resource::<Time>();
@@ -89,6 +98,7 @@ fn save_scene_system(world: &mut World) {
 	scene_world
 		.spawn()
 		.insert_bundle((ComponentA { x: 3.0, y: 4.0 },));
+	scene_world.insert_resource(ResourceA { score: 1 });
 
 	// The TypeRegistry resource contains information about all registered types (including
 	// components). This is used to construct scenes.
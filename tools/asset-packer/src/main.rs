@@ -0,0 +1,49 @@
+//! Packs an asset folder into a single archive read by `bevy::asset::ArchiveAssetIo`.
+//!
+//! Both the `assets` folder and the folder of processed assets (`imported_assets` by default)
+//! can be packed, including their `.meta` files.
+
+use std::{fs::File, io::BufWriter, path::PathBuf};
+
+use bevy::asset::{ArchiveCompression, ArchiveWriter};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+struct Args {
+	#[clap(value_parser, default_value = "assets")]
+	/// The folder to pack
+	input: PathBuf,
+
+	#[clap(short, long, value_parser, default_value = "assets.pack")]
+	/// The archive to write
+	output: PathBuf,
+
+	#[clap(long, value_parser)]
+	/// Store the files without compressing them
+	no_compression: bool,
+}
+
+fn main() {
+	let cli = Args::parse();
+
+	let compression = if cli.no_compression {
+		ArchiveCompression::None
+	} else {
+		ArchiveCompression::Deflate
+	};
+	let mut writer = ArchiveWriter::new();
+	writer
+		.add_directory(&cli.input, compression)
+		.unwrap_or_else(|err| panic!("failed to read {:?}: {}", cli.input, err));
+
+	let file = File::create(&cli.output).expect("failed to create output file");
+	writer
+		.write(BufWriter::new(file))
+		.expect("failed to write archive");
+	println!(
+		"packed {} files from {:?} into {:?}",
+		writer.len(),
+		cli.input,
+		cli.output
+	);
+}
@@ -0,0 +1,65 @@
+//! Exports a JSON Schema describing the reflection serialization format of Bevy's built-in types.
+//!
+//! The generated schema can be used by external tools to validate scene files before they are
+//! loaded. Games registering their own types should use `ReflectSchemaGenerator` directly on
+//! their `TypeRegistry` instead.
+
+use std::{error::Error, fs::File, io::Write, path::PathBuf};
+
+use bevy::{
+	asset::AssetPlugin,
+	input::InputPlugin,
+	prelude::*,
+	reflect::{serde::ReflectSchemaGenerator, TypeRegistry},
+	window::WindowPlugin,
+};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+struct Args {
+	#[clap(short, long, value_parser)]
+	/// Only export the schema of the type with this full type name
+	type_name: Option<String>,
+
+	#[clap(short, long, value_parser)]
+	/// Write the schema to this file instead of stdout
+	output: Option<PathBuf>,
+}
+
+fn main() {
+	if let Err(err) = run(Args::parse()) {
+		eprintln!("error: {}", err);
+		std::process::exit(1);
+	}
+}
+
+fn run(cli: Args) -> Result<(), Box<dyn Error>> {
+	// Building the plugins registers their reflected types, nothing needs to run
+	let mut app = App::new();
+	app.add_plugins(MinimalPlugins);
+	app.add_plugin(TransformPlugin);
+	app.add_plugin(HierarchyPlugin);
+	app.add_plugin(InputPlugin);
+	app.add_plugin(WindowPlugin);
+	app.add_plugin(AssetPlugin);
+
+	let registry = app.world.resource::<TypeRegistry>().read();
+	let generator = ReflectSchemaGenerator::new(&registry);
+	let schema = match &cli.type_name {
+		Some(type_name) => generator
+			.type_schema_by_name(type_name)
+			.ok_or_else(|| format!("type '{}' is not registered", type_name))?,
+		None => generator.registry_schema(),
+	};
+
+	let json = serde_json::to_string_pretty(&schema)?;
+	match cli.output {
+		Some(path) => {
+			let mut file =
+				File::create(&path).map_err(|err| format!("failed to create {:?}: {}", path, err))?;
+			writeln!(file, "{}", json).map_err(|err| format!("failed to write {:?}: {}", path, err))?;
+		},
+		None => println!("{}", json),
+	}
+	Ok(())
+}